@@ -0,0 +1,46 @@
+//! Replay a recorded input log headlessly and verify it stays deterministic.
+//!
+//! ```text
+//! cargo run --example replay --no-default-features -- colony.ptil
+//! ```
+
+use pettoy::replay::Replayer;
+
+fn main() {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: replay <input-log>");
+        std::process::exit(2);
+    };
+
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("failed to read {path}: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let mut replayer = match Replayer::new(bytes) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("{path}: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    match replayer.run_to_end() {
+        Ok(ticks) => {
+            let sim = replayer.sim();
+            println!(
+                "replayed {ticks} ticks (seed {}): {} cats, checksum {:016x}",
+                sim.seed,
+                sim.cat_count(),
+                sim.checksum()
+            );
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
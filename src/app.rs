@@ -1,160 +1,528 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use glam::Vec2;
 use instant::Instant;
 use winit::application::ApplicationHandler;
 use winit::event::WindowEvent;
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
+use winit::monitor::MonitorHandle;
 use winit::window::{Window, WindowAttributes, WindowId, WindowLevel};
 
-use crate::cat;
-use crate::click::ClickState;
-use crate::debug::timer::{SystemPhase, SystemTimers};
-use crate::debug::DebugOverlay;
-use crate::ecs::components::{
-    Appearance, BehaviorState, CatName, CatState, GiftCarrier, Personality, Position, PrevPosition,
-    SleepingPile, SpawnAnimation,
+use pettoy::ecs::components::{
+    Appearance, BehaviorState, CatId, CatName, CatState, GiftCarrier, Lifetime, Needs, Personality,
+    Position, PrevPosition, Prey, PreyKind, Shattered, SleepingPile, SpawnAnimation, ToySprite,
+    Trust,
 };
-use crate::ecs::systems;
-use crate::ecs::systems::interaction::InteractionBuffers;
-use crate::ecs::systems::mouse::CursorState;
-use crate::ecs::systems::window_aware::DesktopWindow;
-use crate::heatmap::Heatmap;
-use crate::mode::{AtkAction, ModeState};
-use crate::platform;
-use crate::render::instance::CatInstance;
-use crate::render::trail::TrailSystem;
-use crate::render::GpuState;
-use crate::spatial::{CatSnapshot, SpatialHash};
-use crate::daynight::DayNightState;
+use pettoy::ecs::systems::window_aware::DesktopWindow;
+use pettoy::config::{Config, ConfigWatcher};
+use pettoy::desktop::{Desktop, Monitor};
+use pettoy::input::{Action, ActionSet, ActionState};
+use pettoy::journal::Journal;
+use pettoy::relations;
+use pettoy::mode::AppMode;
+use pettoy::presence::Presence;
+use pettoy::sim::{SimInput, Simulation, TICK_RATE};
+use pettoy::timer::{SystemPhase, SystemTimers};
+
+use crate::debug::{Acquaintances, DebugOverlay};
 use crate::particles::ParticleSystem;
-use crate::toy::{Boxes, Glasses, YarnBalls};
-use crate::tray::{TrayCommand, TrayIcon};
+use crate::platform::{self, Platform, TrayCommand, WindowRect};
+use crate::render::instance::{CatInstance, UNCLIPPED};
+use crate::render::occlusion::Occlusion;
+use crate::render::trail::TrailSystem;
+use crate::render::{GpuState, Target};
 
-/// Target simulation tick rate (seconds per tick).
-const TICK_RATE: f64 = 1.0 / 60.0;
 /// Max accumulated time before we clamp (prevents spiral of death).
 const MAX_ACCUMULATOR: f64 = 0.25;
-/// How many cats to spawn on startup.
-const INITIAL_CAT_COUNT: usize = 20;
-/// Target population that the colony grows toward.
-const TARGET_CAT_COUNT: usize = 1000;
-/// Cats spawned per second during growth phase.
-const POPULATION_GROWTH_RATE: f64 = 2.0;
-/// Seconds to wait before population starts growing.
-const GROWTH_DELAY: f64 = 5.0;
-/// Spatial hash cell size — 2x interaction radius.
-const SPATIAL_CELL_SIZE: f32 = 128.0;
-/// Spatial hash table size (prime-ish for good distribution).
-const SPATIAL_TABLE_SIZE: usize = 1024;
+/// Seconds between colony autosaves.
+const AUTOSAVE_INTERVAL: f64 = 120.0;
+/// Seconds between config file change checks.
+const CONFIG_POLL_INTERVAL: f64 = 1.0;
+/// Seconds between desktop window list refreshes. The first one comes
+/// right away, so the colony starts out knowing which windows are open.
+const WINDOW_REFRESH: f64 = 2.0;
+/// How often (seconds) hotkeys and windows are still polled while the cats
+/// are away and nothing is drawn.
+const PRESENCE_POLL: f64 = 0.1;
+
+// ---------------------------------------------------------------------------
+// Options
+// ---------------------------------------------------------------------------
+
+/// Command-line options.
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Fixed RNG seed (`--seed N`). Random when absent.
+    pub seed: Option<u64>,
+    /// Write a replayable input log here on exit (`--record PATH`).
+    pub record: Option<PathBuf>,
+    /// Write every cat's journal here as JSON on exit (`--journal PATH`).
+    pub journal: Option<PathBuf>,
+    /// Colony save file (`--save PATH`). Defaults to the per-user data dir.
+    pub save: Option<PathBuf>,
+    /// Start a fresh colony and never touch the save file (`--no-save`).
+    pub no_save: bool,
+    /// Tuning config file (`--config PATH`). Defaults to the per-user data dir.
+    pub config: Option<PathBuf>,
+}
+
+impl Options {
+    /// Parse options from the process arguments.
+    pub fn from_args() -> Result<Self, String> {
+        let mut opts = Self::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    let value = args.next().ok_or("--seed needs a value")?;
+                    let seed = value
+                        .parse()
+                        .map_err(|_| format!("invalid seed: {value}"))?;
+                    opts.seed = Some(seed);
+                }
+                "--record" => {
+                    let path = args.next().ok_or("--record needs a path")?;
+                    opts.record = Some(PathBuf::from(path));
+                }
+                "--journal" => {
+                    let path = args.next().ok_or("--journal needs a path")?;
+                    opts.journal = Some(PathBuf::from(path));
+                }
+                "--save" => {
+                    let path = args.next().ok_or("--save needs a path")?;
+                    opts.save = Some(PathBuf::from(path));
+                }
+                "--no-save" => opts.no_save = true,
+                "--config" => {
+                    let path = args.next().ok_or("--config needs a path")?;
+                    opts.config = Some(PathBuf::from(path));
+                }
+                other => return Err(format!("unknown argument: {other}")),
+            }
+        }
+        Ok(opts)
+    }
+}
 
 // ---------------------------------------------------------------------------
 // App
 // ---------------------------------------------------------------------------
 
-/// Top-level application state.
+/// Top-level application state: the OS-facing shell around a [`Simulation`].
 struct App {
+    /// Overlay on the primary monitor; hosts the debug overlay and drives
+    /// the frame loop. The other monitors' overlays live in `gpu.surfaces`.
     window: Option<Arc<Window>>,
     gpu: Option<GpuState>,
+    // Layer-shell overlays that replace the windows on Wayland; after `gpu`
+    // so its swapchains are dropped before the surfaces they draw to
+    #[cfg(all(target_os = "linux", feature = "wayland"))]
+    layers: Option<platform::wayland::LayerShell>,
+
+    // Monitor layout the overlays were built for (primary first)
+    monitors: Vec<Monitor>,
 
     // Debug overlay (initialized after GPU)
     debug: Option<DebugOverlay>,
 
-    // ECS
-    world: hecs::World,
-
-    // Spatial hash
-    spatial_grid: SpatialHash,
-
-    // Snapshot cache for interaction queries
-    snapshots: Vec<CatSnapshot>,
-
-    // Interaction buffers (pre-allocated, reused each tick)
-    interaction_bufs: InteractionBuffers,
-
-    // Cursor tracking (speed, still timer)
-    cursor_state: CursorState,
-
-    // Mode system (Work/Play/Zen/Chaos + AFK escalation)
-    mode_state: ModeState,
-
-    // Click interaction state (startle, treats, laser)
-    click_state: ClickState,
+    // Colony simulation (ECS, toys, modes, day/night)
+    sim: Simulation,
 
     // Visual systems
     trail_system: TrailSystem,
-    heatmap: Heatmap,
-
-    // Toys
-    yarn_balls: YarnBalls,
-    boxes: Boxes,
-    glasses: Glasses,
 
-    // Emotion particles
+    // Emotion particles (cosmetic, own RNG so they never perturb the sim)
     particles: ParticleSystem,
+    fx_rng: fastrand::Rng,
 
-    // Day/night cycle
-    daynight: DayNightState,
+    // OS input, window enumeration, overlay styling and tray icon
+    platform: Box<dyn Platform>,
 
-    // System tray icon
-    tray: TrayIcon,
+    // Hotkey edge detection, and toy actions waiting for the next tick
+    actions: ActionState,
+    pending_toys: ActionSet,
+    // Cursor as of the latest tick, in colony pixels
+    last_mouse: Vec2,
 
-    // Window platforms (periodically refreshed)
-    desktop_windows: Vec<DesktopWindow>,
-    window_refresh_timer: f64,
+    // What the debug overlay's mode rule line was built from
+    mode_rule_key: Option<(Option<usize>, Option<usize>, bool, u32)>,
 
-    // RNG (shared, deterministic per session)
-    rng: fastrand::Rng,
+    // Window platforms and monitor layout (periodically refreshed)
+    window_refresh_timer: f64,
+    // Which windows sprites are drawn behind
+    occlusion: Occlusion,
+    // Fading out for fullscreen apps and do-not-disturb; `blanked` once an
+    // empty frame has replaced the last visible one
+    presence: Presence,
+    blanked: bool,
 
     // Fixed timestep
     last_frame_time: Option<Instant>,
     accumulator: f64,
-    tick_count: u64,
-
-    // Population growth
+    // Previous `about_to_wait`, which drives the slow timers
+    last_wake: Option<Instant>,
     elapsed_time: f64,
-    spawn_accumulator: f64,
-
-    // Screen dimensions
-    screen_w: u32,
-    screen_h: u32,
 
     // Reusable instance buffer (avoid per-frame allocation)
     instance_buf: Vec<CatInstance>,
+
+    // Where to write the input log on exit, if recording
+    record_path: Option<PathBuf>,
+    journal_path: Option<PathBuf>,
+
+    // Colony persistence (None = disabled)
+    save_path: Option<PathBuf>,
+    autosave_timer: f64,
+
+    // Tuning config hot reload
+    config_watcher: Option<ConfigWatcher>,
+    config_poll_timer: f64,
 }
 
 impl App {
-    fn new() -> Self {
+    fn new(opts: Options) -> Self {
+        let mut sim = match opts.seed {
+            Some(seed) => Simulation::with_seed(1, 1, seed),
+            None => Simulation::new(1, 1),
+        };
+        log::info!("Simulation seed: {}", sim.seed);
+
+        // Load tuning before anything is spawned or recorded.
+        let mut config_watcher = opts
+            .config
+            .or_else(Config::default_path)
+            .map(ConfigWatcher::new);
+        if let Some(watcher) = &mut config_watcher {
+            apply_config_change(&mut sim, watcher);
+        }
+
+        if opts.record.is_some() {
+            sim.start_recording();
+        }
+
+        // A replay starts from an empty colony, so recording runs skip the save.
+        let save_path = if opts.no_save || opts.record.is_some() {
+            None
+        } else {
+            opts.save.or_else(pettoy::save::default_path)
+        };
+
         Self {
             window: None,
             gpu: None,
+            #[cfg(all(target_os = "linux", feature = "wayland"))]
+            layers: None,
+            monitors: Vec::new(),
             debug: None,
-            world: hecs::World::new(),
-            spatial_grid: SpatialHash::new(SPATIAL_CELL_SIZE, SPATIAL_TABLE_SIZE),
-            snapshots: Vec::with_capacity(INITIAL_CAT_COUNT),
-            interaction_bufs: InteractionBuffers::new(INITIAL_CAT_COUNT),
-            cursor_state: CursorState::new(),
-            mode_state: ModeState::new(),
-            click_state: ClickState::new(),
+            sim,
             trail_system: TrailSystem::new(),
-            heatmap: Heatmap::new(1.0, 1.0),
-            yarn_balls: YarnBalls::new(),
-            boxes: Boxes::new(),
-            glasses: Glasses::new(),
             particles: ParticleSystem::new(),
-            daynight: DayNightState::new(),
-            tray: TrayIcon::new(),
-            desktop_windows: Vec::new(),
-            window_refresh_timer: 0.0,
-            rng: fastrand::Rng::new(),
+            fx_rng: fastrand::Rng::new(),
+            platform: platform::native(),
+            actions: ActionState::default(),
+            pending_toys: ActionSet::default(),
+            last_mouse: Vec2::ZERO,
+            mode_rule_key: None,
+            window_refresh_timer: WINDOW_REFRESH,
+            occlusion: Occlusion::default(),
+            presence: Presence::default(),
+            blanked: false,
             last_frame_time: None,
             accumulator: 0.0,
-            tick_count: 0,
+            last_wake: None,
             elapsed_time: 0.0,
-            spawn_accumulator: 0.0,
-            screen_w: 0,
-            screen_h: 0,
-            instance_buf: Vec::with_capacity(INITIAL_CAT_COUNT),
+            instance_buf: Vec::with_capacity(1024),
+            record_path: opts.record,
+            journal_path: opts.journal,
+            save_path,
+            autosave_timer: 0.0,
+            config_watcher,
+            config_poll_timer: 0.0,
+        }
+    }
+
+    /// Restore the saved colony, if there is one. Returns false to start fresh.
+    fn load_colony(&mut self) -> bool {
+        let Some(path) = &self.save_path else {
+            return false;
+        };
+        if !path.exists() {
+            return false;
+        }
+        match pettoy::save::load_from(&mut self.sim, path) {
+            Ok(count) => {
+                log::info!("Restored {count} cats from {}", path.display());
+                count > 0
+            }
+            Err(e) => {
+                log::warn!("Ignoring unreadable colony save {}: {e}", path.display());
+                false
+            }
+        }
+    }
+
+    /// Write the colony to disk, if persistence is enabled.
+    fn save_colony(&self) {
+        let Some(path) = &self.save_path else {
+            return;
+        };
+        match pettoy::save::save_to(&self.sim, path) {
+            Ok(()) => log::info!("Saved {} cats to {}", self.sim.cat_count(), path.display()),
+            Err(e) => log::error!("Failed to save colony to {}: {e}", path.display()),
+        }
+    }
+
+    /// Flush the input recording to disk, if one is running.
+    fn save_recording(&mut self) {
+        let (Some(path), Some(log)) = (&self.record_path, self.sim.take_recording()) else {
+            return;
+        };
+        match std::fs::write(path, &log) {
+            Ok(()) => log::info!(
+                "Input log: {} ticks, {} bytes -> {}",
+                self.sim.tick_count,
+                log.len(),
+                path.display()
+            ),
+            Err(e) => log::error!("Failed to write input log {}: {e}", path.display()),
+        }
+    }
+
+    /// Export the cats' journals as JSON, if asked to.
+    fn save_journal(&self) {
+        let Some(path) = &self.journal_path else {
+            return;
+        };
+        match std::fs::write(path, pettoy::journal::export_json(&self.sim.world)) {
+            Ok(()) => log::info!(
+                "Journals for {} cats -> {}",
+                self.sim.cat_count(),
+                path.display()
+            ),
+            Err(e) => log::error!("Failed to write journals {}: {e}", path.display()),
+        }
+    }
+
+    /// A cat's top three friends and rivals by name, for the tooltip.
+    fn relationships_of(sim: &Simulation, id: CatId) -> (Acquaintances, Acquaintances) {
+        let others = sim.relations.of(id);
+        let friends: Vec<(CatId, f32)> = others
+            .iter()
+            .copied()
+            .take_while(|&(_, a)| a >= relations::FRIEND)
+            .take(3)
+            .collect();
+        let rivals: Vec<(CatId, f32)> = others
+            .iter()
+            .rev()
+            .copied()
+            .take_while(|&(_, a)| a <= relations::RIVAL)
+            .take(3)
+            .collect();
+        if friends.is_empty() && rivals.is_empty() {
+            return Default::default();
+        }
+        let mut names = std::collections::HashMap::new();
+        for (_, (other, name)) in sim.world.query::<(&CatId, &CatName)>().iter() {
+            if friends.iter().chain(&rivals).any(|&(i, _)| i == *other) {
+                names.insert(*other, name.0.clone());
+            }
+        }
+        let named = |cats: Vec<(CatId, f32)>| {
+            cats.into_iter()
+                .filter_map(|(i, a)| Some((names.get(&i)?.clone(), a)))
+                .collect()
+        };
+        (named(friends), named(rivals))
+    }
+
+    /// Create a hidden, click-through overlay window covering `monitor`.
+    fn create_overlay(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        monitor: &MonitorHandle,
+    ) -> Arc<Window> {
+        // No with_transparent(true) — that sets WS_EX_LAYERED which creates
+        // a GDI backing surface that conflicts with DirectComposition.
+        // Transparency comes from wgpu's DxgiFromVisual + PreMultiplied alpha.
+        // Start hidden so DWM doesn't cache stale frame state before our
+        // overlay style changes take effect.
+        let attrs = WindowAttributes::default()
+            .with_title("PetToy")
+            .with_decorations(false)
+            .with_visible(false)
+            .with_window_level(WindowLevel::AlwaysOnTop)
+            .with_inner_size(monitor.size())
+            .with_position(monitor.position());
+        // X11 needs an ARGB visual for per-pixel transparency.
+        #[cfg(target_os = "linux")]
+        let attrs = attrs.with_transparent(true);
+
+        let window = Arc::new(
+            event_loop
+                .create_window(attrs)
+                .expect("failed to create window"),
+        );
+        self.platform.setup_overlay(&window);
+
+        let size = window.inner_size();
+        log::info!(
+            "Overlay window created: {}x{} on {:?}",
+            size.width,
+            size.height,
+            monitor.name().unwrap_or_default()
+        );
+        window
+    }
+
+    /// Give every monitor after the primary its own overlay surface, then
+    /// move the colony onto the monitors that got one.
+    fn attach_monitors(&mut self, event_loop: &ActiveEventLoop, monitors: &[MonitorHandle]) {
+        let mut rects = vec![monitor_rect(&monitors[0])];
+        for monitor in &monitors[1..] {
+            let window = self.create_overlay(event_loop, monitor);
+            let rect = monitor_rect(monitor);
+            let Some(gpu) = &mut self.gpu else {
+                return;
+            };
+            let position = Vec2::new(rect.left, rect.top);
+            let scale = monitor.scale_factor() as f32;
+            if gpu.add_surface(Target::Window(window.clone()), position, scale) {
+                window.set_visible(true);
+                rects.push(rect);
+            }
+        }
+
+        self.monitors = monitors.iter().map(monitor_rect).collect();
+        self.spread_colony(&rects);
+    }
+
+    /// Lay the colony out across the monitors that got an overlay.
+    fn spread_colony(&mut self, rects: &[Monitor]) {
+        self.sim.set_desktop(Desktop::from_virtual(rects));
+        let size = self.sim.desktop.size();
+        log::info!(
+            "Colony spans {} monitor(s), {}x{} virtual desktop",
+            rects.len(),
+            size.x,
+            size.y
+        );
+    }
+
+    /// Cover every output with a layer surface instead of windows, on a
+    /// Wayland session without XWayland. Returns false to fall back to a
+    /// plain window.
+    #[cfg(all(target_os = "linux", feature = "wayland"))]
+    fn open_layers(&mut self) -> bool {
+        if !platform::wayland::wanted() {
+            return false;
         }
+        let Some(layers) = platform::wayland::LayerShell::connect() else {
+            log::warn!("Falling back to a plain window");
+            return false;
+        };
+        let Some(first) = layers.overlays().next() else {
+            log::warn!("No Wayland output to cover; falling back to a plain window");
+            return false;
+        };
+        self.gpu = Some(GpuState::new(Target::Layer(first), first.position, first.scale));
+        self.layers = Some(layers);
+        log::info!("wgpu + cat pipeline initialized (no debug overlay on the layer shell)");
+        self.attach_layers();
+        true
+    }
+
+    /// Rebuild every surface from the layer shell's outputs and move the
+    /// colony onto them.
+    #[cfg(all(target_os = "linux", feature = "wayland"))]
+    fn attach_layers(&mut self) {
+        let (Some(gpu), Some(layers)) = (&mut self.gpu, &self.layers) else {
+            return;
+        };
+        gpu.surfaces.clear();
+        let mut rects = Vec::new();
+        for overlay in layers.overlays() {
+            let position = overlay.position;
+            if gpu.add_surface(Target::Layer(overlay), position, overlay.scale) {
+                let (w, h) = (overlay.width as f32, overlay.height as f32);
+                rects.push(Monitor::new(position.x, position.y, w, h));
+            }
+        }
+        if rects.is_empty() {
+            log::warn!("No Wayland output left to draw on");
+            return;
+        }
+        self.monitors = rects.clone();
+        self.spread_colony(&rects);
+    }
+
+    /// Rebuild the overlays after monitors were added, removed or moved.
+    /// The primary overlay is kept (it owns the debug overlay) and moved to
+    /// the new primary monitor; every other overlay is recreated.
+    fn relayout(&mut self, event_loop: &ActiveEventLoop, monitors: &[MonitorHandle]) {
+        log::info!("Monitor layout changed ({} connected)", monitors.len());
+        let primary = &monitors[0];
+        let rect = monitor_rect(primary);
+        if let (Some(gpu), Some(window)) = (&mut self.gpu, &self.window) {
+            gpu.surfaces.truncate(1);
+            let surface = &mut gpu.surfaces[0];
+            surface.position = Vec2::new(rect.left, rect.top);
+            surface.scale = primary.scale_factor() as f32;
+            window.set_outer_position(primary.position());
+            if let Some(size) = window.request_inner_size(primary.size()) {
+                gpu.resize(window.id(), size.width, size.height);
+            }
+        }
+        self.attach_monitors(event_loop, monitors);
+    }
+
+    /// Create the primary overlay window with the GPU state and debug
+    /// overlay around it, then the other monitors' overlays.
+    fn open_windows(&mut self, event_loop: &ActiveEventLoop) {
+        let monitors = connected_monitors(event_loop);
+        let primary = monitors.first().expect("no monitor found");
+        let window = self.create_overlay(event_loop, primary);
+        let rect = monitor_rect(primary);
+
+        // Initialize wgpu + pipeline
+        let gpu = GpuState::new(
+            Target::Window(window.clone()),
+            Vec2::new(rect.left, rect.top),
+            primary.scale_factor() as f32,
+        );
+
+        // Initialize debug overlay
+        let debug = DebugOverlay::new(&window, &gpu);
+
+        self.gpu = Some(gpu);
+        self.debug = Some(debug);
+        log::info!("wgpu + cat pipeline + debug overlay initialized");
+
+        // Overlays for the other monitors; the colony spans all of them
+        self.attach_monitors(event_loop, &monitors);
+        self.window = Some(window);
+    }
+
+    /// Show or hide the debug overlay; it takes mouse input while shown.
+    fn toggle_debug(&mut self) {
+        if let (Some(debug), Some(window)) = (&mut self.debug, &self.window) {
+            debug.visible = !debug.visible;
+            let _ = window.set_cursor_hittest(debug.visible);
+            log::info!("Debug overlay: {}", if debug.visible { "shown" } else { "hidden" });
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        if let Some(debug) = &mut self.debug {
+            debug.paused = !debug.paused;
+            log::info!("Paused: {}", debug.paused);
+        }
+    }
+
+    fn toggle_do_not_disturb(&mut self) {
+        self.presence.do_not_disturb = !self.presence.do_not_disturb;
+        log::info!("Do not disturb: {}", self.presence.do_not_disturb);
     }
 
     /// Run fixed-timestep simulation ticks.
@@ -165,147 +533,306 @@ impl App {
             self.accumulator = MAX_ACCUMULATOR;
         }
 
-        // Get mouse position once per frame (not per tick)
-        #[cfg(windows)]
-        let (mouse_x, mouse_y) = platform::win32::get_mouse_pos();
-        #[cfg(not(windows))]
-        let (mouse_x, mouse_y) = (0.0f32, 0.0f32);
+        // Lend the debug overlay's timers to the simulation for this frame.
+        if let Some(debug) = &mut self.debug {
+            std::mem::swap(&mut self.sim.timers, &mut debug.system_timers);
+        }
+
+        while self.accumulator >= TICK_RATE {
+            let input = sample_input(
+                self.platform.as_mut(),
+                &self.sim.desktop,
+                &mut self.pending_toys,
+            );
+            self.sim.tick(&input);
+            self.spawn_tick_particles(&input);
+            self.last_mouse = input.mouse;
+            self.accumulator -= TICK_RATE;
+        }
 
-        let mouse_vec = glam::Vec2::new(mouse_x, mouse_y);
+        if let Some(debug) = &mut self.debug {
+            std::mem::swap(&mut self.sim.timers, &mut debug.system_timers);
+        }
+    }
 
-        // Borrow timers from debug overlay (or use a throwaway).
-        let timers = match &mut self.debug {
-            Some(d) => &mut d.system_timers,
-            None => return,
-        };
+    /// Advance the simulation and draw a frame on every monitor.
+    fn redraw(&mut self) {
+        // Skip everything while minimized — simulation would run
+        // with stale (tiny) dimensions, crushing cats to a corner.
+        if let Some(window) = &self.window {
+            if window.is_minimized() == Some(true) {
+                self.last_frame_time = None;
+                return;
+            }
+        }
 
-        // Effective energy scale: mode preset * day/night modifier
-        let energy_scale = self.mode_state.behavior_energy_scale * self.daynight.energy_modifier;
+        // --- Timing ---
+        let now = Instant::now();
+        if let Some(last) = self.last_frame_time {
+            let dt = now.duration_since(last).as_secs_f64();
 
-        while self.accumulator >= TICK_RATE {
-            // Poll mouse buttons INSIDE the tick loop. GetAsyncKeyState's
-            // transition bit persists until read, so clicks can't be lost
-            // even when frame rate far exceeds tick rate (Mailbox mode).
-            #[cfg(windows)]
-            let (left_down, right_down, middle_down) = platform::win32::get_mouse_buttons();
-            #[cfg(not(windows))]
-            let (left_down, right_down, middle_down) = (false, false, false);
-
-            self.click_state.update(
-                left_down,
-                right_down,
-                middle_down,
-                mouse_vec,
-                TICK_RATE as f32,
-            );
+            // Record frame time in debug overlay
+            if let Some(debug) = &mut self.debug {
+                debug.record_frame(dt);
+            }
 
-            // Update yarn ball physics per tick for consistent behavior
-            self.yarn_balls
-                .update(TICK_RATE as f32, self.screen_w as f32, self.screen_h as f32, mouse_vec);
+            // Fixed timestep sim (unless paused or faded out)
+            self.presence.advance(dt as f32, &self.sim.config.presence);
+            let paused = self.debug.as_ref().is_some_and(|d| d.paused) || self.presence.hidden();
+            if !paused {
+                self.run_fixed_update(dt);
+            }
 
-            // Update box lifetimes
-            self.boxes.update(TICK_RATE as f32);
+            self.elapsed_time += dt;
+        }
+        self.last_frame_time = Some(now);
 
-            // Update glass physics
-            let shattered = self.glasses.update(
-                TICK_RATE as f32,
-                self.screen_w as f32,
-                self.screen_h as f32,
-            );
-            // Spawn shatter particles for broken glasses
-            if self.particles.enabled {
-                for spos in &shattered {
-                    self.particles.spawn_burst(*spos, 15, 0x88CCFFEE, 5, &mut self.rng);
-                }
+        // Sync cat count only when slider is explicitly changed
+        let slider_target = self.debug.as_mut().and_then(|d| {
+            if d.cat_count_changed {
+                d.cat_count_changed = false;
+                Some(d.target_cat_count)
+            } else {
+                None
             }
+        });
+        if let Some(target) = slider_target {
+            self.sim.set_cat_count(target);
+        }
 
-            // B key spawns a cardboard box at cursor
-            #[cfg(windows)]
-            if platform::win32::is_b_pressed() {
-                self.boxes.spawn(mouse_vec);
-                log::info!("Cardboard box placed at ({:.0}, {:.0})", mouse_vec.x, mouse_vec.y);
+        // Handle present mode change
+        if let (Some(debug), Some(gpu)) = (&mut self.debug, &mut self.gpu) {
+            if debug.present_mode_changed {
+                debug.present_mode_changed = false;
+                gpu.set_present_mode(debug.selected_present_mode());
             }
+        }
 
-            // G key spawns a water glass at cursor
-            #[cfg(windows)]
-            if platform::win32::is_g_pressed() {
-                self.glasses.spawn(mouse_vec);
-                log::info!("Water glass placed at ({:.0}, {:.0})", mouse_vec.x, mouse_vec.y);
+        // Handle app mode change from debug UI
+        if let Some(debug) = &mut self.debug {
+            if debug.mode_changed {
+                debug.mode_changed = false;
+                let modes = pettoy::mode::ModeState::all_modes();
+                if debug.selected_mode_index < modes.len() {
+                    self.sim.mode.set_mode(modes[debug.selected_mode_index]);
+                    log::info!("Mode set from UI: {}", self.sim.mode.mode.label());
+                }
             }
+        }
 
-            systems::tick(
-                &mut self.world,
-                TICK_RATE as f32,
-                self.screen_w as f32,
-                self.screen_h as f32,
-                mouse_x,
-                mouse_y,
-                &mut self.cursor_state,
-                &mut self.rng,
-                &mut self.spatial_grid,
-                &mut self.snapshots,
-                &mut self.interaction_bufs,
-                timers,
-                &self.heatmap,
-                self.mode_state.edge_affinity,
-                &self.desktop_windows,
-                energy_scale,
-            );
+        // Update entity count / tick count in overlay
+        if let Some(debug) = &mut self.debug {
+            debug.entity_count = self.sim.cat_count();
+            debug.tick_count = self.sim.tick_count;
+            // Keep slider in sync with actual population
+            // (only when user isn't actively changing it)
+            if !debug.cat_count_changed {
+                debug.target_cat_count = self.sim.cat_count();
+            }
+        }
 
-            // Click interactions (startle, treats, laser, yarn ball, boxes, glasses)
-            systems::click::update(
-                &mut self.world,
-                &self.click_state,
-                glam::Vec2::new(mouse_x, mouse_y),
-                &mut self.rng,
-                &mut self.yarn_balls,
-                &mut self.boxes,
-                &mut self.glasses,
-            );
+        // --- Build instance buffer from ECS (timed) ---
+        // We need to borrow debug.system_timers mutably, but also need self.
+        // Extract the timers temporarily.
+        let mut timers = self
+            .debug
+            .as_mut()
+            .map(|d| std::mem::replace(&mut d.system_timers, SystemTimers::new()))
+            .unwrap_or_default();
+        if let Some(gpu) = &self.gpu {
+            let origin = self.sim.desktop.origin();
+            self.occlusion.set_views(gpu.surfaces.iter().map(|s| {
+                let min = s.position - origin;
+                let max = min + Vec2::new(s.config.width as f32, s.config.height as f32);
+                ([min.x, min.y, max.x, max.y], s.scale)
+            }));
+        }
+        self.build_instances(&mut timers);
 
-            // Advance spawn drop-in animations — collect bounce impacts for dust
-            let bounces = update_spawn_animations(&mut self.world, TICK_RATE as f32);
-            if self.particles.enabled {
-                for bounce in &bounces {
-                    // Dust poof: more particles for harder impacts
-                    let count = (3.0 + bounce.intensity * 8.0) as usize;
-                    self.particles.spawn_dust(bounce.pos, count, bounce.intensity, &mut self.rng);
+        // --- Update trails ---
+        {
+            // Build trail positions from ECS with mood colors
+            if self.trail_system.enabled {
+                let mut trail_positions = Vec::with_capacity(self.sim.cat_count());
+                for (_, (pos, appearance, cat_state)) in
+                    self.sim.world.query::<(&Position, &Appearance, &CatState)>().iter()
+                {
+                    let color = appearance.color;
+                    let base_r = ((color >> 24) & 0xFF) as f32 / 255.0;
+                    let base_g = ((color >> 16) & 0xFF) as f32 / 255.0;
+                    let base_b = ((color >> 8) & 0xFF) as f32 / 255.0;
+
+                    // Mood color based on behavior state
+                    let (mood_r, mood_g, mood_b) = mood_color(cat_state.state);
+
+                    trail_positions.push((
+                        pos.0.x, pos.0.y,
+                        base_r, base_g, base_b,
+                        mood_r, mood_g, mood_b,
+                    ));
                 }
+                self.trail_system.update(&trail_positions);
+            }
+        }
+
+        // --- GPU upload (timed) ---
+        timers.begin();
+        if let Some(gpu) = &mut self.gpu {
+            gpu.update_instances(&self.instance_buf);
+
+            // Upload trail vertices
+            if self.trail_system.enabled {
+                let trail_verts = self.trail_system.build_vertices();
+                gpu.update_trails(trail_verts);
+            }
+
+            // Upload heatmap texture (clan territories take its place when shown)
+            if self.sim.scent.shown {
+                gpu.update_heatmap(&self.sim.scent.to_texture_data());
+            } else if self.sim.heatmap.enabled {
+                let heatmap_data = self.sim.heatmap.to_texture_data();
+                gpu.update_heatmap(&heatmap_data);
+            }
+        }
+        timers.end(SystemPhase::GpuUpload);
+
+        // Put timers back
+        if let Some(debug) = &mut self.debug {
+            debug.system_timers = timers;
+        }
+
+        // --- Run egui frame ---
+        let egui_output = if let (Some(debug), Some(window)) =
+            (&mut self.debug, &self.window)
+        {
+            if debug.visible {
+                let size = window.inner_size();
+                Some(debug.run_frame(window, size.width, size.height))
+            } else {
+                None
             }
+        } else {
+            None
+        };
+
+        // --- Render ---
+        if let Some(gpu) = &mut self.gpu {
+            gpu.update_views(self.sim.desktop.origin(), self.sim.desktop.size());
+
+            // Time the render submit phase
+            if let Some(debug) = &mut self.debug {
+                debug.system_timers.begin();
+            }
+
+            for index in 0..gpu.surfaces.len() {
+                let Some(mut frame) = gpu.begin_frame(index) else {
+                    continue;
+                };
+
+                // Cat render pass (includes clear to transparent)
+                gpu.draw_cats(&mut frame);
 
-            // Click feedback particles
-            if self.particles.enabled {
-                let mouse = glam::Vec2::new(mouse_x, mouse_y);
-                if self.click_state.left_clicked {
-                    self.particles.spawn_burst(mouse, 8, 0xFFFF66EE, 5, &mut self.rng);
+                // Heatmap overlay (behind trails/cats but after clear)
+                let shown = !self.presence.hidden();
+                if shown && (self.sim.heatmap.enabled || self.sim.scent.shown) {
+                    gpu.draw_heatmap(&mut frame);
                 }
-                if self.click_state.right_clicked {
-                    self.particles.spawn_burst(mouse, 6, 0xDD3333DD, 3, &mut self.rng);
+
+                // Trail render pass (behind cats, after heatmap)
+                if shown && self.trail_system.enabled {
+                    gpu.draw_trails(&mut frame);
                 }
-                if self.click_state.double_clicked {
-                    self.particles.spawn_burst(mouse, 12, 0xFF0000FF, 5, &mut self.rng);
+
+                // Egui render pass (primary monitor only, if visible)
+                let mut extra_cmd_bufs = Vec::new();
+                if let (0, Some((primitives, textures_delta, screen_desc))) =
+                    (index, &egui_output)
+                {
+                    if let Some(debug) = &mut self.debug {
+                        let bufs = debug.prepare_egui(
+                            &gpu.device,
+                            &gpu.queue,
+                            &mut frame.encoder,
+                            primitives,
+                            textures_delta,
+                            screen_desc,
+                        );
+                        extra_cmd_bufs = bufs;
+
+                        {
+                            let mut egui_pass =
+                                GpuState::begin_egui_pass(&mut frame.encoder, &frame.view);
+                            debug.render_egui(&mut egui_pass, primitives, screen_desc);
+                        }
+                    }
                 }
+
+                gpu.finish_frame(frame.encoder, frame.output, extra_cmd_bufs);
             }
 
-            // Emotion particles: gather cat states, spawn particles, update physics
-            if self.particles.enabled {
-                let mut cat_states: Vec<(glam::Vec2, BehaviorState, f32)> =
-                    Vec::with_capacity(self.world.len() as usize);
-                for (_, (pos, cat_state, appearance)) in self
-                    .world
-                    .query::<(&Position, &CatState, &Appearance)>()
-                    .iter()
-                {
-                    cat_states.push((pos.0, cat_state.state, appearance.size));
+            if let Some(debug) = &mut self.debug {
+                debug.system_timers.end(SystemPhase::RenderSubmit);
+            }
+            self.blanked = self.presence.hidden();
+
+            // Free egui textures after present
+            if let Some((_, ref textures_delta, _)) = egui_output {
+                if let Some(debug) = &mut self.debug {
+                    debug.free_textures(textures_delta);
                 }
-                self.particles
-                    .spawn_from_behaviors(&cat_states, &mut self.rng, TICK_RATE as f32);
-                self.particles.update(TICK_RATE as f32);
             }
+        }
+    }
 
-            self.accumulator -= TICK_RATE;
-            self.tick_count += 1;
+    /// Turn the last tick's events into cosmetic particles.
+    fn spawn_tick_particles(&mut self, input: &SimInput) {
+        if !self.particles.enabled {
+            return;
+        }
+        let rng = &mut self.fx_rng;
+
+        // Shatter particles for broken glasses
+        for spos in &self.sim.events.shattered {
+            self.particles.spawn_burst(*spos, 15, 0x88CCFFEE, 5, rng);
+        }
+
+        // Hearts for newborn kittens
+        for pos in &self.sim.events.births {
+            self.particles.spawn_burst(*pos, 6, 0xFF6699EE, 4, rng);
+        }
+
+        // Dust poof: more particles for harder impacts
+        for bounce in &self.sim.events.bounces {
+            let count = (3.0 + bounce.intensity * 8.0) as usize;
+            self.particles.spawn_dust(bounce.pos, count, bounce.intensity, rng);
+        }
+
+        // Click feedback particles
+        let click = &self.sim.click;
+        if click.left_clicked {
+            self.particles.spawn_burst(input.mouse, 8, 0xFFFF66EE, 5, rng);
+        }
+        if click.right_clicked {
+            self.particles.spawn_burst(input.mouse, 6, 0xDD3333DD, 3, rng);
         }
+        if click.double_clicked {
+            self.particles.spawn_burst(input.mouse, 12, 0xFF0000FF, 5, rng);
+        }
+
+        // Emotion particles: gather cat states, spawn particles, update physics
+        let mut cat_states: Vec<(glam::Vec2, BehaviorState, f32)> =
+            Vec::with_capacity(self.sim.cat_count());
+        for (_, (pos, cat_state, appearance)) in self
+            .sim
+            .world
+            .query::<(&Position, &CatState, &Appearance)>()
+            .iter()
+        {
+            cat_states.push((pos.0, cat_state.state, appearance.size));
+        }
+        self.particles
+            .spawn_from_behaviors(&cat_states, rng, TICK_RATE as f32);
+        self.particles.update(TICK_RATE as f32);
     }
 
     /// Interpolation alpha for rendering between ticks.
@@ -321,11 +848,12 @@ impl App {
         let time = self.elapsed_time as f32;
 
         for (_, (pos, prev_pos, vel, appearance, cat_state, pile, spawn_anim, gift)) in self
+            .sim
             .world
             .query::<(
                 &Position,
                 &PrevPosition,
-                &crate::ecs::components::Velocity,
+                &pettoy::ecs::components::Velocity,
                 &Appearance,
                 &CatState,
                 Option<&SleepingPile>,
@@ -336,6 +864,12 @@ impl App {
         {
             let mut inst = CatInstance::from_components(pos, prev_pos, appearance, cat_state, alpha, time);
 
+            // Behind windows stacked over whatever the cat is standing on
+            let Some(clip) = self.occlusion.sprite(inst.position.into(), appearance.size) else {
+                continue;
+            };
+            inst.clip = clip;
+
             // --- Shadow (rendered first = behind cat) ---
             // Shadow projects to ground at the cat's feet (~25px below center).
             // During spawn animation it stays at landing Y, shrinking/fading with height.
@@ -362,6 +896,7 @@ impl App {
                     color: shadow_alpha, // 0x000000xx — black with variable alpha
                     frame: 3,
                     rotation: 0.0,
+                    clip,
                 });
             }
 
@@ -442,11 +977,11 @@ impl App {
             }
 
             // Day/night color tint
-            inst.color = apply_tint(inst.color, self.daynight.tint);
+            inst.color = apply_tint(inst.color, self.sim.daynight.tint);
 
             // Eye glow at night: add 8 to frame index to signal shader
-            let force_eyes = self.debug.as_ref().map_or(false, |d| d.force_night_eyes);
-            if (self.daynight.is_night || force_eyes) && (inst.frame == 0 || inst.frame == 1 || inst.frame == 7) {
+            let force_eyes = self.debug.as_ref().is_some_and(|d| d.force_night_eyes);
+            if (self.sim.daynight.is_night || force_eyes) && (inst.frame == 0 || inst.frame == 1 || inst.frame == 7) {
                 inst.frame += 8;
             }
 
@@ -461,84 +996,98 @@ impl App {
                     color: 0xFF4488FF, // pink heart
                     frame: 4,          // heart shape
                     rotation: 0.0,
+                    clip,
                 });
             }
         }
-        // Render yarn balls
-        for ball in &self.yarn_balls.balls {
-            let fade = (ball.lifetime / 5.0).clamp(0.0, 1.0);
-            let alpha = (fade * 255.0) as u32;
-            self.instance_buf.push(CatInstance {
-                position: ball.pos.into(),
-                size: [1.0, 1.0],
-                color: (0xDD << 24) | (0x33 << 16) | (0x33 << 8) | alpha,
-                frame: 3,
-                rotation: 0.0,
-            });
-        }
 
-        // Render cardboard boxes
-        for cbox in &self.boxes.boxes {
-            let fade = (cbox.lifetime / 5.0).clamp(0.0, 1.0);
-            let alpha = (fade * 255.0) as u32;
-            // Cardboard brown color
+        // Render prey: mice and bugs as small dots, butterflies as flapping hearts
+        for (_, (pos, prev_pos, prey)) in self
+            .sim
+            .world
+            .query::<(&Position, &PrevPosition, &Prey)>()
+            .iter()
+        {
+            let at = prev_pos.0.lerp(pos.0, alpha);
+            let fade = (prey.lifetime / 2.0).clamp(0.0, 1.0);
+            let alpha_byte = (fade * 255.0) as u32;
+            let (size, rgb, frame, rotation) = match prey.kind {
+                PreyKind::Mouse => ([0.55, 0.4], 0x8A7F76, 3, 0.0),
+                PreyKind::Bug => ([0.3, 0.3], 0x2E2A24, 3, 0.0),
+                PreyKind::Butterfly => {
+                    let flap = (time * 18.0 + pos.0.x * 0.05).sin().abs();
+                    // Upside-down heart reads as a pair of wings.
+                    ([0.3 + flap * 0.3, 0.45], 0xFF9A2E, 4, std::f32::consts::PI)
+                }
+            };
+            let Some(clip) = self.occlusion.sprite(at, size[0].max(size[1])) else {
+                continue;
+            };
             self.instance_buf.push(CatInstance {
-                position: cbox.pos.into(),
-                size: [1.2, 1.2],
-                color: (0xC4 << 24) | (0x8A << 16) | (0x3F << 8) | alpha,
-                frame: 9,
-                rotation: 0.0,
+                position: at.into(),
+                size,
+                color: apply_tint((rgb << 8) | alpha_byte, self.sim.daynight.tint),
+                frame,
+                rotation,
+                clip,
             });
         }
 
-        // Render water glasses
-        for glass in &self.glasses.glasses {
-            if glass.shattered {
+        // Toys and treats, fading out at the end of their lifetime
+        for (_, (pos, sprite, life)) in self
+            .sim
+            .world
+            .query::<(&Position, &ToySprite, &Lifetime)>()
+            .without::<&Shattered>()
+            .iter()
+        {
+            let fade = (life.0 / sprite.fade).clamp(0.0, 1.0);
+            let pulse = 1.0 + sprite.pulse * (time * 3.0 + pos.0.x * 0.01).sin();
+            let size = sprite.size.map(|s| s * pulse);
+            let Some(clip) = self.occlusion.sprite(pos.0, size[0].max(size[1])) else {
                 continue;
-            }
-            let fade = (glass.lifetime / 5.0).clamp(0.0, 1.0);
-            let alpha = (fade * 255.0) as u32;
-            // Light blue glass color, small sprite
+            };
             self.instance_buf.push(CatInstance {
-                position: glass.pos.into(),
-                size: [0.7, 0.9],
-                color: (0x88 << 24) | (0xCC << 16) | (0xFF << 8) | alpha,
-                frame: 10,
+                position: pos.0.into(),
+                size,
+                color: sprite.color | (fade * 255.0) as u32,
+                frame: sprite.frame,
                 rotation: 0.0,
+                clip,
             });
         }
 
-        // Render treats as golden star shapes
-        for treat in &self.click_state.treats {
-            let fade = (treat.timer / 10.0).clamp(0.0, 1.0);
-            let alpha = (fade * 200.0 + 55.0) as u32;
-            let pulse = (time * 3.0 + treat.pos.x * 0.01).sin() * 0.1 + 1.0;
-            let s = 0.7 * pulse;
-            self.instance_buf.push(CatInstance {
-                position: treat.pos.into(),
-                size: [s, s],
-                color: (0xFF << 24) | (0xCC << 16) | (0x33 << 8) | alpha,
-                frame: 5,
-                rotation: 0.0,
-            });
+        // Feather wand: the string as a trail of dots
+        if self.sim.wand.active {
+            let points = &self.sim.wand.points;
+            for p in &points[1..points.len() - 1] {
+                self.instance_buf.push(CatInstance {
+                    position: (*p).into(),
+                    size: [0.08, 0.08],
+                    color: apply_tint(0xE8E0D0C0, self.sim.daynight.tint),
+                    frame: 3,
+                    rotation: 0.0,
+                    clip: UNCLIPPED,
+                });
+            }
+            // Feather: a plume of shrinking puffs trailing up the string
+            let tip = points[points.len() - 1];
+            let up = (points[points.len() - 2] - tip).normalize_or_zero();
+            for (i, size) in [0.32, 0.26, 0.2].into_iter().enumerate() {
+                self.instance_buf.push(CatInstance {
+                    position: (tip + up * (i as f32 * 6.0)).into(),
+                    size: [size, size],
+                    color: apply_tint(0xD94FA8FF, self.sim.daynight.tint),
+                    frame: 3,
+                    rotation: 0.0,
+                    clip: UNCLIPPED,
+                });
+            }
         }
 
         // Add laser pointer dot when active
-        if self.click_state.laser_active {
-            #[cfg(windows)]
-            let (mx, my) = platform::win32::get_mouse_pos();
-            #[cfg(not(windows))]
-            let (mx, my) = (0.0f32, 0.0f32);
-
-            let pulse = (time * 12.0).sin() * 0.15 + 1.0;
-            let s = 0.5 * pulse;
-            self.instance_buf.push(CatInstance {
-                position: [mx, my],
-                size: [s, s],
-                color: 0xFF0000FF,
-                frame: 3,
-                rotation: 0.0,
-            });
+        if self.sim.click.laser_active {
+            self.instance_buf.push(laser_dot(self.last_mouse, time));
         }
 
         // Emotion particles
@@ -546,110 +1095,57 @@ impl App {
             self.particles.build_instances(&mut self.instance_buf);
         }
 
-        timers.end(SystemPhase::BuildInstances);
-    }
-
-    /// Spawn or despawn cats to match target count.
-    fn sync_cat_count(&mut self, target: usize) {
-        let current = self.world.len() as usize;
-        if target > current {
-            cat::spawn_cats(
-                &mut self.world,
-                target - current,
-                self.screen_w as f32,
-                self.screen_h as f32,
-            );
-        } else if target < current {
-            let to_remove = current - target;
-            let entities: Vec<hecs::Entity> = self
-                .world
-                .iter()
-                .take(to_remove)
-                .map(|e| e.entity())
-                .collect();
-            for entity in entities {
-                let _ = self.world.despawn(entity);
+        // Fading out for a fullscreen app or do-not-disturb
+        let visibility = self.presence.visibility();
+        if visibility <= 0.0 {
+            self.instance_buf.clear();
+        } else if visibility < 1.0 {
+            for inst in &mut self.instance_buf {
+                inst.color = fade(inst.color, visibility);
             }
         }
+
+        timers.end(SystemPhase::BuildInstances);
     }
 }
 
 impl ApplicationHandler for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        if self.window.is_some() {
+        if self.gpu.is_some() {
             return;
         }
 
-        // Create fullscreen-sized borderless transparent window
-        let monitor = event_loop
-            .primary_monitor()
-            .or_else(|| event_loop.available_monitors().next())
-            .expect("no monitor found");
-        let screen_size = monitor.size();
-
-        // No with_transparent(true) — that sets WS_EX_LAYERED which creates
-        // a GDI backing surface that conflicts with DirectComposition.
-        // Transparency comes from wgpu's DxgiFromVisual + PreMultiplied alpha.
-        // Start hidden so DWM doesn't cache stale frame state before our
-        // overlay style changes take effect.
-        let attrs = WindowAttributes::default()
-            .with_title("PetToy")
-            .with_decorations(false)
-            .with_visible(false)
-            .with_window_level(WindowLevel::AlwaysOnTop)
-            .with_inner_size(screen_size)
-            .with_position(winit::dpi::PhysicalPosition::new(0, 0));
-
-        let window = Arc::new(
-            event_loop
-                .create_window(attrs)
-                .expect("failed to create window"),
-        );
-
-        #[cfg(windows)]
-        platform::win32::setup_overlay(&window);
-
-        let size = window.inner_size();
-        self.screen_w = size.width;
-        self.screen_h = size.height;
-
-        log::info!(
-            "Overlay window created: {}x{} on {:?}",
-            size.width,
-            size.height,
-            monitor.name().unwrap_or_default()
-        );
-
-        // Initialize wgpu + pipeline
-        let gpu = GpuState::new(window.clone());
-
-        // Initialize debug overlay
-        let debug = DebugOverlay::new(&window, &gpu);
-
-        self.gpu = Some(gpu);
-        self.debug = Some(debug);
-        log::info!("wgpu + cat pipeline + debug overlay initialized");
-
-        // Initialize heatmap with actual screen dimensions
-        self.heatmap.resize(self.screen_w as f32, self.screen_h as f32);
+        // A layer surface per output on Wayland without XWayland, or else
+        // one borderless transparent overlay window per monitor
+        #[cfg(all(target_os = "linux", feature = "wayland"))]
+        let layered = self.open_layers();
+        #[cfg(not(all(target_os = "linux", feature = "wayland")))]
+        let layered = false;
+        if !layered {
+            self.open_windows(event_loop);
+        }
 
-        // Spawn cats
-        cat::spawn_cats(
-            &mut self.world,
-            INITIAL_CAT_COUNT,
-            self.screen_w as f32,
-            self.screen_h as f32,
-        );
-        log::info!("Spawned {} cats", INITIAL_CAT_COUNT);
+        // Restore the saved colony, or spawn a fresh one
+        if !self.load_colony() {
+            let count = self.sim.config.population.initial_cat_count;
+            self.sim.spawn_cats(count);
+            log::info!("Spawned {} cats", count);
+        }
 
         // Continuous game loop
         event_loop.set_control_flow(ControlFlow::Poll);
 
         // Show window now that all styles and GPU resources are ready.
         // This prevents DWM from caching stale frame state (the "white box").
-        window.set_visible(true);
+        if let Some(window) = &self.window {
+            window.set_visible(true);
+        }
+    }
 
-        self.window = Some(window);
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        self.save_colony();
+        self.save_recording();
+        self.save_journal();
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
@@ -663,188 +1159,207 @@ impl ApplicationHandler for App {
             }
         }
 
+        // Outputs plugged, unplugged or resized under the layer shell
+        #[cfg(all(target_os = "linux", feature = "wayland"))]
+        if self.layers.as_mut().is_some_and(|l| l.dispatch()) {
+            self.attach_layers();
+        }
+
         // Poll system tray menu commands
-        match self.tray.poll() {
+        match self.platform.poll_tray() {
             TrayCommand::Quit => {
                 log::info!("Quit from tray menu");
                 event_loop.exit();
                 return;
             }
             TrayCommand::SetModeWork => {
-                self.mode_state.set_mode(crate::mode::AppMode::Work);
+                self.sim.mode.set_mode(AppMode::Work);
                 log::info!("Tray: mode set to Work");
             }
             TrayCommand::SetModePlay => {
-                self.mode_state.set_mode(crate::mode::AppMode::Play);
+                self.sim.mode.set_mode(AppMode::Play);
                 log::info!("Tray: mode set to Play");
             }
             TrayCommand::SetModeZen => {
-                self.mode_state.set_mode(crate::mode::AppMode::Zen);
+                self.sim.mode.set_mode(AppMode::Zen);
                 log::info!("Tray: mode set to Zen");
             }
             TrayCommand::SetModeChaos => {
-                self.mode_state.set_mode(crate::mode::AppMode::Chaos);
+                self.sim.mode.set_mode(AppMode::Chaos);
                 log::info!("Tray: mode set to Chaos");
             }
-            TrayCommand::TogglePause => {
-                if let Some(debug) = &mut self.debug {
-                    debug.paused = !debug.paused;
-                    log::info!("Tray: paused = {}", debug.paused);
-                }
-            }
-            TrayCommand::ToggleDebug => {
-                if let (Some(debug), Some(window)) = (&mut self.debug, &self.window) {
-                    debug.visible = !debug.visible;
-                    let _ = window.set_cursor_hittest(debug.visible);
-                    log::info!("Tray: debug overlay = {}", debug.visible);
-                }
-            }
+            TrayCommand::TogglePause => self.toggle_pause(),
+            TrayCommand::ToggleDoNotDisturb => self.toggle_do_not_disturb(),
+            TrayCommand::ToggleDebug => self.toggle_debug(),
             TrayCommand::None => {}
         }
 
-        // Poll ESC key (window is click-through so can't receive keyboard events)
-        #[cfg(windows)]
-        if platform::win32::is_escape_pressed() {
-            log::info!("ESC pressed, exiting");
+        // Poll hotkeys (window is click-through so can't receive keyboard events)
+        let mut fired = self
+            .actions
+            .poll(&self.sim.config.hotkeys, self.platform.as_mut());
+        if fired.take(Action::Quit) {
+            log::info!("Quit hotkey pressed, exiting");
             event_loop.exit();
             return;
         }
-
-        // Poll F12 for debug overlay toggle
-        #[cfg(windows)]
-        {
-            let f12_down = platform::win32::is_f12_pressed();
-            if let (Some(debug), Some(window)) = (&mut self.debug, &self.window) {
-                if debug.poll_toggle(f12_down) {
-                    let _ = window.set_cursor_hittest(debug.visible);
-                    log::info!("Debug overlay: {}", if debug.visible { "shown" } else { "hidden" });
-                }
-            }
+        if fired.take(Action::ToggleDebug) {
+            self.toggle_debug();
         }
-
-        // Poll F11 for mode cycle
-        #[cfg(windows)]
-        {
-            let f11_down = platform::win32::is_f11_pressed();
-            if self.mode_state.poll_f11(f11_down) {
-                log::info!("Mode changed to: {}", self.mode_state.mode.label());
-            }
+        if fired.take(Action::CycleMode) {
+            self.sim.mode.cycle();
+            log::info!("Mode changed to: {}", self.sim.mode.mode.label());
+        }
+        if fired.take(Action::TogglePause) {
+            self.toggle_pause();
+        }
+        if fired.take(Action::DoNotDisturb) {
+            self.toggle_do_not_disturb();
         }
+        // Toys drop on the next sim tick
+        self.pending_toys.extend(fired);
 
-        // AFK escalation
-        #[cfg(windows)]
+        // Timers that keep going while the cats are faded out and no
+        // frames are drawn
         {
-            let idle_secs = platform::win32::get_idle_time();
-            let dt = self.last_frame_time
-                .map(|t| instant::Instant::now().duration_since(t).as_secs_f64())
-                .unwrap_or(0.016);
-            match self.mode_state.update_afk(idle_secs, dt) {
-                AtkAction::SpawnCats(n) => {
-                    cat::spawn_cats(
-                        &mut self.world,
-                        n,
-                        self.screen_w as f32,
-                        self.screen_h as f32,
-                    );
+            let now = Instant::now();
+            let dt = self
+                .last_wake
+                .map_or(0.0, |t| now.duration_since(t).as_secs_f64());
+            self.last_wake = Some(now);
+
+            // Pick up config file edits
+            self.config_poll_timer += dt;
+            if self.config_poll_timer >= CONFIG_POLL_INTERVAL {
+                self.config_poll_timer = 0.0;
+                if let Some(watcher) = &mut self.config_watcher {
+                    apply_config_change(&mut self.sim, watcher);
                 }
-                AtkAction::ScatterAndDespawn(n) => {
-                    log::info!("User returned — despawning {} bonus cats", n);
-                    self.sync_cat_count((self.world.len() as usize).saturating_sub(n));
-                }
-                AtkAction::Scatter => {
-                    log::info!("User returned — scattering cats");
-                }
-                AtkAction::None => {}
             }
-        }
 
-        // Periodically refresh desktop window list for window awareness
-        #[cfg(windows)]
-        {
-            let dt = self.last_frame_time
-                .map(|t| instant::Instant::now().duration_since(t).as_secs_f64())
-                .unwrap_or(0.016);
+            // Periodic autosave
+            self.autosave_timer += dt;
+            if self.autosave_timer >= AUTOSAVE_INTERVAL {
+                self.autosave_timer = 0.0;
+                self.save_colony();
+            }
+
+            // Periodically refresh desktop window list for window awareness
             self.window_refresh_timer += dt;
-            if self.window_refresh_timer >= 2.0 {
+            if self.window_refresh_timer >= WINDOW_REFRESH {
                 self.window_refresh_timer = 0.0;
-                if let Some(window) = &self.window {
-                    let hwnd = platform::win32::get_hwnd(window);
-                    let rects = platform::win32::enumerate_windows(hwnd);
-                    self.desktop_windows = rects
-                        .into_iter()
-                        .map(|r| DesktopWindow {
-                            left: r.x as f32,
-                            top: r.y as f32,
-                            right: (r.x + r.w) as f32,
-                            bottom: (r.y + r.h) as f32,
-                        })
-                        .collect();
+                if self.window.is_some() {
+                    // Docking, undocking or rearranging screens
+                    let monitors = connected_monitors(event_loop);
+                    if !monitors.is_empty()
+                        && monitors.iter().map(monitor_rect).ne(self.monitors.iter().copied())
+                    {
+                        self.relayout(event_loop, &monitors);
+                    }
+                }
+                if self.gpu.is_some() {
+                    let rects = self.platform.enumerate_windows();
+                    let windows = desktop_windows(rects, &self.sim.desktop);
+                    self.sim.set_desktop_windows(windows);
+                    self.occlusion.update(self.sim.desktop_windows());
+                    let presence = &self.sim.config.presence;
+                    if self.presence.check(self.sim.desktop_windows(), presence) {
+                        match self.presence.quiet() {
+                            Some(quiet) => log::info!("Cats stepping away: {quiet:?}"),
+                            None => log::info!("Cats coming back"),
+                        }
+                    }
                 }
             }
         }
 
-        // Update day/night cycle from system clock
-        self.daynight.update();
-
         // Sync debug mode display + visual toggles
         if let Some(debug) = &mut self.debug {
-            debug.current_mode = self.mode_state.mode;
-            debug.idle_seconds = self.mode_state.idle_seconds;
-            debug.edge_affinity = self.mode_state.edge_affinity;
-            debug.energy_scale = self.mode_state.behavior_energy_scale;
+            debug.current_mode = self.sim.mode.mode;
+            debug.idle_seconds = self.sim.mode.idle_seconds;
+            debug.edge_affinity = self.sim.mode.edge_affinity;
+            debug.energy_scale = self.sim.mode.behavior_energy_scale;
             self.trail_system.enabled = debug.show_trails;
-            self.heatmap.enabled = debug.show_heatmap;
+            self.sim.heatmap.enabled = debug.show_heatmap;
+            self.sim.scent.shown = debug.show_scent;
             self.particles.enabled = debug.show_particles;
+            self.occlusion.enabled = debug.occlusion;
 
-            // Tooltip hit-test: find nearest cat to mouse cursor
             if debug.visible {
-                #[cfg(windows)]
-                let (mx, my) = platform::win32::get_mouse_pos();
-                #[cfg(not(windows))]
-                let (mx, my) = (0.0f32, 0.0f32);
-                let mouse = glam::Vec2::new(mx, my);
-
-                let mut best: Option<(f32, crate::debug::HoveredCatInfo)> = None;
-                for (_, (pos, name, state, personality)) in self
-                    .world
-                    .query::<(&Position, &CatName, &CatState, &Personality)>()
-                    .iter()
-                {
+                // Rule line, rebuilt only when it would read differently
+                let automation = &self.sim.config.automation;
+                let key = self.sim.automation.describe_key(automation);
+                if self.mode_rule_key != Some(key) {
+                    self.mode_rule_key = Some(key);
+                    debug.mode_rule = self.sim.automation.describe(automation);
+                }
+
+                // Tooltip hit-test: find nearest cat to mouse cursor
+                let mouse = self.sim.desktop.to_colony(self.platform.mouse_pos());
+
+                let mut best: Option<(f32, hecs::Entity)> = None;
+                for (entity, pos) in self.sim.world.query::<&Position>().with::<&CatName>().iter() {
                     let dist_sq = (pos.0 - mouse).length_squared();
-                    if dist_sq < 40.0 * 40.0 {
-                        if best.is_none() || dist_sq < best.as_ref().expect("checked").0 {
-                            best = Some((
-                                dist_sq,
-                                crate::debug::HoveredCatInfo {
-                                    screen_pos: pos.0,
-                                    name: name.0.clone(),
-                                    state: state.state,
-                                    personality: *personality,
-                                },
-                            ));
-                        }
+                    if dist_sq < 40.0 * 40.0 && best.is_none_or(|(d, _)| dist_sq < d) {
+                        best = Some((dist_sq, entity));
                     }
                 }
-                debug.hovered_cat = best.map(|(_, info)| info);
+                debug.hovered_cat = best.and_then(|(_, entity)| {
+                    let cat = self.sim.world.entity(entity).ok()?;
+                    let (friends, rivals) = cat
+                        .get::<&CatId>()
+                        .map(|id| Self::relationships_of(&self.sim, *id))
+                        .unwrap_or_default();
+                    Some(crate::debug::HoveredCatInfo {
+                        name: cat.get::<&CatName>()?.0.clone(),
+                        state: cat.get::<&CatState>()?.state,
+                        personality: *cat.get::<&Personality>()?,
+                        id: cat.get::<&CatId>().map(|id| id.0),
+                        highlights: cat
+                            .get::<&Journal>()
+                            .map(|j| j.highlights())
+                            .unwrap_or_default(),
+                        needs: cat.get::<&Needs>().map(|n| *n),
+                        trust: cat.get::<&Trust>().map(|t| t.0),
+                        friends,
+                        rivals,
+                    })
+                });
             } else {
                 debug.hovered_cat = None;
             }
         }
 
+        // Faded out with nothing left on screen: stop drawing frames and
+        // just wake up now and then for hotkeys and window changes
+        let overlay = self.debug.as_ref().is_some_and(|d| d.visible);
+        if self.blanked && self.presence.quiet().is_some() && !overlay {
+            self.last_frame_time = Some(Instant::now());
+            let wake = Instant::now() + std::time::Duration::from_secs_f64(PRESENCE_POLL);
+            event_loop.set_control_flow(ControlFlow::WaitUntil(wake));
+            return;
+        }
+        event_loop.set_control_flow(ControlFlow::Poll);
+
         if let Some(w) = &self.window {
             w.request_redraw();
+        } else if self.gpu.is_some() {
+            // Layer surfaces get no redraw events; draw straight away
+            self.redraw();
         }
     }
 
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
-        _window_id: WindowId,
+        window_id: WindowId,
         event: WindowEvent,
     ) {
+        let primary = self.window.as_ref().is_some_and(|w| w.id() == window_id);
+
         // Forward events to egui when overlay is visible
         if let (Some(debug), Some(window)) = (&mut self.debug, &self.window) {
-            if debug.visible {
+            if primary && debug.visible {
                 let consumed = debug.on_window_event(window, &event);
                 if consumed {
                     return;
@@ -857,267 +1372,68 @@ impl ApplicationHandler for App {
                 log::info!("Close requested, exiting");
                 event_loop.exit();
             }
-            WindowEvent::Resized(new_size) => {
+            WindowEvent::Resized(new_size)
                 // Ignore tiny resizes (happens during minimize animation) —
                 // prevents cats from getting clamped to a tiny area.
                 // A real desktop window is always larger than 200x200.
-                if new_size.width >= 200 && new_size.height >= 200 {
+                if new_size.width >= 200 && new_size.height >= 200 => {
                     if let Some(gpu) = &mut self.gpu {
-                        gpu.resize(new_size.width, new_size.height);
-                        self.screen_w = new_size.width;
-                        self.screen_h = new_size.height;
-                        self.heatmap.resize(new_size.width as f32, new_size.height as f32);
-                    }
-                }
-            }
-            WindowEvent::RedrawRequested => {
-                // Skip everything while minimized — simulation would run
-                // with stale (tiny) dimensions, crushing cats to a corner.
-                if let Some(window) = &self.window {
-                    if window.is_minimized() == Some(true) {
-                        self.last_frame_time = None;
-                        return;
-                    }
-                }
-
-                // --- Timing ---
-                let now = Instant::now();
-                if let Some(last) = self.last_frame_time {
-                    let dt = now.duration_since(last).as_secs_f64();
-
-                    // Record frame time in debug overlay
-                    if let Some(debug) = &mut self.debug {
-                        debug.record_frame(dt);
-                    }
-
-                    // Fixed timestep sim (unless paused)
-                    let paused = self.debug.as_ref().map_or(false, |d| d.paused);
-                    if !paused {
-                        self.run_fixed_update(dt);
-                    }
-
-                    // Population growth: gradually spawn cats up to target
-                    self.elapsed_time += dt;
-                    if self.elapsed_time > GROWTH_DELAY {
-                        let current = self.world.len() as usize;
-                        if current < TARGET_CAT_COUNT {
-                            self.spawn_accumulator += dt * POPULATION_GROWTH_RATE;
-                            let to_spawn = self.spawn_accumulator as usize;
-                            if to_spawn > 0 {
-                                self.spawn_accumulator -= to_spawn as f64;
-                                let actual = to_spawn.min(TARGET_CAT_COUNT - current);
-                                cat::spawn_cats(
-                                    &mut self.world,
-                                    actual,
-                                    self.screen_w as f32,
-                                    self.screen_h as f32,
-                                );
-                            }
-                        }
-                    }
-                }
-                self.last_frame_time = Some(now);
-
-                // Sync cat count only when slider is explicitly changed
-                let slider_target = self.debug.as_mut().and_then(|d| {
-                    if d.cat_count_changed {
-                        d.cat_count_changed = false;
-                        Some(d.target_cat_count)
-                    } else {
-                        None
-                    }
-                });
-                if let Some(target) = slider_target {
-                    self.sync_cat_count(target);
-                }
-
-                // Handle present mode change
-                if let (Some(debug), Some(gpu)) = (&mut self.debug, &mut self.gpu) {
-                    if debug.present_mode_changed {
-                        debug.present_mode_changed = false;
-                        gpu.set_present_mode(debug.selected_present_mode());
-                    }
-                }
-
-                // Handle app mode change from debug UI
-                if let Some(debug) = &mut self.debug {
-                    if debug.mode_changed {
-                        debug.mode_changed = false;
-                        let modes = crate::mode::ModeState::all_modes();
-                        if debug.selected_mode_index < modes.len() {
-                            self.mode_state.set_mode(modes[debug.selected_mode_index]);
-                            log::info!("Mode set from UI: {}", self.mode_state.mode.label());
-                        }
-                    }
-                }
-
-                // Update entity count / tick count in overlay
-                if let Some(debug) = &mut self.debug {
-                    debug.entity_count = self.world.len() as usize;
-                    debug.tick_count = self.tick_count;
-                    // Keep slider in sync with actual population
-                    // (only when user isn't actively changing it)
-                    if !debug.cat_count_changed {
-                        debug.target_cat_count = self.world.len() as usize;
-                    }
-                }
-
-                // --- Build instance buffer from ECS (timed) ---
-                // We need to borrow debug.system_timers mutably, but also need self.
-                // Extract the timers temporarily.
-                let mut timers_temp = self
-                    .debug
-                    .as_mut()
-                    .map(|d| std::mem::replace(&mut d.system_timers, SystemTimers::new()));
-                if let Some(timers) = &mut timers_temp {
-                    self.build_instances(timers);
-                } else {
-                    self.instance_buf.clear();
-                }
-
-                // --- Update trail and heatmap ---
-                {
-                    // Build trail positions from ECS with mood colors
-                    if self.trail_system.enabled {
-                        let mut trail_positions = Vec::with_capacity(self.world.len() as usize);
-                        for (_, (pos, appearance, cat_state)) in
-                            self.world.query::<(&Position, &Appearance, &CatState)>().iter()
-                        {
-                            let color = appearance.color;
-                            let base_r = ((color >> 24) & 0xFF) as f32 / 255.0;
-                            let base_g = ((color >> 16) & 0xFF) as f32 / 255.0;
-                            let base_b = ((color >> 8) & 0xFF) as f32 / 255.0;
-
-                            // Mood color based on behavior state
-                            let (mood_r, mood_g, mood_b) = mood_color(cat_state.state);
-
-                            trail_positions.push((
-                                pos.0.x, pos.0.y,
-                                base_r, base_g, base_b,
-                                mood_r, mood_g, mood_b,
-                            ));
-                        }
-                        self.trail_system.update(&trail_positions);
-                    }
-
-                    // Update heatmap with cursor position
-                    if self.heatmap.enabled {
-                        #[cfg(windows)]
-                        let (mx, my) = platform::win32::get_mouse_pos();
-                        #[cfg(not(windows))]
-                        let (mx, my) = (0.0f32, 0.0f32);
-                        self.heatmap.update(mx, my, TICK_RATE as f32);
+                        gpu.resize(window_id, new_size.width, new_size.height);
                     }
                 }
-
-                // --- GPU upload (timed) ---
-                if let Some(timers) = &mut timers_temp {
-                    timers.begin();
-                }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                 if let Some(gpu) = &mut self.gpu {
-                    gpu.update_instances(&self.instance_buf);
-
-                    // Upload trail vertices
-                    if self.trail_system.enabled {
-                        let trail_verts = self.trail_system.build_vertices();
-                        gpu.update_trails(trail_verts);
-                    }
-
-                    // Upload heatmap texture
-                    if self.heatmap.enabled {
-                        let heatmap_data = self.heatmap.to_texture_data();
-                        gpu.update_heatmap(&heatmap_data);
-                    }
-                }
-                if let Some(timers) = &mut timers_temp {
-                    timers.end(SystemPhase::GpuUpload);
-                }
-
-                // Put timers back
-                if let (Some(debug), Some(timers)) = (&mut self.debug, timers_temp) {
-                    debug.system_timers = timers;
-                }
-
-                // --- Run egui frame ---
-                let egui_output = if let (Some(debug), Some(window)) =
-                    (&mut self.debug, &self.window)
-                {
-                    if debug.visible {
-                        Some(debug.run_frame(window, self.screen_w, self.screen_h))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-
-                // --- Render ---
-                if let Some(gpu) = &mut self.gpu {
-                    let Some(mut frame) = gpu.begin_frame() else {
-                        return;
-                    };
-
-                    // Time the render submit phase
-                    if let Some(debug) = &mut self.debug {
-                        debug.system_timers.begin();
-                    }
-
-                    // Cat render pass (includes clear to transparent)
-                    gpu.draw_cats(&mut frame.encoder, &frame.view);
-
-                    // Heatmap overlay (behind trails/cats but after clear)
-                    if self.heatmap.enabled {
-                        gpu.draw_heatmap(&mut frame.encoder, &frame.view);
-                    }
-
-                    // Trail render pass (behind cats, after heatmap)
-                    if self.trail_system.enabled {
-                        gpu.draw_trails(&mut frame.encoder, &frame.view);
-                    }
-
-                    // Egui render pass (if visible)
-                    let mut extra_cmd_bufs = Vec::new();
-                    if let Some((ref primitives, ref textures_delta, ref screen_desc)) =
-                        egui_output
-                    {
-                        if let Some(debug) = &mut self.debug {
-                            let bufs = debug.prepare_egui(
-                                &gpu.device,
-                                &gpu.queue,
-                                &mut frame.encoder,
-                                primitives,
-                                textures_delta,
-                                screen_desc,
-                            );
-                            extra_cmd_bufs = bufs;
-
-                            {
-                                let mut egui_pass =
-                                    GpuState::begin_egui_pass(&mut frame.encoder, &frame.view);
-                                debug.render_egui(&mut egui_pass, primitives, screen_desc);
-                            }
-                        }
-                    }
-
-                    if let Some(debug) = &mut self.debug {
-                        debug.system_timers.end(SystemPhase::RenderSubmit);
-                    }
-
-                    gpu.finish_frame(frame.encoder, frame.output, extra_cmd_bufs);
-
-                    // Free egui textures after present
-                    if let Some((_, ref textures_delta, _)) = egui_output {
-                        if let Some(debug) = &mut self.debug {
-                            debug.free_textures(textures_delta);
-                        }
+                    let surface = gpu
+                        .surfaces
+                        .iter_mut()
+                        .find(|s| s.target.window().is_some_and(|w| w.id() == window_id));
+                    if let Some(s) = surface {
+                        s.scale = scale_factor as f32;
                     }
                 }
             }
+            // The primary overlay's redraw renders every monitor.
+            WindowEvent::RedrawRequested if primary => self.redraw(),
             _ => {}
         }
     }
 }
 
+/// Reload the config file if it changed. A bad file is reported and the
+/// previous tuning stays in effect.
+fn apply_config_change(sim: &mut Simulation, watcher: &mut ConfigWatcher) {
+    match watcher.poll() {
+        Some(Ok(config)) => {
+            sim.apply_config(config);
+            log::info!("Config loaded from {}", watcher.path().display());
+        }
+        Some(Err(e)) => log::error!("Config rejected, keeping previous values: {e}"),
+        None => {}
+    }
+}
+
+/// The laser pointer's pulsing red dot, at `mouse` in colony pixels (where
+/// the cats last saw the cursor).
+fn laser_dot(mouse: Vec2, time: f32) -> CatInstance {
+    let pulse = (time * 12.0).sin() * 0.15 + 1.0;
+    let s = 0.5 * pulse;
+    CatInstance {
+        position: mouse.into(),
+        size: [s, s],
+        color: 0xFF0000FF,
+        frame: 3,
+        rotation: 0.0,
+        clip: UNCLIPPED,
+    }
+}
+
+/// Scale every channel of a packed (premultiplied) RGBA u32 color by
+/// `amount`, fading it toward transparent.
+fn fade(color: u32, amount: f32) -> u32 {
+    let channel = |shift: u32| ((((color >> shift) & 0xFF) as f32 * amount) as u32) << shift;
+    channel(24) | channel(16) | channel(8) | channel(0)
+}
+
 /// Apply a color tint to a packed RGBA u32 color.
 fn apply_tint(color: u32, tint: [f32; 3]) -> u32 {
     let r = ((color >> 24) & 0xFF) as f32;
@@ -1148,77 +1464,243 @@ fn mood_color(state: BehaviorState) -> (f32, f32, f32) {
     }
 }
 
-/// Gravity for spawn animation (pixels/second²). Tuned for ~1s fall time.
-const SPAWN_GRAVITY: f32 = 1500.0;
-/// Velocity multiplier on bounce (energy retained).
-const BOUNCE_DAMPING: f32 = 0.4;
-/// After this many bounces, animation is done.
-const MAX_BOUNCES: u8 = 3;
-/// If velocity is below this after a bounce, stop early.
-const BOUNCE_VEL_THRESHOLD: f32 = 30.0;
-
-/// A bounce impact event — position + intensity for dust particles.
-struct BounceEvent {
-    pos: glam::Vec2,
-    intensity: f32, // 0.0–1.0, higher = bigger impact
-}
-
-/// Advance spawn drop-in animations with real physics.
-/// Gravity accelerates cats downward. On impact at target_y, velocity reverses
-/// with damping for a natural bounce. Returns bounce events for particle effects.
-fn update_spawn_animations(world: &mut hecs::World, dt: f32) -> Vec<BounceEvent> {
-    let mut done = Vec::new();
-    let mut bounces = Vec::new();
-
-    for (entity, (pos, prev_pos, anim)) in
-        world.query_mut::<(&mut Position, &mut PrevPosition, &mut SpawnAnimation)>()
-    {
-        // Apply gravity (positive Y = downward on screen)
-        anim.vel_y += SPAWN_GRAVITY * dt;
-        prev_pos.0.y = pos.0.y;
-        pos.0.y += anim.vel_y * dt;
-
-        // Ground collision detection
-        if pos.0.y >= anim.target_y {
-            // Record bounce intensity from impact velocity (before damping)
-            let impact_vel = anim.vel_y.abs();
-            let intensity = (impact_vel / 800.0).clamp(0.0, 1.0);
-
-            pos.0.y = anim.target_y;
-            anim.has_landed = true;
-            anim.bounce_count += 1;
-
-            // Reverse velocity with damping
-            anim.vel_y = -anim.vel_y.abs() * BOUNCE_DAMPING;
-
-            // Emit bounce event for dust particles
-            if intensity > 0.05 {
-                bounces.push(BounceEvent {
-                    pos: glam::Vec2::new(pos.0.x, anim.target_y),
-                    intensity,
-                });
-            }
-
-            // Check if animation is done (enough bounces or too little energy)
-            if anim.bounce_count >= MAX_BOUNCES || anim.vel_y.abs() < BOUNCE_VEL_THRESHOLD {
-                pos.0.y = anim.target_y;
-                prev_pos.0.y = anim.target_y;
-                done.push(entity);
-            }
+/// Connected monitors, primary first.
+fn connected_monitors(event_loop: &ActiveEventLoop) -> Vec<MonitorHandle> {
+    let mut monitors: Vec<MonitorHandle> = event_loop.available_monitors().collect();
+    if let Some(primary) = event_loop.primary_monitor() {
+        if let Some(i) = monitors.iter().position(|m| *m == primary) {
+            let primary = monitors.remove(i);
+            monitors.insert(0, primary);
         }
     }
+    monitors
+}
+
+/// A monitor's rectangle in virtual-desktop (physical) pixels.
+fn monitor_rect(monitor: &MonitorHandle) -> Monitor {
+    let pos = monitor.position();
+    let size = monitor.size();
+    Monitor::new(
+        pos.x as f32,
+        pos.y as f32,
+        size.width as f32,
+        size.height as f32,
+    )
+}
 
-    for entity in done {
-        let _ = world.remove_one::<SpawnAnimation>(entity);
+/// Sample everything the simulation needs from the OS for one tick.
+/// Screen positions are converted into colony coordinates; toy hotkeys
+/// fired since the last tick are drained from `toys`.
+fn sample_input(platform: &mut dyn Platform, desktop: &Desktop, toys: &mut ActionSet) -> SimInput {
+    // Poll mouse buttons once per tick. Backends latch clicks until read
+    // (GetAsyncKeyState's transition bit, XInput2 raw events on X11), so
+    // clicks can't be lost even when frame rate far exceeds tick rate.
+    let (left_down, right_down, middle_down) = platform.mouse_buttons();
+    SimInput {
+        mouse: desktop.to_colony(platform.mouse_pos()),
+        left_down,
+        right_down,
+        middle_down,
+        spawn_box: toys.take(Action::SpawnBox),
+        spawn_glass: toys.take(Action::SpawnGlass),
+        toggle_wand: toys.take(Action::ToggleWand),
+        idle_seconds: platform.idle_seconds(),
+        hour: platform.local_hour(),
+        weekday: platform.weekday(),
+        battery: platform.battery(),
     }
+}
 
-    bounces
+/// Convert enumerated desktop windows (topmost first) into the simulation's
+/// platforms.
+fn desktop_windows(rects: Vec<WindowRect>, desktop: &Desktop) -> Vec<DesktopWindow> {
+    // The desktop and taskbar aren't windows anything can sit on or behind
+    rects
+        .into_iter()
+        .filter(|r| !r.state.shell)
+        .enumerate()
+        .map(|(z, r)| {
+            let top_left = desktop.to_colony(Vec2::new(r.x as f32, r.y as f32));
+            DesktopWindow {
+                id: r.id,
+                left: top_left.x,
+                top: top_left.y,
+                right: top_left.x + r.w as f32,
+                bottom: top_left.y + r.h as f32,
+                z: z as u32,
+                state: r.state,
+                title: r.title,
+                process: r.process,
+            }
+        })
+        .collect()
 }
 
 /// Entry point — create event loop and run.
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let event_loop = EventLoop::new()?;
-    let mut app = App::new();
+pub fn run(opts: Options) -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(unused_mut)]
+    let mut builder = EventLoop::builder();
+    #[cfg(target_os = "linux")]
+    if platform::linux::use_xwayland() {
+        use winit::platform::x11::EventLoopBuilderExtX11;
+        log::info!("Wayland session: running through XWayland for global input");
+        builder.with_x11();
+    }
+    let event_loop = builder.build()?;
+    let mut app = App::new(opts);
     event_loop.run_app(&mut app)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::mock::MockPlatform;
+    use pettoy::ecs::systems::window_aware::WindowState;
+    use pettoy::config::HotkeysConfig;
+    use pettoy::input::Key;
+
+    #[test]
+    fn sample_input_reads_the_platform() {
+        let mut platform = MockPlatform::default();
+        platform.mouse = glam::Vec2::new(640.0, 360.0);
+        platform.buttons = (false, true, false);
+        platform.idle = 42.0;
+        platform.hour = 21.5;
+        platform.click((true, false, false));
+        platform.held.extend([Key::Ctrl, Key::Shift]);
+        platform.tap(Key::G);
+
+        let hotkeys = HotkeysConfig::default();
+        let mut toys = ActionState::default().poll(&hotkeys, &mut platform);
+        let desktop = Desktop::single(1920, 1080);
+        let input = sample_input(&mut platform, &desktop, &mut toys);
+        assert_eq!(input.mouse, glam::Vec2::new(640.0, 360.0));
+        assert!(input.left_down && input.right_down && !input.middle_down);
+        assert!(input.spawn_glass && !input.spawn_box);
+        assert_eq!(input.idle_seconds, 42.0);
+        assert_eq!(input.hour, 21.5);
+
+        // Clicks and toy actions are consumed; held buttons persist.
+        let input = sample_input(&mut platform, &desktop, &mut toys);
+        assert!(!input.left_down && input.right_down);
+        assert!(!input.spawn_glass);
+    }
+
+    #[test]
+    fn enumerated_windows_become_platforms() {
+        let mut platform = MockPlatform::default();
+        let focused = WindowState {
+            focused: true,
+            ..WindowState::default()
+        };
+        platform.windows.push(WindowRect {
+            id: 7,
+            x: 100,
+            y: 50,
+            w: 800,
+            h: 600,
+            state: focused,
+            title: "notes.txt".into(),
+            process: "editor".into(),
+        });
+        platform.windows.push(WindowRect {
+            id: 9,
+            x: 0,
+            y: 0,
+            w: 300,
+            h: 200,
+            ..WindowRect::default()
+        });
+
+        let desktop = Desktop::single(1920, 1080);
+        let platforms = desktop_windows(platform.enumerate_windows(), &desktop);
+        assert_eq!(
+            platforms[0],
+            DesktopWindow {
+                id: 7,
+                left: 100.0,
+                top: 50.0,
+                right: 900.0,
+                bottom: 650.0,
+                z: 0,
+                state: focused,
+                title: "notes.txt".into(),
+                process: "editor".into(),
+            }
+        );
+        assert_eq!((platforms[1].id, platforms[1].z), (9, 1));
+    }
+
+    #[test]
+    fn left_of_primary_monitor_maps_into_colony_space() {
+        let desktop = Desktop::from_virtual(&[
+            Monitor::new(0.0, 0.0, 1920.0, 1080.0),
+            Monitor::new(-1280.0, 0.0, 1280.0, 1024.0),
+        ]);
+        let mut platform = MockPlatform::default();
+        platform.mouse = glam::Vec2::new(-1000.0, 500.0);
+        platform.windows.push(WindowRect {
+            id: 3,
+            x: -1200,
+            y: 100,
+            w: 400,
+            h: 300,
+            title: "chat".into(),
+            ..WindowRect::default()
+        });
+
+        let input = sample_input(&mut platform, &desktop, &mut ActionSet::default());
+        assert_eq!(input.mouse, glam::Vec2::new(280.0, 500.0));
+        // The laser dot is drawn right where the cats see the cursor
+        assert_eq!(laser_dot(input.mouse, 0.0).position, [280.0, 500.0]);
+        let platforms = desktop_windows(platform.enumerate_windows(), &desktop);
+        assert_eq!(platforms[0].left, 80.0);
+        assert_eq!(platforms[0].right, 480.0);
+    }
+
+    #[test]
+    fn the_desktop_shell_hides_no_cats() {
+        let mut platform = MockPlatform::default();
+        platform.windows.push(WindowRect {
+            id: 2,
+            x: 0,
+            y: 0,
+            w: 400,
+            h: 300,
+            title: "browser".into(),
+            ..WindowRect::default()
+        });
+        // Explorer's desktop window fills the screen underneath everything
+        platform.windows.push(WindowRect {
+            id: 1,
+            x: 0,
+            y: 0,
+            w: 1920,
+            h: 1080,
+            state: WindowState {
+                fullscreen: true,
+                shell: true,
+                ..WindowState::default()
+            },
+            title: "Program Manager".into(),
+            process: "explorer.exe".into(),
+        });
+
+        let desktop = Desktop::single(1920, 1080);
+        let mut occlusion = Occlusion::default();
+        occlusion.update(&desktop_windows(platform.enumerate_windows(), &desktop));
+        assert!(occlusion.sprite(glam::Vec2::new(960.0, 540.0), 1.0).is_some());
+        assert!(occlusion.sprite(glam::Vec2::new(200.0, 150.0), 1.0).is_none());
+    }
+
+    #[test]
+    fn tray_commands_drain_in_order() {
+        let mut platform = MockPlatform::default();
+        platform.queue_tray(TrayCommand::SetModeZen);
+        platform.queue_tray(TrayCommand::Quit);
+        assert_eq!(platform.poll_tray(), TrayCommand::SetModeZen);
+        assert_eq!(platform.poll_tray(), TrayCommand::Quit);
+        assert_eq!(platform.poll_tray(), TrayCommand::None);
+    }
+}
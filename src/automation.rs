@@ -0,0 +1,322 @@
+//! Switching modes on their own.
+//!
+//! `[[automation.rules]]` in the config pair conditions (time of day,
+//! weekday, the focused app, idle time, battery) with a mode and preset keys.
+//! [`Automation`] checks them every tick and steers [`ModeState`] to the first
+//! rule that holds. A different rule has to keep holding for
+//! `automation.hold` seconds before it takes over, and picking a mode by hand
+//! wins until the rules move on to something else.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AutomationConfig, ModeRule, PresetOverride};
+use crate::mode::{AppMode, ModeState};
+use crate::presence::app_name;
+
+/// Day of the week, as written in `automation.rules[].days`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Weekday {
+    #[default]
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ];
+
+    /// Day `index` days after Monday.
+    pub fn from_index(index: u8) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+
+    /// Days since Monday.
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+}
+
+/// What the rules get to look at.
+#[derive(Debug, Clone, Copy)]
+pub struct Conditions<'a> {
+    /// Local hour, 0.0-24.0.
+    pub hour: f32,
+    pub weekday: Weekday,
+    /// Process name of the focused window, empty if unknown.
+    pub foreground: &'a str,
+    pub idle_seconds: f64,
+    /// Charge in percent while on battery power, `None` on mains.
+    pub battery: Option<f32>,
+}
+
+impl ModeRule {
+    /// Whether every condition of this rule holds.
+    pub fn matches(&self, c: &Conditions) -> bool {
+        let hours = self.hours.is_none_or(|[from, to]| {
+            if from <= to {
+                (from..to).contains(&c.hour)
+            } else {
+                c.hour >= from || c.hour < to
+            }
+        });
+        let foreground = app_name(c.foreground);
+        hours
+            && (self.days.is_empty() || self.days.contains(&c.weekday))
+            && (self.apps.is_empty() || self.apps.iter().any(|a| app_name(a) == foreground))
+            && self.idle_above.is_none_or(|s| c.idle_seconds >= s)
+            && self.idle_below.is_none_or(|s| c.idle_seconds < s)
+            && self.on_battery.is_none_or(|b| b == c.battery.is_some())
+            && self
+                .battery_below
+                .is_none_or(|p| c.battery.is_some_and(|charge| charge < p))
+    }
+}
+
+/// Which rule is steering the mode, and which one is waiting to.
+#[derive(Debug, Default)]
+pub struct Automation {
+    /// Index into `automation.rules` of the rule in effect.
+    active: Option<usize>,
+    /// What the rules point to right now, and for how many seconds they
+    /// have.
+    pending: Option<usize>,
+    held: f32,
+    /// Mode picked by hand before the rules took over, to go back to.
+    base: Option<AppMode>,
+    /// The user picked a mode by hand while `active` was in effect.
+    overridden: bool,
+}
+
+impl Automation {
+    /// Check the rules against `conditions` and steer `mode` when a new one
+    /// (or none) has held for long enough. Returns whether that happened.
+    pub fn update(
+        &mut self,
+        config: &AutomationConfig,
+        conditions: &Conditions,
+        mode: &mut ModeState,
+        dt: f32,
+    ) -> bool {
+        let matched = config.rules.iter().position(|r| r.matches(conditions));
+        if matched == self.pending {
+            self.held += dt;
+        } else {
+            self.pending = matched;
+            self.held = 0.0;
+        }
+
+        // AFK escalation is steering the mode for now
+        if mode.afk_active {
+            return false;
+        }
+        if let Some(rule) = self.active.and_then(|i| config.rules.get(i)) {
+            let base = self.base.unwrap_or(mode.mode);
+            if mode.mode != rule.mode.unwrap_or(base) || mode.overrides != rule.overrides() {
+                self.overridden = true;
+            }
+        }
+        if self.pending == self.active || self.held < config.hold {
+            return false;
+        }
+
+        match self.pending.and_then(|i| config.rules.get(i)) {
+            Some(rule) => {
+                if self.overridden {
+                    self.base = Some(mode.mode);
+                }
+                let base = *self.base.get_or_insert(mode.mode);
+                mode.steer(rule.mode.unwrap_or(base), rule.overrides());
+            }
+            None => self.release(mode),
+        }
+        self.active = self.pending;
+        self.overridden = false;
+        true
+    }
+
+    /// Let go of the mode, handing back the one picked by hand, and forget
+    /// every rule (e.g. because the rules were edited).
+    pub fn reset(&mut self, mode: &mut ModeState) {
+        self.release(mode);
+        *self = Self::default();
+    }
+
+    fn release(&mut self, mode: &mut ModeState) {
+        if let Some(base) = self.base.take() {
+            if !self.overridden {
+                mode.steer(base, PresetOverride::default());
+            }
+        }
+    }
+
+    /// Index into `automation.rules` of the rule in effect.
+    pub fn active(&self) -> Option<usize> {
+        self.active
+    }
+
+    /// One line for the debug overlay: the rule in effect and the one about
+    /// to replace it.
+    pub fn describe(&self, config: &AutomationConfig) -> String {
+        let name = |i: Option<usize>| match i.and_then(|i| config.rules.get(i)) {
+            Some(rule) if rule.name.is_empty() => format!("#{}", i.unwrap_or(0) + 1),
+            Some(rule) => rule.name.clone(),
+            None => "none".to_owned(),
+        };
+        let mut line = name(self.active);
+        if self.overridden {
+            line.push_str(" (overridden)");
+        }
+        if self.pending != self.active {
+            let left = (config.hold - self.held).max(0.0);
+            line.push_str(&format!(" -> {} in {left:.0}s", name(self.pending)));
+        }
+        line
+    }
+
+    /// Changes whenever [`Self::describe`] would read differently, without
+    /// building the line.
+    pub fn describe_key(
+        &self,
+        config: &AutomationConfig,
+    ) -> (Option<usize>, Option<usize>, bool, u32) {
+        let left = if self.pending != self.active {
+            (config.hold - self.held).max(0.0).round() as u32
+        } else {
+            0
+        };
+        (self.active, self.pending, self.overridden, left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conditions(hour: f32, foreground: &str) -> Conditions<'_> {
+        Conditions {
+            hour,
+            weekday: Weekday::Wed,
+            foreground,
+            idle_seconds: 0.0,
+            battery: None,
+        }
+    }
+
+    fn config() -> AutomationConfig {
+        AutomationConfig {
+            hold: 1.0,
+            rules: vec![
+                ModeRule {
+                    name: "Games".into(),
+                    apps: vec!["Steam.exe".into()],
+                    mode: Some(AppMode::Chaos),
+                    ..ModeRule::default()
+                },
+                ModeRule {
+                    name: "Office hours".into(),
+                    hours: Some([9.0, 17.0]),
+                    days: vec![Weekday::Mon, Weekday::Wed],
+                    mode: Some(AppMode::Work),
+                    chase_enabled: Some(true),
+                    ..ModeRule::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn conditions_all_have_to_hold() {
+        let night = ModeRule {
+            hours: Some([22.0, 6.0]),
+            battery_below: Some(30.0),
+            idle_above: Some(10.0),
+            ..ModeRule::default()
+        };
+        let mut c = Conditions {
+            idle_seconds: 20.0,
+            battery: Some(25.0),
+            ..conditions(23.5, "")
+        };
+        assert!(night.matches(&c));
+        c.hour = 3.0;
+        assert!(night.matches(&c));
+        c.hour = 12.0;
+        assert!(!night.matches(&c));
+        c.hour = 3.0;
+        c.battery = None;
+        assert!(!night.matches(&c));
+
+        let office = &config().rules[1];
+        assert!(office.matches(&conditions(9.0, "code")));
+        assert!(!office.matches(&conditions(17.0, "code")));
+        let friday = Conditions {
+            weekday: Weekday::Fri,
+            ..conditions(10.0, "code")
+        };
+        assert!(!office.matches(&friday));
+    }
+
+    #[test]
+    fn rules_take_over_after_holding_and_hand_the_mode_back() {
+        let config = config();
+        let mut automation = Automation::default();
+        let mut mode = ModeState::new();
+        let dt = 0.25;
+
+        // Office hours need a second of matching before they kick in
+        let work = conditions(10.0, "code");
+        for _ in 0..4 {
+            assert!(!automation.update(&config, &work, &mut mode, dt));
+        }
+        assert_eq!(mode.mode, AppMode::Play);
+        assert!(automation.update(&config, &work, &mut mode, dt));
+        assert_eq!(mode.mode, AppMode::Work);
+        assert!(mode.chase_enabled, "the rule overrides the Work preset");
+        assert_eq!(automation.describe(&config), "Office hours");
+
+        // A quick look at a game doesn't switch anything
+        let game = conditions(10.0, "steam");
+        let key = automation.describe_key(&config);
+        automation.update(&config, &game, &mut mode, dt);
+        assert_ne!(automation.describe_key(&config), key);
+        assert_eq!(automation.describe(&config), "Office hours -> Games in 1s");
+        automation.update(&config, &work, &mut mode, dt);
+        assert_eq!(mode.mode, AppMode::Work);
+
+        // Switching by hand wins until the rules move on
+        mode.set_mode(AppMode::Zen);
+        automation.update(&config, &work, &mut mode, dt);
+        assert_eq!(mode.mode, AppMode::Zen);
+        assert_eq!(automation.describe(&config), "Office hours (overridden)");
+        let evening = conditions(19.0, "code");
+        for _ in 0..5 {
+            automation.update(&config, &evening, &mut mode, dt);
+        }
+        assert_eq!(automation.active(), None);
+        assert_eq!(mode.mode, AppMode::Zen);
+
+        // Otherwise the mode from before the rules comes back
+        for _ in 0..5 {
+            automation.update(&config, &game, &mut mode, dt);
+        }
+        assert_eq!(mode.mode, AppMode::Chaos);
+        for _ in 0..5 {
+            automation.update(&config, &evening, &mut mode, dt);
+        }
+        assert_eq!(mode.mode, AppMode::Zen);
+        assert_eq!(mode.overrides, PresetOverride::default());
+    }
+}
@@ -1,17 +1,41 @@
+use crate::desktop::Desktop;
 use crate::ecs::components::*;
+use crate::journal::Journal;
+use crate::scent::CLANS;
 use glam::Vec2;
 
-/// Spawn a batch of cats with randomized attributes.
-/// Cats start above the screen and drop in with a somersault animation.
-pub fn spawn_cats(world: &mut hecs::World, count: usize, screen_w: f32, screen_h: f32) {
-    let mut rng = fastrand::Rng::new();
+/// Hands out [`CatId`]s in order. Ids are never reused, even after the cat
+/// is gone; the counter is saved with the colony.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CatIds {
+    pub next: u64,
+}
+
+impl CatIds {
+    pub fn allocate(&mut self) -> CatId {
+        let id = CatId(self.next);
+        self.next += 1;
+        id
+    }
+}
 
+/// Spawn a batch of cats with randomized attributes.
+/// Cats start above a monitor and drop in with a somersault animation.
+/// All randomness comes from `rng` so seeded colonies spawn identically.
+pub fn spawn_cats(
+    world: &mut hecs::World,
+    count: usize,
+    desktop: &Desktop,
+    ids: &mut CatIds,
+    rng: &mut fastrand::Rng,
+) {
     for _ in 0..count {
-        let target_x = rng.f32() * screen_w;
-        let target_y = rng.f32() * screen_h;
+        let monitor = desktop.random_monitor(rng);
+        let target = monitor.random_point(rng);
+        let target_y = target.y;
         // Start well above the screen for visible tumble time
-        let start_y = -(rng.f32() * 150.0 + 100.0);
-        let pos = Vec2::new(target_x, start_y);
+        let start_y = monitor.top - (rng.f32() * 150.0 + 100.0);
+        let pos = Vec2::new(target.x, start_y);
 
         world.spawn((
             Position(pos),
@@ -28,14 +52,27 @@ pub fn spawn_cats(world: &mut hecs::World, count: usize, screen_w: f32, screen_h
                 skittishness: rng.f32(),
             },
             Appearance {
-                color: random_cat_color(&mut rng),
+                color: random_cat_color(rng),
                 pattern: rng.u8(0..4),
                 size: 0.6 + rng.f32() * 0.8, // 0.6x to 1.4x
             },
             SpatialCell(0),
-            CatName(generate_cat_name(&mut rng)),
+            ids.allocate(),
+            CatName(generate_cat_name(rng)),
+            Age(0.0),
+            Journal::default(),
+            Needs {
+                energy: 0.5 + rng.f32() * 0.5,
+                hunger: 0.5 + rng.f32() * 0.5,
+                social: 0.5 + rng.f32() * 0.5,
+                play: 0.5 + rng.f32() * 0.5,
+                comfort: 0.5 + rng.f32() * 0.5,
+                motive: None,
+            },
+            Clan(rng.u8(0..CLANS as u8)),
+            Trust::default(),
             SpawnAnimation {
-                start_y: start_y,
+                start_y,
                 target_y,
                 vel_y: 0.0, // starts at rest, gravity accelerates
                 // All cats flip: 1 (20%), 2 (50%), 3 (30%)
@@ -51,6 +88,95 @@ pub fn spawn_cats(world: &mut hecs::World, count: usize, screen_w: f32, screen_h
     }
 }
 
+/// Size a newborn kitten starts at, relative to its adult size.
+pub const KITTEN_START_SCALE: f32 = 0.4;
+
+/// Spawn a newborn kitten at `pos` whose traits blend its two parents'.
+/// It starts at [`KITTEN_START_SCALE`] of its adult size and grows in
+/// `systems::lifecycle`.
+pub fn spawn_kitten(
+    world: &mut hecs::World,
+    pos: Vec2,
+    parents: [(Personality, Appearance); 2],
+    mutation: f32,
+    ids: &mut CatIds,
+    rng: &mut fastrand::Rng,
+) -> hecs::Entity {
+    let [(pa, la), (pb, lb)] = parents;
+    let personality = inherit_personality(&pa, &pb, mutation, rng);
+    let adult = inherit_appearance(&la, &lb, mutation, rng);
+
+    world.spawn((
+        Position(pos),
+        PrevPosition(pos),
+        Velocity(Vec2::ZERO),
+        CatState {
+            state: BehaviorState::Idle,
+            timer: 1.0 + rng.f32() * 2.0,
+        },
+        personality,
+        Appearance {
+            size: adult.size * KITTEN_START_SCALE,
+            ..adult
+        },
+        SpatialCell(0),
+        ids.allocate(),
+        CatName(generate_cat_name(rng)),
+        Age(0.0),
+        Journal::default(),
+        Needs::default(),
+        Trust::default(),
+        Kitten {
+            adult_size: adult.size,
+        },
+    ))
+}
+
+/// Random blend of `a` and `b`, nudged by up to `mutation` either way.
+fn blend(a: f32, b: f32, mutation: f32, rng: &mut fastrand::Rng) -> f32 {
+    a + (b - a) * rng.f32() + (rng.f32() * 2.0 - 1.0) * mutation
+}
+
+/// Each trait lands somewhere between the parents', plus a little drift.
+pub fn inherit_personality(
+    a: &Personality,
+    b: &Personality,
+    mutation: f32,
+    rng: &mut fastrand::Rng,
+) -> Personality {
+    let mut trait_of = |x: f32, y: f32| blend(x, y, mutation, rng).clamp(0.0, 1.0);
+    Personality {
+        laziness: trait_of(a.laziness, b.laziness),
+        energy: trait_of(a.energy, b.energy),
+        curiosity: trait_of(a.curiosity, b.curiosity),
+        skittishness: trait_of(a.skittishness, b.skittishness),
+    }
+}
+
+/// Coat color mixes the parents' channel by channel, the pattern comes
+/// from one parent, and adult size blends within the usual 0.6x-1.4x.
+pub fn inherit_appearance(
+    a: &Appearance,
+    b: &Appearance,
+    mutation: f32,
+    rng: &mut fastrand::Rng,
+) -> Appearance {
+    let t = rng.f32();
+    let mut color = 0xFF;
+    for shift in [24, 16, 8] {
+        let ca = ((a.color >> shift) & 0xFF) as f32;
+        let cb = ((b.color >> shift) & 0xFF) as f32;
+        let drift = (rng.f32() * 2.0 - 1.0) * mutation * 64.0;
+        let c = (ca + (cb - ca) * t + drift).round().clamp(0.0, 255.0) as u32;
+        color |= c << shift;
+    }
+    Appearance {
+        color,
+        pattern: if rng.bool() { a.pattern } else { b.pattern },
+        size: blend(a.size, b.size, mutation, rng).clamp(0.6, 1.4),
+    }
+}
+
 /// Generate a procedural cat name from name parts.
 fn generate_cat_name(rng: &mut fastrand::Rng) -> String {
     const PREFIXES: &[&str] = &[
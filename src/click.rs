@@ -1,17 +1,3 @@
-use glam::Vec2;
-
-/// A treat placed by middle-clicking — attracts nearby cats.
-#[derive(Debug, Clone, Copy)]
-pub struct Treat {
-    pub pos: Vec2,
-    pub vel: Vec2,
-    pub timer: f32,
-}
-
-/// Max active treats on screen at once.
-const MAX_TREATS: usize = 10;
-/// Treat lifetime in seconds.
-const TREAT_LIFETIME: f32 = 10.0;
 /// Double-click window in seconds.
 const DOUBLE_CLICK_WINDOW: f64 = 0.3;
 /// Laser pointer duration in seconds.
@@ -23,21 +9,26 @@ pub struct ClickState {
     right_was_down: bool,
     middle_was_down: bool,
     last_left_click_time: f64,
-    pub treats: Vec<Treat>,
     pub laser_active: bool,
     pub laser_timer: f32,
     /// Elapsed time since app start (accumulated).
     elapsed: f64,
     /// Set for one frame when left click detected.
     pub left_clicked: bool,
-    /// Set for one frame when right click detected.
+    /// Set for one frame when right click detected (yarn ball).
     pub right_clicked: bool,
     /// Set for one frame when double click detected.
     pub double_clicked: bool,
-    /// Set for one frame when middle click detected (yarn ball spawn/throw).
+    /// Set for one frame when middle click detected (treat).
     pub middle_clicked: bool,
 }
 
+impl Default for ClickState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ClickState {
     pub fn new() -> Self {
         Self {
@@ -45,7 +36,6 @@ impl ClickState {
             right_was_down: false,
             middle_was_down: false,
             last_left_click_time: -1.0,
-            treats: Vec::with_capacity(MAX_TREATS),
             laser_active: false,
             laser_timer: 0.0,
             elapsed: 0.0,
@@ -57,14 +47,7 @@ impl ClickState {
     }
 
     /// Update click state from raw button polling. Call once per frame.
-    pub fn update(
-        &mut self,
-        left_down: bool,
-        right_down: bool,
-        middle_down: bool,
-        mouse_pos: Vec2,
-        dt: f32,
-    ) {
+    pub fn update(&mut self, left_down: bool, right_down: bool, middle_down: bool, dt: f32) {
         self.elapsed += dt as f64;
         self.left_clicked = false;
         self.right_clicked = false;
@@ -83,7 +66,7 @@ impl ClickState {
         }
         self.left_was_down = left_down;
 
-        // Edge-detect right click (yarn ball spawn/throw)
+        // Edge-detect right click (yarn ball)
         if right_down && !self.right_was_down {
             self.right_clicked = true;
         }
@@ -92,33 +75,9 @@ impl ClickState {
         // Edge-detect middle click (treat)
         if middle_down && !self.middle_was_down {
             self.middle_clicked = true;
-            // Spawn a treat
-            if self.treats.len() < MAX_TREATS {
-                self.treats.push(Treat {
-                    pos: mouse_pos,
-                    vel: Vec2::ZERO,
-                    timer: TREAT_LIFETIME,
-                });
-            }
         }
         self.middle_was_down = middle_down;
 
-        // Update treat physics: cursor push, friction, timers
-        for treat in &mut self.treats {
-            // Mouse pushes treats
-            let to_treat = treat.pos - mouse_pos;
-            let dist = to_treat.length();
-            if dist < 80.0 && dist > 1.0 {
-                let push_dir = to_treat / dist;
-                let push_strength = (1.0 - dist / 80.0) * 300.0;
-                treat.vel += push_dir * push_strength * dt;
-            }
-            treat.pos += treat.vel * dt;
-            treat.vel *= 0.95; // friction
-            treat.timer -= dt;
-        }
-        self.treats.retain(|t| t.timer > 0.0);
-
         // Update laser timer
         if self.double_clicked {
             self.laser_active = true;
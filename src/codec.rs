@@ -0,0 +1,131 @@
+//! Little-endian byte packing shared by the on-disk formats
+//! (input logs in [`crate::replay`], colony saves in [`crate::save`]).
+
+use std::io;
+
+use glam::Vec2;
+
+/// Growable output buffer.
+#[derive(Default)]
+pub(crate) struct Writer {
+    pub buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+    pub fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+    pub fn u16(&mut self, v: u16) {
+        self.bytes(&v.to_le_bytes());
+    }
+    pub fn u32(&mut self, v: u32) {
+        self.bytes(&v.to_le_bytes());
+    }
+    pub fn u64(&mut self, v: u64) {
+        self.bytes(&v.to_le_bytes());
+    }
+    pub fn f32(&mut self, v: f32) {
+        self.bytes(&v.to_le_bytes());
+    }
+    pub fn f64(&mut self, v: f64) {
+        self.bytes(&v.to_le_bytes());
+    }
+    pub fn vec2(&mut self, v: Vec2) {
+        self.f32(v.x);
+        self.f32(v.y);
+    }
+    /// Length-prefixed UTF-8 (u16 length, truncated at 64 KiB).
+    pub fn str(&mut self, s: &str) {
+        let len = s.len().min(u16::MAX as usize);
+        self.u16(len as u16);
+        self.bytes(&s.as_bytes()[..len]);
+    }
+    /// Length-prefixed UTF-8 without the 64 KiB limit (u32 length).
+    pub fn text(&mut self, s: &str) {
+        self.u32(s.len() as u32);
+        self.bytes(s.as_bytes());
+    }
+}
+
+/// Cursor over an input buffer. Every read fails cleanly on truncation.
+pub(crate) struct Reader {
+    bytes: Vec<u8>,
+    pos: usize,
+}
+
+impl Reader {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    pub fn take<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let end = self.pos + N;
+        let Some(slice) = self.bytes.get(self.pos..end) else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected end of data",
+            ));
+        };
+        self.pos = end;
+        Ok(slice.try_into().expect("slice length is N"))
+    }
+
+    pub fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+    pub fn u16(&mut self) -> io::Result<u16> {
+        self.take().map(u16::from_le_bytes)
+    }
+    pub fn u32(&mut self) -> io::Result<u32> {
+        self.take().map(u32::from_le_bytes)
+    }
+    pub fn u64(&mut self) -> io::Result<u64> {
+        self.take().map(u64::from_le_bytes)
+    }
+    pub fn f32(&mut self) -> io::Result<f32> {
+        self.take().map(f32::from_le_bytes)
+    }
+    pub fn f64(&mut self) -> io::Result<f64> {
+        self.take().map(f64::from_le_bytes)
+    }
+    pub fn vec2(&mut self) -> io::Result<Vec2> {
+        Ok(Vec2::new(self.f32()?, self.f32()?))
+    }
+    pub fn str(&mut self) -> io::Result<String> {
+        let len = self.u16()? as usize;
+        self.utf8(len)
+    }
+    pub fn text(&mut self) -> io::Result<String> {
+        let len = self.u32()? as usize;
+        self.utf8(len)
+    }
+    fn utf8(&mut self, len: usize) -> io::Result<String> {
+        let end = self.pos + len;
+        let Some(slice) = self.bytes.get(self.pos..end) else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected end of data",
+            ));
+        };
+        self.pos = end;
+        String::from_utf8(slice.to_vec()).map_err(|e| invalid(e.to_string()))
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+}
+
+/// `InvalidData` error with a message.
+pub(crate) fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
@@ -0,0 +1,906 @@
+//! Tuning config — overrides for the colony's behavior constants, loaded from
+//! a TOML file and hot-reloaded while the overlay runs.
+//!
+//! Every field has a default matching the built-in tuning, so a config file
+//! only needs the keys it changes:
+//!
+//! ```toml
+//! [interaction]
+//! play_chance = 0.02
+//!
+//! [modes.work]
+//! edge_affinity = 0.9
+//! ```
+//!
+//! See `config.example.toml` for every key.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::automation::Weekday;
+use crate::input::{Action, Chord, Key, Modifiers};
+use crate::mode::AppMode;
+
+/// All tunable constants, grouped by the system that reads them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub population: PopulationConfig,
+    pub spatial: SpatialConfig,
+    pub behavior: BehaviorConfig,
+    pub interaction: InteractionConfig,
+    pub mouse: MouseConfig,
+    pub modes: ModesConfig,
+    pub afk: AfkConfig,
+    pub automation: AutomationConfig,
+    pub lifecycle: LifecycleConfig,
+    pub prey: PreyConfig,
+    pub gravity: GravityConfig,
+    pub navigation: NavigationConfig,
+    pub presence: PresenceConfig,
+    pub hotkeys: HotkeysConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PopulationConfig {
+    /// How many cats to spawn on startup.
+    pub initial_cat_count: usize,
+    /// Target population that the colony grows toward.
+    pub target_cat_count: usize,
+    /// Cats spawned per second during growth phase.
+    pub growth_rate: f64,
+    /// Seconds to wait before population starts growing.
+    pub growth_delay: f64,
+}
+
+impl Default for PopulationConfig {
+    fn default() -> Self {
+        Self {
+            initial_cat_count: 20,
+            target_cat_count: 1000,
+            growth_rate: 2.0,
+            growth_delay: 5.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpatialConfig {
+    /// Spatial hash cell size — 2x interaction radius.
+    pub cell_size: f32,
+}
+
+impl Default for SpatialConfig {
+    fn default() -> Self {
+        Self { cell_size: 128.0 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BehaviorConfig {
+    /// Chance per tick that an idle/walking cat gets zoomies (before energy mult).
+    pub zoomies_chance: f32,
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        Self {
+            zoomies_chance: 0.003,
+        }
+    }
+}
+
+/// Per-tick chances for cat-to-cat interactions (before personality scaling).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InteractionConfig {
+    pub play_chance: f32,
+    pub chase_chance: f32,
+    pub pounce_chance: f32,
+    pub fight_chance: f32,
+    /// Idle cat joins a nearby sleeper.
+    pub nap_cluster_chance: f32,
+    pub zoomie_contagion_chance: f32,
+    pub yawn_contagion_chance: f32,
+    /// Sleeping cat yawns spontaneously.
+    pub yawn_seed_chance: f32,
+    /// Idle curious cat sets off to bring the cursor a gift.
+    pub gift_spawn_chance: f32,
+}
+
+impl Default for InteractionConfig {
+    fn default() -> Self {
+        Self {
+            play_chance: 0.008,
+            chase_chance: 0.005,
+            pounce_chance: 0.002,
+            fight_chance: 0.001,
+            nap_cluster_chance: 0.015,
+            zoomie_contagion_chance: 0.05,
+            yawn_contagion_chance: 0.30,
+            yawn_seed_chance: 0.001,
+            gift_spawn_chance: 0.0003,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MouseConfig {
+    /// Distance within which cats notice the mouse.
+    pub notice_radius: f32,
+    /// Speed at which cats chase the mouse.
+    pub chase_speed: f32,
+    /// Chance per tick that a curious cat starts chasing.
+    pub chase_chance: f32,
+    /// Radius of cursor repulsion when the cursor moves fast.
+    pub moses_radius: f32,
+    /// Cursor speed (px/s) that triggers the Moses Effect.
+    pub moses_speed_threshold: f32,
+    /// Moses repulsion strength multiplier.
+    pub moses_strength: f32,
+    /// Max Moses repulsion velocity applied per tick.
+    pub moses_max_push: f32,
+    /// Skittish cats flee inside this radius.
+    pub flee_radius: f32,
+}
+
+impl Default for MouseConfig {
+    fn default() -> Self {
+        Self {
+            notice_radius: 200.0,
+            chase_speed: 100.0,
+            chase_chance: 0.02,
+            moses_radius: 400.0,
+            moses_speed_threshold: 100.0,
+            moses_strength: 3.5,
+            moses_max_push: 300.0,
+            flee_radius: 200.0,
+        }
+    }
+}
+
+/// Parameters one app mode applies when selected.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ModePreset {
+    /// 0.0 = uniform distribution, 1.0 = edges only.
+    pub edge_affinity: f32,
+    /// Multiplier on behavior energy (walk/run weights).
+    pub energy_scale: f32,
+    pub chase_enabled: bool,
+}
+
+impl Default for ModePreset {
+    fn default() -> Self {
+        Self {
+            edge_affinity: 0.0,
+            energy_scale: 1.0,
+            chase_enabled: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModesConfig {
+    pub work: ModePreset,
+    pub play: ModePreset,
+    pub zen: ModePreset,
+    pub chaos: ModePreset,
+}
+
+impl Default for ModesConfig {
+    fn default() -> Self {
+        Self {
+            work: ModePreset {
+                edge_affinity: 0.7,
+                energy_scale: 0.3,
+                chase_enabled: false,
+            },
+            play: ModePreset::default(),
+            zen: ModePreset {
+                energy_scale: 1.5,
+                ..ModePreset::default()
+            },
+            chaos: ModePreset {
+                energy_scale: 3.0,
+                ..ModePreset::default()
+            },
+        }
+    }
+}
+
+/// Preset keys given for one mode in the file, or set by a mode rule.
+/// Missing keys fall back to a base preset: the mode's own default, not a
+/// generic one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PresetOverride {
+    pub edge_affinity: Option<f32>,
+    pub energy_scale: Option<f32>,
+    pub chase_enabled: Option<bool>,
+}
+
+impl PresetOverride {
+    pub fn over(self, base: ModePreset) -> ModePreset {
+        ModePreset {
+            edge_affinity: self.edge_affinity.unwrap_or(base.edge_affinity),
+            energy_scale: self.energy_scale.unwrap_or(base.energy_scale),
+            chase_enabled: self.chase_enabled.unwrap_or(base.chase_enabled),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ModesConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Default, Deserialize)]
+        #[serde(default, deny_unknown_fields)]
+        struct ModesOverride {
+            work: PresetOverride,
+            play: PresetOverride,
+            zen: PresetOverride,
+            chaos: PresetOverride,
+        }
+
+        let o = ModesOverride::deserialize(d)?;
+        let base = ModesConfig::default();
+        Ok(Self {
+            work: o.work.over(base.work),
+            play: o.play.over(base.play),
+            zen: o.zen.over(base.zen),
+            chaos: o.chaos.over(base.chaos),
+        })
+    }
+}
+
+/// AFK escalation: drift toward center, then liven up, then Zen + bonus cats.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AfkConfig {
+    /// Whether to escalate at all.
+    pub enabled: bool,
+    /// Idle seconds before cats start drifting away from the edges.
+    pub drift_after: f64,
+    /// Idle seconds before cats get more energetic.
+    pub energize_after: f64,
+    /// Energy scale while energized.
+    pub energized_scale: f32,
+    /// Idle seconds before switching to Zen and spawning bonus cats.
+    pub zen_after: f64,
+    pub bonus_cats_per_minute: f64,
+    pub bonus_cats_cap: usize,
+}
+
+impl Default for AfkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            drift_after: 30.0,
+            energize_after: 120.0,
+            energized_scale: 1.5,
+            zen_after: 300.0,
+            bonus_cats_per_minute: 50.0,
+            bonus_cats_cap: 1000,
+        }
+    }
+}
+
+/// Kittens: bonded play partners breed, kittens inherit and grow up.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LifecycleConfig {
+    /// Whether bonded cats have kittens at all.
+    pub breeding: bool,
+    /// Seconds two adults must play together before they can breed.
+    pub bond_time: f32,
+    /// Chance per tick that a bonded pair at play has a litter.
+    pub breed_chance: f32,
+    /// Most kittens in one litter.
+    pub litter_max: usize,
+    /// Seconds for a kitten to grow to adult size.
+    pub grow_time: f32,
+    /// How far each inherited trait may drift from the parents' blend.
+    pub mutation: f32,
+}
+
+impl Default for LifecycleConfig {
+    fn default() -> Self {
+        Self {
+            breeding: true,
+            bond_time: 10.0,
+            breed_chance: 0.005,
+            litter_max: 3,
+            grow_time: 600.0,
+            mutation: 0.1,
+        }
+    }
+}
+
+/// Mice, bugs and butterflies for the cats to hunt (see `systems::prey`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PreyConfig {
+    /// Whether new prey turn up at all.
+    pub enabled: bool,
+    /// Most prey on the desktop at once.
+    pub max_prey: usize,
+    /// Average seconds between new prey turning up.
+    pub spawn_interval: f32,
+    /// Chance per tick that a fully curious, energetic cat starts stalking
+    /// prey it has noticed.
+    pub hunt_chance: f32,
+}
+
+impl Default for PreyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_prey: 5,
+            spawn_interval: 30.0,
+            hunt_chance: 0.01,
+        }
+    }
+}
+
+/// Platformer world: cats fall, stand on window tops and the bottom of the
+/// screen, and jump between them (see `systems::gravity`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GravityConfig {
+    /// Whether gravity is on. Off, cats roam the desktop freely.
+    pub enabled: bool,
+    /// Chance per tick that a walking cat jumps to another platform in reach.
+    pub jump_chance: f32,
+    /// Highest platform a cat can jump up to, in pixels.
+    pub jump_height: f32,
+}
+
+impl Default for GravityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            jump_chance: 0.005,
+            jump_height: 300.0,
+        }
+    }
+}
+
+/// Rules that switch modes on their own (see `crate::automation`). The first
+/// rule whose conditions all hold is the one in effect; when none does, the
+/// mode picked by hand comes back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AutomationConfig {
+    /// Seconds a different rule has to keep matching before it takes over
+    /// (or none has, before the rules let go), so a quick alt-tab doesn't
+    /// flip the mode back and forth.
+    pub hold: f32,
+    pub rules: Vec<ModeRule>,
+}
+
+impl Default for AutomationConfig {
+    fn default() -> Self {
+        Self {
+            hold: 5.0,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// One `[[automation.rules]]` entry: conditions, all of which must hold
+/// (unset ones always do), then the mode and preset keys it applies.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModeRule {
+    /// Shown in the debug overlay and the log.
+    pub name: String,
+    /// Local hours `[from, to)`, wrapping past midnight when `from > to`.
+    pub hours: Option<[f32; 2]>,
+    /// Days of the week (`"mon"` .. `"sun"`); empty means every day.
+    pub days: Vec<Weekday>,
+    /// Process names of the focused window (case-insensitive, `.exe`
+    /// optional); empty means any.
+    pub apps: Vec<String>,
+    /// Seconds without mouse or keyboard input.
+    pub idle_above: Option<f64>,
+    pub idle_below: Option<f64>,
+    /// `true` only on battery power, `false` only on mains.
+    pub on_battery: Option<bool>,
+    /// Battery charge in percent; only matches on battery power.
+    pub battery_below: Option<f32>,
+    /// Mode to switch to; unset keeps the mode picked by hand.
+    pub mode: Option<AppMode>,
+    pub edge_affinity: Option<f32>,
+    pub energy_scale: Option<f32>,
+    pub chase_enabled: Option<bool>,
+}
+
+impl ModeRule {
+    /// The preset keys this rule sets.
+    pub fn overrides(&self) -> PresetOverride {
+        PresetOverride {
+            edge_affinity: self.edge_affinity,
+            energy_scale: self.energy_scale,
+            chase_enabled: self.chase_enabled,
+        }
+    }
+}
+
+/// When the cats get out of the way (see `crate::presence`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PresenceConfig {
+    /// Hide while the focused window is fullscreen (games, video, slides).
+    pub fullscreen: bool,
+    /// Hide while any of these programs has a window open, e.g. screen
+    /// sharing or recording tools. Process names, case-insensitive, with or
+    /// without `.exe`.
+    pub apps: Vec<String>,
+    /// Seconds the cats take to fade out, and back in.
+    pub fade_time: f32,
+}
+
+impl Default for PresenceConfig {
+    fn default() -> Self {
+        Self {
+            fullscreen: true,
+            apps: Vec::new(),
+            fade_time: 1.0,
+        }
+    }
+}
+
+/// Routing for cats heading to treats, toys or the cursor (see `crate::nav`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NavigationConfig {
+    /// Route around obstacles; off means straight lines.
+    pub enabled: bool,
+    /// Extra cost per cell for crossing a desktop window.
+    pub window_cost: f32,
+    /// Extra cost per cell at full cursor heat.
+    pub heat_cost: f32,
+    /// Screen rectangles cats never path through.
+    pub avoid: Vec<AvoidZone>,
+}
+
+impl Default for NavigationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_cost: 3.0,
+            heat_cost: 4.0,
+            avoid: Vec::new(),
+        }
+    }
+}
+
+/// A no-go rectangle in virtual-screen pixels (the same coordinates the OS
+/// reports windows in).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AvoidZone {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+/// Global hotkeys: each action lists the chords that trigger it (`[]` to
+/// unbind). Toy hotkeys need modifiers by default so typing in other apps
+/// doesn't drop boxes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HotkeysConfig {
+    /// Whether toy hotkeys (spawn box/glass, feather wand) fire at all.
+    pub toys: bool,
+    pub quit: Vec<Chord>,
+    pub toggle_debug: Vec<Chord>,
+    pub cycle_mode: Vec<Chord>,
+    pub toggle_pause: Vec<Chord>,
+    pub do_not_disturb: Vec<Chord>,
+    pub spawn_box: Vec<Chord>,
+    pub spawn_glass: Vec<Chord>,
+    pub toggle_wand: Vec<Chord>,
+}
+
+impl Default for HotkeysConfig {
+    fn default() -> Self {
+        let toy = |key| vec![Chord::with(key, Modifiers::CTRL | Modifiers::SHIFT)];
+        Self {
+            toys: true,
+            quit: vec![Chord::new(Key::Escape)],
+            toggle_debug: vec![Chord::new(Key::F12)],
+            cycle_mode: vec![Chord::new(Key::F11)],
+            toggle_pause: Vec::new(),
+            do_not_disturb: Vec::new(),
+            spawn_box: toy(Key::B),
+            spawn_glass: toy(Key::G),
+            toggle_wand: toy(Key::F),
+        }
+    }
+}
+
+impl HotkeysConfig {
+    /// Chords bound to `action`.
+    pub fn bindings(&self, action: Action) -> &[Chord] {
+        match action {
+            Action::Quit => &self.quit,
+            Action::ToggleDebug => &self.toggle_debug,
+            Action::CycleMode => &self.cycle_mode,
+            Action::TogglePause => &self.toggle_pause,
+            Action::DoNotDisturb => &self.do_not_disturb,
+            Action::SpawnBox => &self.spawn_box,
+            Action::SpawnGlass => &self.spawn_glass,
+            Action::ToggleWand => &self.toggle_wand,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Loading + validation
+// ---------------------------------------------------------------------------
+
+/// Why a config file was rejected.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Parse(toml::de::Error),
+    /// Parsed fine but a value is out of range.
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            ConfigError::Parse(e) => write!(f, "{e}"),
+            ConfigError::Invalid(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Parse and validate TOML text.
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        let config: Config = toml::from_str(text).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Read, parse and validate a config file.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_owned(), e))?;
+        Self::from_toml(&text)
+    }
+
+    /// Serialize back to TOML (every key, defaults included).
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("config is always representable as TOML")
+    }
+
+    /// Default location: `config.toml` next to the colony save.
+    pub fn default_path() -> Option<PathBuf> {
+        crate::save::default_path().map(|p| p.with_file_name("config.toml"))
+    }
+
+    /// Check every value is in a range the systems can cope with.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        fn chance(name: &str, v: f32) -> Result<(), ConfigError> {
+            if (0.0..=1.0).contains(&v) {
+                Ok(())
+            } else {
+                Err(ConfigError::Invalid(format!(
+                    "{name} must be between 0 and 1 (got {v})"
+                )))
+            }
+        }
+        fn positive(name: &str, v: f64) -> Result<(), ConfigError> {
+            if v > 0.0 && v.is_finite() {
+                Ok(())
+            } else {
+                Err(ConfigError::Invalid(format!(
+                    "{name} must be greater than 0 (got {v})"
+                )))
+            }
+        }
+        fn non_negative(name: &str, v: f64) -> Result<(), ConfigError> {
+            if v >= 0.0 && v.is_finite() {
+                Ok(())
+            } else {
+                Err(ConfigError::Invalid(format!(
+                    "{name} must not be negative (got {v})"
+                )))
+            }
+        }
+
+        let p = &self.population;
+        if p.initial_cat_count > p.target_cat_count {
+            return Err(ConfigError::Invalid(format!(
+                "population.initial_cat_count ({}) exceeds population.target_cat_count ({})",
+                p.initial_cat_count, p.target_cat_count
+            )));
+        }
+        non_negative("population.growth_rate", p.growth_rate)?;
+        non_negative("population.growth_delay", p.growth_delay)?;
+
+        let cell = self.spatial.cell_size as f64;
+        positive("spatial.cell_size", cell)?;
+        if cell < 16.0 {
+            return Err(ConfigError::Invalid(format!(
+                "spatial.cell_size must be at least 16 (got {cell})"
+            )));
+        }
+
+        chance("behavior.zoomies_chance", self.behavior.zoomies_chance)?;
+
+        let i = &self.interaction;
+        for (name, v) in [
+            ("interaction.play_chance", i.play_chance),
+            ("interaction.chase_chance", i.chase_chance),
+            ("interaction.pounce_chance", i.pounce_chance),
+            ("interaction.fight_chance", i.fight_chance),
+            ("interaction.nap_cluster_chance", i.nap_cluster_chance),
+            (
+                "interaction.zoomie_contagion_chance",
+                i.zoomie_contagion_chance,
+            ),
+            ("interaction.yawn_contagion_chance", i.yawn_contagion_chance),
+            ("interaction.yawn_seed_chance", i.yawn_seed_chance),
+            ("interaction.gift_spawn_chance", i.gift_spawn_chance),
+        ] {
+            chance(name, v)?;
+        }
+
+        let m = &self.mouse;
+        chance("mouse.chase_chance", m.chase_chance)?;
+        for (name, v) in [
+            ("mouse.notice_radius", m.notice_radius),
+            ("mouse.chase_speed", m.chase_speed),
+            ("mouse.moses_radius", m.moses_radius),
+            ("mouse.moses_speed_threshold", m.moses_speed_threshold),
+            ("mouse.flee_radius", m.flee_radius),
+        ] {
+            positive(name, v as f64)?;
+        }
+        non_negative("mouse.moses_strength", m.moses_strength as f64)?;
+        non_negative("mouse.moses_max_push", m.moses_max_push as f64)?;
+
+        for (name, preset) in [
+            ("modes.work", &self.modes.work),
+            ("modes.play", &self.modes.play),
+            ("modes.zen", &self.modes.zen),
+            ("modes.chaos", &self.modes.chaos),
+        ] {
+            if !(0.0..=1.0).contains(&preset.edge_affinity) {
+                return Err(ConfigError::Invalid(format!(
+                    "{name}.edge_affinity must be between 0 and 1 (got {})",
+                    preset.edge_affinity
+                )));
+            }
+            non_negative(&format!("{name}.energy_scale"), preset.energy_scale as f64)?;
+        }
+
+        let a = &self.afk;
+        non_negative("afk.drift_after", a.drift_after)?;
+        if !(a.drift_after < a.energize_after && a.energize_after < a.zen_after) {
+            return Err(ConfigError::Invalid(format!(
+                "afk thresholds must increase: drift_after ({}) < energize_after ({}) < zen_after ({})",
+                a.drift_after, a.energize_after, a.zen_after
+            )));
+        }
+        non_negative("afk.energized_scale", a.energized_scale as f64)?;
+        non_negative("afk.bonus_cats_per_minute", a.bonus_cats_per_minute)?;
+
+        non_negative("automation.hold", self.automation.hold as f64)?;
+        for (i, rule) in self.automation.rules.iter().enumerate() {
+            let name = format!("automation.rules[{i}]");
+            if rule.mode.is_none() && rule.overrides() == PresetOverride::default() {
+                return Err(ConfigError::Invalid(format!(
+                    "{name} sets no mode and no preset keys"
+                )));
+            }
+            if let Some(hours) = rule.hours {
+                for h in hours {
+                    if !(0.0..=24.0).contains(&h) {
+                        return Err(ConfigError::Invalid(format!(
+                            "{name}.hours must be between 0 and 24 (got {h})"
+                        )));
+                    }
+                }
+            }
+            if let Some(v) = rule.idle_above {
+                non_negative(&format!("{name}.idle_above"), v)?;
+            }
+            if let Some(v) = rule.idle_below {
+                positive(&format!("{name}.idle_below"), v)?;
+            }
+            if let Some(v) = rule.battery_below {
+                positive(&format!("{name}.battery_below"), v as f64)?;
+            }
+            if let Some(v) = rule.edge_affinity {
+                chance(&format!("{name}.edge_affinity"), v)?;
+            }
+            if let Some(v) = rule.energy_scale {
+                non_negative(&format!("{name}.energy_scale"), v as f64)?;
+            }
+        }
+
+        let l = &self.lifecycle;
+        non_negative("lifecycle.bond_time", l.bond_time as f64)?;
+        chance("lifecycle.breed_chance", l.breed_chance)?;
+        if l.litter_max == 0 {
+            return Err(ConfigError::Invalid(
+                "lifecycle.litter_max must be at least 1".into(),
+            ));
+        }
+        positive("lifecycle.grow_time", l.grow_time as f64)?;
+        chance("lifecycle.mutation", l.mutation)?;
+
+        let p = &self.prey;
+        positive("prey.spawn_interval", p.spawn_interval as f64)?;
+        chance("prey.hunt_chance", p.hunt_chance)?;
+
+        let g = &self.gravity;
+        chance("gravity.jump_chance", g.jump_chance)?;
+        non_negative("gravity.jump_height", g.jump_height as f64)?;
+
+        let n = &self.navigation;
+        non_negative("navigation.window_cost", n.window_cost as f64)?;
+        non_negative("navigation.heat_cost", n.heat_cost as f64)?;
+        for (i, zone) in n.avoid.iter().enumerate() {
+            positive(&format!("navigation.avoid[{i}].w"), zone.w as f64)?;
+            positive(&format!("navigation.avoid[{i}].h"), zone.h as f64)?;
+        }
+
+        non_negative("presence.fade_time", self.presence.fade_time as f64)?;
+
+        let mut bound: Vec<(Chord, Action)> = Vec::new();
+        for action in Action::ALL {
+            for &chord in self.hotkeys.bindings(action) {
+                if let Some(&(_, other)) = bound.iter().find(|(c, _)| *c == chord) {
+                    return Err(ConfigError::Invalid(format!(
+                        "hotkeys.{} and hotkeys.{} are both bound to {chord}",
+                        other.config_key(),
+                        action.config_key()
+                    )));
+                }
+                bound.push((chord, action));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Hot reload
+// ---------------------------------------------------------------------------
+
+/// Polls a config file's modification time and reloads it when it changes.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    /// Watch `path`. The first [`Self::poll`] reports the file if it exists.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            modified: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the freshly loaded config if the file changed since the last
+    /// poll, or the reason it was rejected. A missing file is not a change.
+    pub fn poll(&mut self) -> Option<Result<Config, ConfigError>> {
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()?;
+        if self.modified == Some(modified) {
+            return None;
+        }
+        self.modified = Some(modified);
+        Some(Config::load(&self.path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_file_matches_defaults() {
+        let example = include_str!("../config.example.toml");
+        assert_eq!(
+            Config::from_toml(example).expect("example parses"),
+            Config::default()
+        );
+    }
+
+    #[test]
+    fn partial_file_overrides_only_its_keys() {
+        let config = Config::from_toml(
+            "[interaction]\nplay_chance = 0.02\n\n[modes.work]\nedge_affinity = 0.9\n",
+        )
+        .expect("valid");
+        assert_eq!(config.interaction.play_chance, 0.02);
+        assert_eq!(config.interaction.fight_chance, 0.001);
+        assert_eq!(config.modes.work.edge_affinity, 0.9);
+        assert_eq!(config.modes.work.energy_scale, 0.3);
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_bad_values() {
+        let typo = Config::from_toml("[interaction]\nplya_chance = 0.5\n").unwrap_err();
+        assert!(typo.to_string().contains("plya_chance"), "{typo}");
+
+        let range = Config::from_toml("[interaction]\nfight_chance = 1.5\n").unwrap_err();
+        assert!(
+            range.to_string().contains("interaction.fight_chance"),
+            "{range}"
+        );
+
+        let prey = Config::from_toml("[prey]\nspawn_interval = 0.0\n").unwrap_err();
+        assert!(prey.to_string().contains("prey.spawn_interval"), "{prey}");
+
+        let order = Config::from_toml("[afk]\nenergize_after = 10.0\n").unwrap_err();
+        assert!(order.to_string().contains("afk thresholds"), "{order}");
+
+        let zone = Config::from_toml("[navigation]\navoid = [{ x = 0, y = 0, w = -5, h = 10 }]\n")
+            .unwrap_err();
+        assert!(zone.to_string().contains("navigation.avoid[0].w"), "{zone}");
+
+        let rule = Config::from_toml("[[automation.rules]]\nhours = [9.0, 25.0]\nmode = \"work\"\n")
+            .unwrap_err();
+        assert!(rule.to_string().contains("automation.rules[0].hours"), "{rule}");
+        let idle = Config::from_toml("[[automation.rules]]\nidle_above = 60.0\n").unwrap_err();
+        assert!(idle.to_string().contains("sets no mode"), "{idle}");
+
+        let chord = Config::from_toml("[hotkeys]\nquit = [\"Ctrl+Q+W\"]\n").unwrap_err();
+        assert!(chord.to_string().contains("not a modifier"), "{chord}");
+
+        let clash =
+            Config::from_toml("[hotkeys]\ntoggle_pause = [\"f11\"]\n").unwrap_err();
+        assert!(
+            clash.to_string().contains("hotkeys.cycle_mode and hotkeys.toggle_pause"),
+            "{clash}"
+        );
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let mut config = Config::default();
+        config.mouse.moses_radius = 250.0;
+        config.hotkeys.toggle_pause = vec!["Alt+P".parse().expect("valid chord")];
+        config.navigation.avoid.push(AvoidZone {
+            x: 10.0,
+            y: 20.0,
+            w: 300.0,
+            h: 200.0,
+        });
+        config.automation.rules.push(ModeRule {
+            name: "Evenings".into(),
+            hours: Some([18.0, 23.0]),
+            days: vec![Weekday::Fri, Weekday::Sat],
+            mode: Some(AppMode::Chaos),
+            chase_enabled: Some(false),
+            ..ModeRule::default()
+        });
+        assert_eq!(Config::from_toml(&config.to_toml()).expect("valid"), config);
+    }
+}
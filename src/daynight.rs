@@ -1,5 +1,5 @@
-/// Day/night cycle based on system clock.
-/// Provides ambient color tint and behavior energy modifier.
+//! Day/night cycle based on the local clock.
+//! Provides ambient color tint and behavior energy modifier.
 
 /// Time-of-day state computed from the system clock.
 #[derive(Debug, Clone, Copy)]
@@ -14,6 +14,12 @@ pub struct DayNightState {
     pub is_night: bool,
 }
 
+impl Default for DayNightState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DayNightState {
     pub fn new() -> Self {
         let mut s = Self {
@@ -22,21 +28,13 @@ impl DayNightState {
             energy_modifier: 1.0,
             is_night: false,
         };
-        s.update();
+        s.update(12.0);
         s
     }
 
-    /// Refresh from system clock. Call once per frame or less.
-    pub fn update(&mut self) {
-        #[cfg(windows)]
-        {
-            self.hour = crate::platform::win32::get_local_hour();
-        }
-        #[cfg(not(windows))]
-        {
-            // Fallback: assume noon
-            self.hour = 12.0;
-        }
+    /// Refresh from a local hour (0.0-24.0) read by the platform layer.
+    pub fn update(&mut self, hour: f32) {
+        self.hour = hour;
         self.tint = compute_tint(self.hour);
         self.energy_modifier = compute_energy_modifier(self.hour);
         self.is_night = self.hour < 6.0 || self.hour >= 20.0;
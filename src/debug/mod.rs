@@ -1,22 +1,34 @@
 pub mod ring;
-pub mod timer;
 
 use winit::window::Window;
 
 use self::ring::RingBuffer;
-use self::timer::{SystemPhase, SystemTimers};
-use glam::Vec2;
 
-use crate::ecs::components::{BehaviorState, Personality};
-use crate::mode::AppMode;
+use pettoy::ecs::components::{BehaviorState, Need, Needs, Personality};
+use pettoy::mode::AppMode;
+use pettoy::timer::{SystemPhase, SystemTimers};
+
 use crate::render::GpuState;
 
+/// Other cats by name, with this cat's affinity toward them.
+pub type Acquaintances = Vec<(String, f32)>;
+
 /// Info about the cat currently under the mouse cursor.
 pub struct HoveredCatInfo {
-    pub screen_pos: Vec2,
     pub name: String,
     pub state: BehaviorState,
     pub personality: Personality,
+    /// Stable [`pettoy::ecs::components::CatId`], if the cat has one.
+    pub id: Option<u64>,
+    /// Journal highlights, e.g. "3 fights won".
+    pub highlights: Vec<String>,
+    /// Current needs and the one behind its last choice.
+    pub needs: Option<Needs>,
+    /// Trust toward the cursor, 0.0-1.0.
+    pub trust: Option<f32>,
+    /// Closest friends and worst rivals with their affinity, strongest first.
+    pub friends: Acquaintances,
+    pub rivals: Acquaintances,
 }
 
 /// Number of frame times to keep in the histogram.
@@ -29,7 +41,6 @@ pub struct DebugOverlay {
     pub egui_renderer: egui_wgpu::Renderer,
 
     pub visible: bool,
-    f12_was_down: bool,
 
     /// Rolling window of frame times (seconds).
     pub frame_times: RingBuffer<f64>,
@@ -59,14 +70,18 @@ pub struct DebugOverlay {
     pub idle_seconds: f64,
     pub edge_affinity: f32,
     pub energy_scale: f32,
+    /// Mode rule in effect, and the one about to take over.
+    pub mode_rule: String,
     pub mode_changed: bool,
     pub selected_mode_index: usize,
 
     /// Visual toggle controls.
     pub show_trails: bool,
     pub show_heatmap: bool,
+    pub show_scent: bool,
     pub show_particles: bool,
     pub force_night_eyes: bool,
+    pub occlusion: bool,
 
     /// Hovered cat tooltip info (updated by app each frame).
     pub hovered_cat: Option<HoveredCatInfo>,
@@ -103,7 +118,7 @@ impl DebugOverlay {
 
         let egui_renderer = egui_wgpu::Renderer::new(
             &gpu.device,
-            gpu.surface_config.format,
+            gpu.format,
             egui_wgpu::RendererOptions {
                 depth_stencil_format: None,
                 msaa_samples: 1,
@@ -117,7 +132,6 @@ impl DebugOverlay {
             egui_state,
             egui_renderer,
             visible: false,
-            f12_was_down: false,
             frame_times: RingBuffer::new(FRAME_HISTORY_LEN),
             fps: 0.0,
             frame_time_avg: 0.0,
@@ -135,12 +149,15 @@ impl DebugOverlay {
             idle_seconds: 0.0,
             edge_affinity: 0.0,
             energy_scale: 1.0,
+            mode_rule: String::new(),
             mode_changed: false,
             selected_mode_index: 1, // Play
             show_trails: false,
             show_heatmap: false,
+            show_scent: false,
             show_particles: true,
             force_night_eyes: false,
+            occlusion: true,
             hovered_cat: None,
             frame_count: 0,
             log_timer: 0.0,
@@ -199,20 +216,6 @@ impl DebugOverlay {
         }
     }
 
-    /// Handle F12 toggle. Returns true if visibility changed.
-    pub fn poll_toggle(&mut self, f12_down: bool) -> bool {
-        // Edge-detect: trigger on press, not hold.
-        if f12_down && !self.f12_was_down {
-            self.f12_was_down = true;
-            self.visible = !self.visible;
-            return true;
-        }
-        if !f12_down {
-            self.f12_was_down = false;
-        }
-        false
-    }
-
     /// Forward a winit event to egui. Returns true if egui consumed it.
     pub fn on_window_event(
         &mut self,
@@ -247,7 +250,10 @@ impl DebugOverlay {
         let (hovered_name, hovered_state, hovered_personality) =
             if let Some(ref info) = self.hovered_cat {
                 (
-                    Some(info.name.clone()),
+                    Some(match info.id {
+                        Some(id) => format!("{} #{id}", info.name),
+                        None => info.name.clone(),
+                    }),
                     Some(format!("{:?}", info.state)),
                     Some([
                         info.personality.laziness,
@@ -259,6 +265,18 @@ impl DebugOverlay {
             } else {
                 (None, None, None)
             };
+        let hovered_needs = self.hovered_cat.as_ref().and_then(|info| info.needs);
+        let hovered_trust = self.hovered_cat.as_ref().and_then(|info| info.trust);
+        let hovered_relations = self
+            .hovered_cat
+            .as_ref()
+            .map(|info| (info.friends.clone(), info.rivals.clone()))
+            .unwrap_or_default();
+        let hovered_highlights = self
+            .hovered_cat
+            .as_ref()
+            .map(|info| info.highlights.clone())
+            .unwrap_or_default();
 
         let ui_state = UiSnapshot {
             visible: self.visible,
@@ -270,13 +288,17 @@ impl DebugOverlay {
             system_durations: self.system_timers.durations_us,
             entity_count: self.entity_count,
             tick_count: self.tick_count,
-            current_mode: self.current_mode,
             idle_seconds: self.idle_seconds,
             edge_affinity: self.edge_affinity,
             energy_scale: self.energy_scale,
+            mode_rule: self.mode_rule.clone(),
             hovered_cat_name: hovered_name,
             hovered_cat_state: hovered_state,
             hovered_cat_personality: hovered_personality,
+            hovered_cat_highlights: hovered_highlights,
+            hovered_cat_needs: hovered_needs,
+            hovered_cat_trust: hovered_trust,
+            hovered_cat_relations: hovered_relations,
         };
 
         // Mutable controls — read from self, written back after run().
@@ -286,8 +308,10 @@ impl DebugOverlay {
         let mut selected_mode_index = self.selected_mode_index;
         let mut show_trails = self.show_trails;
         let mut show_heatmap = self.show_heatmap;
+        let mut show_scent = self.show_scent;
         let mut show_particles = self.show_particles;
         let mut force_night_eyes = self.force_night_eyes;
+        let mut occlusion = self.occlusion;
 
         let ctx = self.egui_ctx.clone();
         let full_output = ctx.run(raw_input, |ctx| {
@@ -295,7 +319,8 @@ impl DebugOverlay {
                 ctx, &ui_state,
                 &mut paused, &mut target_cat_count, &mut present_mode_index,
                 &mut selected_mode_index, &mut show_trails, &mut show_heatmap,
-                &mut show_particles, &mut force_night_eyes,
+                &mut show_scent, &mut show_particles, &mut force_night_eyes,
+                &mut occlusion,
             );
         });
 
@@ -315,8 +340,10 @@ impl DebugOverlay {
         self.selected_mode_index = selected_mode_index;
         self.show_trails = show_trails;
         self.show_heatmap = show_heatmap;
+        self.show_scent = show_scent;
         self.show_particles = show_particles;
         self.force_night_eyes = force_night_eyes;
+        self.occlusion = occlusion;
 
         self.egui_state
             .handle_platform_output(window, full_output.platform_output);
@@ -385,15 +412,20 @@ struct UiSnapshot {
     system_durations: [f64; 8],
     entity_count: usize,
     tick_count: u64,
-    current_mode: AppMode,
     idle_seconds: f64,
     edge_affinity: f32,
     energy_scale: f32,
+    mode_rule: String,
     hovered_cat_name: Option<String>,
     hovered_cat_state: Option<String>,
     hovered_cat_personality: Option<[f32; 4]>,
+    hovered_cat_highlights: Vec<String>,
+    hovered_cat_needs: Option<Needs>,
+    hovered_cat_trust: Option<f32>,
+    hovered_cat_relations: (Acquaintances, Acquaintances),
 }
 
+#[allow(clippy::too_many_arguments)]
 fn draw_ui(
     ctx: &egui::Context,
     s: &UiSnapshot,
@@ -403,8 +435,10 @@ fn draw_ui(
     selected_mode_index: &mut usize,
     show_trails: &mut bool,
     show_heatmap: &mut bool,
+    show_scent: &mut bool,
     show_particles: &mut bool,
     force_night_eyes: &mut bool,
+    occlusion: &mut bool,
 ) {
     if !s.visible {
         return;
@@ -545,8 +579,10 @@ fn draw_ui(
             ui.heading("Visuals");
             ui.checkbox(show_trails, "Show Trails");
             ui.checkbox(show_heatmap, "Show Heatmap");
+            ui.checkbox(show_scent, "Show Territories");
             ui.checkbox(show_particles, "Show Particles");
             ui.checkbox(force_night_eyes, "Force Night Eyes");
+            ui.checkbox(occlusion, "Hide Behind Windows");
             ui.add_space(4.0);
 
             // --- Mode ---
@@ -567,6 +603,7 @@ fn draw_ui(
                 "Edge: {:.2} | Energy: {:.2}x",
                 s.edge_affinity, s.energy_scale,
             ));
+            ui.label(format!("Rule: {}", s.mode_rule));
             ui.add_space(4.0);
 
             // --- Info ---
@@ -612,6 +649,32 @@ fn draw_ui(
                         ));
                     });
                 }
+                if let Some(needs) = s.hovered_cat_needs {
+                    let levels: Vec<String> = Need::ALL
+                        .iter()
+                        .map(|&n| format!("{:?}:{:.0}%", n, needs.get(n) * 100.0))
+                        .collect();
+                    ui.label(levels.join(" "));
+                    if let Some(motive) = needs.motive {
+                        ui.label(format!("Wants: {}", motive.label()));
+                    }
+                }
+                if let Some(trust) = s.hovered_cat_trust {
+                    ui.label(format!("Trusts you: {:.0}%", trust * 100.0));
+                }
+                let (friends, rivals) = &s.hovered_cat_relations;
+                for (label, cats) in [("Friends", friends), ("Rivals", rivals)] {
+                    if !cats.is_empty() {
+                        let names: Vec<String> = cats
+                            .iter()
+                            .map(|(name, affinity)| format!("{name} ({affinity:+.2})"))
+                            .collect();
+                        ui.label(format!("{label}: {}", names.join(", ")));
+                    }
+                }
+                for highlight in &s.hovered_cat_highlights {
+                    ui.label(highlight);
+                }
             });
     }
 }
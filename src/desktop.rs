@@ -0,0 +1,437 @@
+//! The virtual desktop the colony lives on.
+//!
+//! Monitors are rectangles in virtual-desktop pixels, which start at negative
+//! coordinates for screens left of or above the primary. The colony works in
+//! its own coordinates, with the top-left of the bounding box of every
+//! monitor at (0, 0); [`Desktop::to_colony`] and [`Desktop::to_virtual`]
+//! convert between the two.
+//!
+//! Cats are confined to monitor area. A monitor edge that faces a neighbour
+//! within [`LEAP_DISTANCE`] is open: cats walk straight across it, or leap the
+//! gap if the screens don't touch. Every other edge is a wall.
+
+use std::io;
+
+use glam::Vec2;
+
+use crate::codec::{invalid, Reader, Writer};
+
+/// Widest gap between two monitors a cat will leap across (pixels).
+pub const LEAP_DISTANCE: f32 = 160.0;
+
+/// One monitor's rectangle. Right and bottom are exclusive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Monitor {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Monitor {
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self {
+            left: x,
+            top: y,
+            right: x + w,
+            bottom: y + h,
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> f32 {
+        self.bottom - self.top
+    }
+
+    pub fn center(&self) -> Vec2 {
+        Vec2::new((self.left + self.right) * 0.5, (self.top + self.bottom) * 0.5)
+    }
+
+    pub fn contains(&self, p: Vec2) -> bool {
+        p.x >= self.left && p.x < self.right && p.y >= self.top && p.y < self.bottom
+    }
+
+    /// Distance from `p` to the nearest point of the monitor (0 inside).
+    pub fn distance(&self, p: Vec2) -> f32 {
+        let dx = (self.left - p.x).max(p.x - self.right).max(0.0);
+        let dy = (self.top - p.y).max(p.y - self.bottom).max(0.0);
+        Vec2::new(dx, dy).length()
+    }
+
+    /// `p` pulled at least `margin` pixels inside every edge.
+    pub fn clamp(&self, p: Vec2, margin: f32) -> Vec2 {
+        Vec2::new(
+            p.x.max(self.left + margin).min(self.right - margin),
+            p.y.max(self.top + margin).min(self.bottom - margin),
+        )
+    }
+
+    /// A uniformly random point on the monitor.
+    pub fn random_point(&self, rng: &mut fastrand::Rng) -> Vec2 {
+        let x = self.left + rng.f32() * self.width();
+        let y = self.top + rng.f32() * self.height();
+        Vec2::new(x, y)
+    }
+
+    fn offset(&self, by: Vec2) -> Self {
+        Self {
+            left: self.left + by.x,
+            top: self.top + by.y,
+            right: self.right + by.x,
+            bottom: self.bottom + by.y,
+        }
+    }
+}
+
+/// Which edges of a monitor face a neighbour and can be crossed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OpenEdges {
+    pub left: bool,
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool,
+}
+
+impl OpenEdges {
+    fn between(a: &Monitor, b: &Monitor) -> Self {
+        // Adjacent screens can overlap by a pixel of rounding.
+        let near = |gap: f32| (-1.0..=LEAP_DISTANCE).contains(&gap);
+        let side_by_side = a.top < b.bottom && b.top < a.bottom;
+        let stacked = a.left < b.right && b.left < a.right;
+        Self {
+            left: side_by_side && near(a.left - b.right),
+            top: stacked && near(a.top - b.bottom),
+            right: side_by_side && near(b.left - a.right),
+            bottom: stacked && near(b.top - a.bottom),
+        }
+    }
+
+    fn union(self, other: Self) -> Self {
+        Self {
+            left: self.left || other.left,
+            top: self.top || other.top,
+            right: self.right || other.right,
+            bottom: self.bottom || other.bottom,
+        }
+    }
+}
+
+/// Every monitor, in colony coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Desktop {
+    monitors: Vec<Monitor>,
+    open: Vec<OpenEdges>,
+    /// Virtual-desktop position of colony (0, 0).
+    origin: Vec2,
+    size: Vec2,
+}
+
+impl Desktop {
+    /// A single `w` x `h` screen at the origin.
+    pub fn single(w: u32, h: u32) -> Self {
+        Self::from_virtual(&[Monitor::new(0.0, 0.0, w as f32, h as f32)])
+    }
+
+    /// Build from monitor rectangles in virtual-desktop pixels. Mirrored
+    /// (identical) and empty rectangles are dropped; with nothing left this
+    /// falls back to a 1x1 screen.
+    pub fn from_virtual(rects: &[Monitor]) -> Self {
+        let mut virt: Vec<Monitor> = Vec::with_capacity(rects.len());
+        for &m in rects {
+            if m.width() > 0.0 && m.height() > 0.0 && !virt.contains(&m) {
+                virt.push(m);
+            }
+        }
+        if virt.is_empty() {
+            virt.push(Monitor::new(0.0, 0.0, 1.0, 1.0));
+        }
+
+        let min = virt
+            .iter()
+            .fold(Vec2::INFINITY, |acc, m| acc.min(Vec2::new(m.left, m.top)));
+        let max = virt.iter().fold(Vec2::NEG_INFINITY, |acc, m| {
+            acc.max(Vec2::new(m.right, m.bottom))
+        });
+        let monitors: Vec<Monitor> = virt.iter().map(|m| m.offset(-min)).collect();
+        let open = monitors
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                monitors
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .fold(OpenEdges::default(), |open, (_, b)| {
+                        open.union(OpenEdges::between(a, b))
+                    })
+            })
+            .collect();
+
+        Self {
+            monitors,
+            open,
+            origin: min,
+            size: max - min,
+        }
+    }
+
+    /// Monitors in colony coordinates. Never empty.
+    pub fn monitors(&self) -> &[Monitor] {
+        &self.monitors
+    }
+
+    /// Open edges of monitor `index`.
+    pub fn open_edges(&self, index: usize) -> OpenEdges {
+        self.open[index]
+    }
+
+    /// Virtual-desktop position of colony (0, 0).
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// Size of the bounding box of every monitor.
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn to_colony(&self, virtual_pos: Vec2) -> Vec2 {
+        virtual_pos - self.origin
+    }
+
+    pub fn to_virtual(&self, colony_pos: Vec2) -> Vec2 {
+        colony_pos + self.origin
+    }
+
+    /// Index of the monitor containing `p`.
+    pub fn monitor_at(&self, p: Vec2) -> Option<usize> {
+        self.monitors.iter().position(|m| m.contains(p))
+    }
+
+    /// Index of the monitor closest to `p`.
+    pub fn nearest(&self, p: Vec2) -> usize {
+        let mut best = (0, f32::INFINITY);
+        for (i, m) in self.monitors.iter().enumerate() {
+            let d = m.distance(p);
+            if d < best.1 {
+                best = (i, d);
+            }
+        }
+        best.0
+    }
+
+    /// The monitor `p` belongs to: the one it's on, or else the closest.
+    pub fn home(&self, p: Vec2) -> usize {
+        self.monitor_at(p).unwrap_or_else(|| self.nearest(p))
+    }
+
+    /// Pick a monitor, weighted by area. A single-monitor desktop uses no
+    /// randomness, so seeded runs match the old one-screen colony.
+    pub fn random_monitor(&self, rng: &mut fastrand::Rng) -> &Monitor {
+        if self.monitors.len() == 1 {
+            return &self.monitors[0];
+        }
+        let total: f32 = self.monitors.iter().map(|m| m.width() * m.height()).sum();
+        let mut pick = rng.f32() * total;
+        for m in &self.monitors {
+            pick -= m.width() * m.height();
+            if pick < 0.0 {
+                return m;
+            }
+        }
+        &self.monitors[self.monitors.len() - 1]
+    }
+
+    /// Where something moving from `from` to `to` ends up. Inside a monitor
+    /// it is only held off the walls; off every monitor it leaps onto the
+    /// closest monitor ahead within [`LEAP_DISTANCE`], or is pushed back onto
+    /// the monitor it came from.
+    pub fn confine(&self, from: Vec2, to: Vec2, margin: f32) -> Vec2 {
+        if let Some(i) = self.monitor_at(to) {
+            let m = &self.monitors[i];
+            let open = self.open[i];
+            let mut p = to;
+            if !open.left {
+                p.x = p.x.max(m.left + margin);
+            }
+            if !open.right {
+                p.x = p.x.min(m.right - margin);
+            }
+            if !open.top {
+                p.y = p.y.max(m.top + margin);
+            }
+            if !open.bottom {
+                p.y = p.y.min(m.bottom - margin);
+            }
+            return p;
+        }
+        // Off every monitor: leap to the closest screen ahead, if in reach.
+        let here = self.home(from);
+        let heading = to - from;
+        let mut leap = (here, LEAP_DISTANCE);
+        for (i, m) in self.monitors.iter().enumerate() {
+            let d = m.distance(to);
+            if i != here && d <= leap.1 && (m.center() - from).dot(heading) > 0.0 {
+                leap = (i, d);
+            }
+        }
+        self.monitors[leap.0].clamp(to, margin)
+    }
+
+    /// Whether `p` is on a monitor and at least `margin` from any wall.
+    pub fn inside(&self, p: Vec2, margin: f32) -> bool {
+        self.monitor_at(p).is_some() && self.confine(p, p, margin) == p
+    }
+
+    /// Move `p` (colony coordinates of `old`) onto this desktop after a
+    /// layout change. Anything on a monitor that is still connected keeps its
+    /// spot on screen; anything on a monitor that went away keeps its
+    /// relative position, on whichever monitor now covers that screen's
+    /// center (or the closest one).
+    pub fn rehome(&self, old: &Desktop, p: Vec2) -> Vec2 {
+        let from = old.monitors[old.home(p)];
+        let from_virtual = from.offset(old.origin);
+        if self
+            .monitors
+            .iter()
+            .any(|m| m.offset(self.origin) == from_virtual)
+        {
+            return self.to_colony(old.to_virtual(p));
+        }
+
+        let to = self.monitors[self.home(self.to_colony(from_virtual.center()))];
+        let rel = (p - Vec2::new(from.left, from.top)) / Vec2::new(from.width(), from.height());
+        Vec2::new(to.left, to.top) + rel * Vec2::new(to.width(), to.height())
+    }
+
+    /// Write the monitors (virtual-desktop pixels).
+    pub(crate) fn encode(&self, out: &mut Writer) {
+        out.u16(self.monitors.len() as u16);
+        for m in &self.monitors {
+            let v = m.offset(self.origin);
+            for edge in [v.left, v.top, v.right, v.bottom] {
+                out.f32(edge);
+            }
+        }
+    }
+
+    pub(crate) fn decode(r: &mut Reader) -> io::Result<Self> {
+        let count = r.u16()? as usize;
+        if count == 0 {
+            return Err(invalid("desktop with no monitors"));
+        }
+        let mut rects = Vec::with_capacity(count);
+        for _ in 0..count {
+            rects.push(Monitor {
+                left: r.f32()?,
+                top: r.f32()?,
+                right: r.f32()?,
+                bottom: r.f32()?,
+            });
+        }
+        Ok(Self::from_virtual(&rects))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1920x1080 primary with a 1280x1024 screen to its left, sitting lower.
+    fn dual() -> Desktop {
+        Desktop::from_virtual(&[
+            Monitor::new(0.0, 0.0, 1920.0, 1080.0),
+            Monitor::new(-1280.0, 200.0, 1280.0, 1024.0),
+        ])
+    }
+
+    #[test]
+    fn negative_origin_shifts_into_colony_space() {
+        let desktop = dual();
+        assert_eq!(desktop.origin(), Vec2::new(-1280.0, 0.0));
+        assert_eq!(desktop.size(), Vec2::new(3200.0, 1224.0));
+        let primary_corner = desktop.to_colony(Vec2::ZERO);
+        assert_eq!(primary_corner, Vec2::new(1280.0, 0.0));
+        assert_eq!(desktop.to_virtual(primary_corner), Vec2::ZERO);
+        assert_eq!(desktop.monitor_at(primary_corner), Some(0));
+    }
+
+    #[test]
+    fn single_monitor_clamps_like_a_screen() {
+        let desktop = Desktop::single(800, 600);
+        let margin = 8.0;
+        for p in [
+            Vec2::new(-50.0, 300.0),
+            Vec2::new(3.0, 3.0),
+            Vec2::new(799.0, 650.0),
+            Vec2::new(400.0, 300.0),
+        ] {
+            let expected = Vec2::new(p.x.clamp(margin, 792.0), p.y.clamp(margin, 592.0));
+            assert_eq!(desktop.confine(p, p, margin), expected);
+        }
+    }
+
+    #[test]
+    fn cats_walk_across_touching_edges() {
+        let desktop = dual();
+        let open = desktop.open_edges(desktop.monitor_at(Vec2::new(1500.0, 500.0)).unwrap());
+        assert!(open.left && !open.right && !open.top && !open.bottom);
+        // Stepping from the primary's left edge onto the side screen.
+        let to = desktop.confine(Vec2::new(1281.0, 500.0), Vec2::new(1279.0, 500.0), 8.0);
+        assert_eq!(to, Vec2::new(1279.0, 500.0));
+        assert_eq!(desktop.monitor_at(to), Some(1));
+    }
+
+    #[test]
+    fn cats_leap_small_gaps_but_not_large_ones() {
+        let gapped = |gap: f32| {
+            Desktop::from_virtual(&[
+                Monitor::new(0.0, 0.0, 1000.0, 800.0),
+                Monitor::new(1000.0 + gap, 0.0, 1000.0, 800.0),
+            ])
+        };
+        let near = gapped(100.0);
+        // One step past the open edge carries the cat over the whole gap.
+        let landed = near.confine(Vec2::new(998.0, 400.0), Vec2::new(1001.0, 400.0), 8.0);
+        assert_eq!(landed, Vec2::new(1108.0, 400.0));
+        // And back again the other way.
+        let back = near.confine(Vec2::new(1102.0, 400.0), Vec2::new(1099.0, 400.0), 8.0);
+        assert_eq!(near.monitor_at(back), Some(0));
+
+        let far = gapped(400.0);
+        let held = far.confine(Vec2::new(990.0, 400.0), Vec2::new(1001.0, 400.0), 8.0);
+        assert_eq!(held, Vec2::new(992.0, 400.0));
+    }
+
+    #[test]
+    fn rehome_keeps_cats_on_surviving_monitors() {
+        // Laptop docks: an external screen appears to the left.
+        let laptop = Desktop::single(1920, 1080);
+        let docked = dual();
+        let p = Vec2::new(100.0, 900.0);
+        assert_eq!(docked.rehome(&laptop, p), Vec2::new(1380.0, 900.0));
+    }
+
+    #[test]
+    fn rehome_moves_cats_off_lost_monitors() {
+        // Undock: the side screen goes away, its cats keep their relative spot.
+        let docked = dual();
+        let laptop = Desktop::single(1920, 1080);
+        let on_side = Vec2::new(640.0, 200.0 + 512.0);
+        let p = laptop.rehome(&docked, on_side);
+        assert_eq!(p, Vec2::new(960.0, 540.0));
+        assert!(laptop.inside(p, 8.0));
+    }
+
+    #[test]
+    fn codec_round_trip() {
+        let desktop = dual();
+        let mut out = Writer::default();
+        desktop.encode(&mut out);
+        let decoded = Desktop::decode(&mut Reader::new(out.buf)).expect("valid");
+        assert_eq!(decoded, desktop);
+    }
+}
@@ -40,6 +40,28 @@ pub enum BehaviorState {
     Fighting,
 }
 
+impl BehaviorState {
+    /// Inverse of `state as u8`, for decoding saved colonies.
+    pub fn from_u8(v: u8) -> Option<Self> {
+        use BehaviorState::*;
+        const ALL: [BehaviorState; 15] = [
+            Idle, Walking, Running, Sleeping, Grooming, ChasingMouse, FleeingCursor,
+            ChasingCat, Playing, Zoomies, Startled, Yawning, Parading, Pouncing, Fighting,
+        ];
+        ALL.get(v as usize).copied()
+    }
+}
+
+/// Identity that survives despawns and saves, unlike `hecs::Entity` which
+/// is recycled. Handed out by [`crate::cat::CatIds`] and never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CatId(pub u64);
+
+/// The clan whose scent a cat lays down and defends, below
+/// [`crate::scent::CLANS`]. Strays join one at random; kittens take a parent's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clan(pub u8);
+
 /// Cat name for tooltips.
 #[derive(Debug, Clone)]
 pub struct CatName(pub String);
@@ -53,6 +75,107 @@ pub struct Personality {
     pub skittishness: f32,
 }
 
+/// The five needs behind a cat's choices. See [`Needs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Need {
+    Energy,
+    Hunger,
+    Social,
+    Play,
+    Comfort,
+}
+
+impl Need {
+    pub const ALL: [Need; 5] = [
+        Need::Energy,
+        Need::Hunger,
+        Need::Social,
+        Need::Play,
+        Need::Comfort,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Need::Energy => "rest",
+            Need::Hunger => "food",
+            Need::Social => "company",
+            Need::Play => "play",
+            Need::Comfort => "comfort",
+        }
+    }
+}
+
+/// How well each need is met, in [0.0, 1.0] (1.0 = fully satisfied). They
+/// wear off or recover with what the cat is doing (`systems::needs`), and
+/// the most urgent ones pick its next idle-time activity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Needs {
+    /// Rested vs. exhausted. Sleep restores it, running drains it.
+    pub energy: f32,
+    /// Fed vs. starving. Treats fill it.
+    pub hunger: f32,
+    /// Time with other cats: play, parades, piles.
+    pub social: f32,
+    /// Chasing, pouncing and zoomies.
+    pub play: f32,
+    /// Grooming and sleeping piles; scares take it away.
+    pub comfort: f32,
+    /// The need behind the current activity, if one drove the choice.
+    pub motive: Option<Need>,
+}
+
+impl Default for Needs {
+    fn default() -> Self {
+        Self {
+            energy: 0.8,
+            hunger: 0.8,
+            social: 0.8,
+            play: 0.8,
+            comfort: 0.8,
+            motive: None,
+        }
+    }
+}
+
+impl Needs {
+    pub fn get(&self, need: Need) -> f32 {
+        match need {
+            Need::Energy => self.energy,
+            Need::Hunger => self.hunger,
+            Need::Social => self.social,
+            Need::Play => self.play,
+            Need::Comfort => self.comfort,
+        }
+    }
+
+    /// How pressing `need` is: 0.0 when met, 1.0 when empty.
+    pub fn urgency(&self, need: Need) -> f32 {
+        1.0 - self.get(need)
+    }
+}
+
+/// How far a cat trusts the cursor — its owner — in [0.0, 1.0]. Treats,
+/// petting and gifts it delivers build it up; click startles and being
+/// shoved aside by a fast cursor wear it down (`systems::trust`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trust(pub f32);
+
+impl Trust {
+    /// Where every cat starts: no reason yet to seek or avoid the cursor.
+    pub const NEUTRAL: f32 = 0.5;
+
+    /// Nudge by `delta`, clamped to [0.0, 1.0].
+    pub fn adjust(&mut self, delta: f32) {
+        self.0 = (self.0 + delta).clamp(0.0, 1.0);
+    }
+}
+
+impl Default for Trust {
+    fn default() -> Self {
+        Self(Self::NEUTRAL)
+    }
+}
+
 /// Visual appearance — packed for cache efficiency.
 #[derive(Debug, Clone, Copy)]
 pub struct Appearance {
@@ -64,6 +187,26 @@ pub struct Appearance {
     pub size: f32,
 }
 
+/// Simulated seconds since the cat was born (kittens) or dropped in (strays).
+#[derive(Debug, Clone, Copy)]
+pub struct Age(pub f32);
+
+/// A kitten still growing. `Appearance.size` ramps up to `adult_size` as
+/// it ages; the component is removed once it's grown.
+#[derive(Debug, Clone, Copy)]
+pub struct Kitten {
+    pub adult_size: f32,
+}
+
+/// The cat this one has been playing with, and for how long in total.
+/// Playing with someone else starts a new bond.
+#[derive(Debug, Clone, Copy)]
+pub struct Bond {
+    pub partner: hecs::Entity,
+    /// Seconds spent playing together since the last litter.
+    pub play_time: f32,
+}
+
 /// Cached spatial hash cell index for fast neighbor lookups.
 #[derive(Debug, Clone, Copy)]
 pub struct SpatialCell(pub u32);
@@ -92,6 +235,191 @@ pub struct GiftCarrier {
     pub timer: f32,
 }
 
+/// Small critters that wander the desktop for cats to hunt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreyKind {
+    /// Scurries in quick dashes along the ground.
+    Mouse,
+    /// Crawls slowly and erratically.
+    Bug,
+    /// Flutters about, bobbing up and down.
+    Butterfly,
+}
+
+/// A prey animal. Prey share the world with cats but have no [`CatState`],
+/// so cat systems pass them by; `systems::prey` moves them.
+#[derive(Debug, Clone, Copy)]
+pub struct Prey {
+    pub kind: PreyKind,
+    /// Direction it's wandering in (unit length).
+    pub heading: Vec2,
+    /// Seconds until it picks a new heading (or, for a mouse, dashes or stops).
+    pub timer: f32,
+    /// Seconds left before it wanders off the desktop for good.
+    pub lifetime: f32,
+}
+
+/// A cat stalking `prey`: it creeps up, pounces via the usual
+/// [`InteractionTarget`] wind-up, and catches it if the leap lands.
+#[derive(Debug, Clone, Copy)]
+pub struct Hunting {
+    pub prey: hecs::Entity,
+}
+
+/// A toy or treat lying on the desktop. What it is decides its
+/// [`crate::toy::ToySpec`]; the other toy components are spawned from that.
+#[derive(Debug, Clone, Copy)]
+pub struct Toy {
+    pub kind: crate::toy::ToyKind,
+}
+
+/// Seconds before a toy is cleared away.
+#[derive(Debug, Clone, Copy)]
+pub struct Lifetime(pub f32);
+
+/// How a toy reacts to the cursor and the edge of the screen. How it moves
+/// is down to its [`RigidBody`] and [`Collider`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToyBody {
+    /// The cursor pushes it away within `push_radius`, at up to
+    /// `push_strength` pixels/second².
+    pub push_radius: f32,
+    pub push_strength: f32,
+    /// Shatters on reaching a screen edge.
+    pub fragile: bool,
+}
+
+/// Collision shape, centred on the entity's position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    Circle { radius: f32 },
+    /// Axis-aligned box with the given half extents.
+    Rect { half: Vec2 },
+}
+
+impl Shape {
+    /// Half of the shape's height (how far it reaches below its centre).
+    pub fn half_height(self) -> f32 {
+        match self {
+            Shape::Circle { radius } => radius,
+            Shape::Rect { half } => half.y,
+        }
+    }
+
+    /// Radius of the smallest circle around the shape.
+    pub fn bound(self) -> f32 {
+        match self {
+            Shape::Circle { radius } => radius,
+            Shape::Rect { half } => half.length(),
+        }
+    }
+}
+
+/// Something physical bodies bump into (see `systems::physics`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Collider {
+    pub shape: Shape,
+    /// Fraction of the closing speed kept on impact (0 = dead stop,
+    /// 1 = perfectly elastic). Of two colliders the bouncier one wins.
+    pub restitution: f32,
+    /// Cats bump it along as they walk into it.
+    pub hit_by_cats: bool,
+}
+
+/// A body moved by the physics step. Needs a [`Collider`], a [`Position`]
+/// and a [`Velocity`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RigidBody {
+    /// 1 / mass; 0 for bodies nothing can move.
+    pub inv_mass: f32,
+    /// Fraction of its speed lost per second, as a rate:
+    /// `vel *= exp(-damping * dt)`.
+    pub damping: f32,
+    /// Bounces off monitor walls; otherwise slides past them.
+    pub walls: bool,
+}
+
+/// What a cat does with a toy once it's within reach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Play {
+    /// Chase it down, bat it away and dash off.
+    Bat,
+    /// Walk over and sit inside.
+    SitIn,
+    /// Walk over and eat it.
+    Eat,
+    /// Shove it along when bumping into it.
+    Nudge,
+}
+
+/// Which cats a toy draws in, from how far, and what they do with it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Appeal {
+    pub play: Play,
+    /// Cats notice it within this distance.
+    pub radius: f32,
+    /// Cats act on it (bat, sit, eat, shove) within this distance.
+    pub reach: f32,
+    /// Speed cats approach at.
+    pub speed: f32,
+    /// Impulse of a bat or shove.
+    pub strength: f32,
+    /// Trait that draws cats to it (and scales their speed), and the least
+    /// of it a cat needs to bother.
+    pub drive: Drive,
+    pub min_drive: f32,
+}
+
+/// A personality trait that makes a toy appealing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Drive {
+    Energy,
+    Curiosity,
+}
+
+impl Drive {
+    pub fn of(self, personality: &Personality) -> f32 {
+        match self {
+            Drive::Energy => personality.energy,
+            Drive::Curiosity => personality.curiosity,
+        }
+    }
+}
+
+/// How a toy is drawn through the instanced cat pipeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToySprite {
+    /// Shape index in the cat shader.
+    pub frame: u32,
+    pub size: [f32; 2],
+    /// RGB packed as 0xRRGGBB00; alpha comes from the fade.
+    pub color: u32,
+    /// Seconds of remaining lifetime over which it fades out.
+    pub fade: f32,
+    /// Size pulse amplitude (0.0 = steady).
+    pub pulse: f32,
+}
+
+/// A box with cats sitting in it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Occupancy {
+    pub occupants: u8,
+    pub capacity: u8,
+}
+
+/// A fragile toy that has hit the floor. It stays (unseen) until its
+/// lifetime runs out.
+#[derive(Debug, Clone, Copy)]
+pub struct Shattered;
+
+/// A cat in mid-air in gravity mode: jumping between platforms or falling
+/// off one. It flies a ballistic arc until it lands on something.
+#[derive(Debug, Clone, Copy)]
+pub struct Airborne {
+    /// Velocity along the arc (pixels/second); gravity pulls `y` down.
+    pub vel: Vec2,
+}
+
 /// Spawn drop-in animation. Physics-based: gravity fall, bounce on impact, eased rotation.
 #[derive(Debug, Clone, Copy)]
 pub struct SpawnAnimation {
@@ -108,3 +436,79 @@ pub struct SpawnAnimation {
     /// How many bounces have occurred.
     pub bounce_count: u8,
 }
+
+/// A cat for system tests: grey, normal-sized and middling in every trait,
+/// standing still and idle for a good while unless told otherwise.
+#[cfg(test)]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TestCat {
+    pos: Vec2,
+    vel: Vec2,
+    state: CatState,
+    personality: Personality,
+}
+
+#[cfg(test)]
+impl TestCat {
+    pub fn at(pos: Vec2) -> Self {
+        Self {
+            pos,
+            vel: Vec2::ZERO,
+            state: CatState {
+                state: BehaviorState::Idle,
+                timer: 30.0,
+            },
+            personality: Personality {
+                laziness: 0.5,
+                energy: 0.5,
+                curiosity: 0.5,
+                skittishness: 0.5,
+            },
+        }
+    }
+
+    pub fn moving(mut self, vel: Vec2) -> Self {
+        self.vel = vel;
+        self
+    }
+
+    pub fn state(mut self, state: BehaviorState, timer: f32) -> Self {
+        self.state = CatState { state, timer };
+        self
+    }
+
+    pub fn laziness(mut self, laziness: f32) -> Self {
+        self.personality.laziness = laziness;
+        self
+    }
+
+    pub fn energy(mut self, energy: f32) -> Self {
+        self.personality.energy = energy;
+        self
+    }
+
+    pub fn curiosity(mut self, curiosity: f32) -> Self {
+        self.personality.curiosity = curiosity;
+        self
+    }
+
+    pub fn skittishness(mut self, skittishness: f32) -> Self {
+        self.personality.skittishness = skittishness;
+        self
+    }
+
+    pub fn spawn(self, world: &mut hecs::World) -> hecs::Entity {
+        world.spawn((
+            Position(self.pos),
+            PrevPosition(self.pos),
+            Velocity(self.vel),
+            self.state,
+            self.personality,
+            Appearance {
+                color: 0x808080FF,
+                pattern: 0,
+                size: 1.0,
+            },
+        ))
+    }
+}
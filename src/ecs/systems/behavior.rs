@@ -1,6 +1,11 @@
 use glam::Vec2;
 
-use crate::ecs::components::{Appearance, BehaviorState, CatState, Personality, Position, Velocity};
+use crate::config::BehaviorConfig;
+use crate::ecs::components::{
+    Appearance, BehaviorState, CatState, Need, Needs, Personality, Velocity,
+};
+use crate::ecs::systems::needs;
+use crate::journal::{self, JournalEvent};
 
 /// Max walk speed in pixels/second.
 const WALK_SPEED: f32 = 40.0;
@@ -8,8 +13,6 @@ const WALK_SPEED: f32 = 40.0;
 const RUN_SPEED: f32 = 120.0;
 /// Zoomies speed — fast and frantic.
 const ZOOMIES_SPEED: f32 = 300.0;
-/// Chance per tick that an idle/walking cat gets zoomies (before energy mult).
-const ZOOMIES_CHANCE: f32 = 0.003;
 /// Startled upward velocity spike.
 const STARTLE_JUMP_VY: f32 = -200.0;
 /// Startled horizontal scatter speed.
@@ -23,10 +26,21 @@ fn size_speed_mult(size: f32) -> f32 {
 
 /// Update cat behavior state machines — handle transitions, timers.
 /// `energy_scale` combines mode preset with day/night modifier.
-pub fn update(world: &mut hecs::World, dt: f32, rng: &mut fastrand::Rng, energy_scale: f32) {
-    for (_, (state, personality, vel, pos, appearance)) in world
-        .query_mut::<(&mut CatState, &Personality, &mut Velocity, &Position, &Appearance)>()
-    {
+pub fn update(
+    world: &mut hecs::World,
+    dt: f32,
+    rng: &mut fastrand::Rng,
+    energy_scale: f32,
+    tuning: &BehaviorConfig,
+) {
+    let mut zoomers = Vec::new();
+    for (entity, (state, personality, vel, appearance, cat_needs)) in world.query_mut::<(
+        &mut CatState,
+        &Personality,
+        &mut Velocity,
+        &Appearance,
+        Option<&mut Needs>,
+    )>() {
         let speed_mult = size_speed_mult(appearance.size);
         state.timer -= dt;
 
@@ -134,29 +148,42 @@ pub fn update(world: &mut hecs::World, dt: f32, rng: &mut fastrand::Rng, energy_
             }
 
             // Normal transition to a new state
-            transition(state, personality, vel, pos, appearance, rng, energy_scale);
+            let mut fallback = Needs::default();
+            let cat_needs = cat_needs.unwrap_or(&mut fallback);
+            transition(state, personality, vel, appearance, cat_needs, rng, energy_scale, tuning);
+            if state.state == BehaviorState::Zoomies {
+                zoomers.push(entity);
+            }
         }
     }
+    for entity in zoomers {
+        journal::record(world, entity, JournalEvent::Zoomies);
+    }
 }
 
-/// Pick a new random state and configure velocity/timer.
+/// Pick the next state by what the cat needs most and configure
+/// velocity/timer. Records the driving need in `needs.motive`.
+#[allow(clippy::too_many_arguments)]
 fn transition(
     state: &mut CatState,
     personality: &Personality,
     vel: &mut Velocity,
-    _pos: &Position,
     appearance: &Appearance,
+    needs: &mut Needs,
     rng: &mut fastrand::Rng,
     energy_scale: f32,
+    tuning: &BehaviorConfig,
 ) {
     let speed_mult = size_speed_mult(appearance.size);
-    // Size-based laziness boost: big cats are lazier, small cats are more energetic
-    let size_lazy = ((appearance.size - 1.0) * 0.5).clamp(-0.15, 0.15);
 
     // Check for zoomies first (rare, energy-weighted)
     // Small cats get zoomies more often, big cats less. Night = fewer zoomies.
-    let zoomies_chance = ZOOMIES_CHANCE * personality.energy * speed_mult * energy_scale;
+    // Pent-up play makes them likelier; exhaustion rules them out.
+    let pent_up = needs.energy * (0.5 + needs.urgency(Need::Play));
+    let zoomies_chance =
+        tuning.zoomies_chance * personality.energy * speed_mult * energy_scale * pent_up;
     if rng.f32() < zoomies_chance {
+        needs.motive = Some(Need::Play);
         state.state = BehaviorState::Zoomies;
         state.timer = 1.0 + rng.f32() * 1.0; // 1-2s
         let angle = rng.f32() * std::f32::consts::TAU;
@@ -164,62 +191,35 @@ fn transition(
         return;
     }
 
-    // Weighted random state selection based on personality + size
-    let roll = rng.f32();
-
-    // Lazier/bigger cats idle/sleep more, energetic/smaller cats walk/run more
-    // energy_scale modulates from mode + day/night: <1 = sleepier, >1 = more active
-    let eff_laziness = (personality.laziness + size_lazy + (1.0 - energy_scale) * 0.2).clamp(0.0, 1.0);
-    let eff_energy = ((personality.energy - size_lazy) * energy_scale).clamp(0.0, 1.0);
-    let idle_weight = 0.25 + eff_laziness * 0.2;
-    let sleep_weight = 0.15 + eff_laziness * 0.15;
-    let groom_weight = 0.1;
-    let walk_weight = 0.25 + eff_energy * 0.15;
-    let run_weight = 0.1 + eff_energy * 0.1;
-
-    let total = idle_weight + sleep_weight + groom_weight + walk_weight + run_weight;
-    let r = roll * total;
-
-    let mut acc = 0.0;
-
-    acc += idle_weight;
-    if r < acc {
-        state.state = BehaviorState::Idle;
-        state.timer = 1.0 + rng.f32() * 3.0;
-        return;
-    }
-
-    acc += sleep_weight;
-    if r < acc {
-        state.state = BehaviorState::Sleeping;
-        state.timer = 3.0 + rng.f32() * 5.0;
-        return;
-    }
-
-    acc += groom_weight;
-    if r < acc {
-        state.state = BehaviorState::Grooming;
-        state.timer = 1.5 + rng.f32() * 2.0;
-        return;
-    }
-
-    acc += walk_weight;
-    if r < acc {
-        state.state = BehaviorState::Walking;
-        state.timer = 2.0 + rng.f32() * 4.0;
-        // Pick a random walk direction — small cats walk faster
-        let angle = rng.f32() * std::f32::consts::TAU;
-        let speed = WALK_SPEED * (0.5 + personality.energy * 0.5) * speed_mult;
-        vel.0 = Vec2::new(angle.cos(), angle.sin()) * speed;
-        return;
+    let (next, motive) = needs::choose(needs, personality, appearance.size, energy_scale, rng);
+    needs.motive = motive;
+    state.state = next;
+    match next {
+        BehaviorState::Sleeping => {
+            state.timer = 3.0 + rng.f32() * 5.0;
+        }
+        BehaviorState::Grooming => {
+            state.timer = 1.5 + rng.f32() * 2.0;
+        }
+        BehaviorState::Walking => {
+            state.timer = 2.0 + rng.f32() * 4.0;
+            // Pick a random walk direction — small cats walk faster
+            let angle = rng.f32() * std::f32::consts::TAU;
+            let speed = WALK_SPEED * (0.5 + personality.energy * 0.5) * speed_mult;
+            vel.0 = Vec2::new(angle.cos(), angle.sin()) * speed;
+        }
+        BehaviorState::Running => {
+            // Small cats sprint faster, big cats lumber
+            state.timer = 0.8 + rng.f32() * 1.5;
+            let angle = rng.f32() * std::f32::consts::TAU;
+            let speed = RUN_SPEED * (0.5 + personality.energy * 0.5) * speed_mult;
+            vel.0 = Vec2::new(angle.cos(), angle.sin()) * speed;
+        }
+        _ => {
+            state.state = BehaviorState::Idle;
+            state.timer = 1.0 + rng.f32() * 3.0;
+        }
     }
-
-    // Run — small cats sprint faster, big cats lumber
-    state.state = BehaviorState::Running;
-    state.timer = 0.8 + rng.f32() * 1.5;
-    let angle = rng.f32() * std::f32::consts::TAU;
-    let speed = RUN_SPEED * (0.5 + personality.energy * 0.5) * speed_mult;
-    vel.0 = Vec2::new(angle.cos(), angle.sin()) * speed;
 }
 
 /// Trigger a startle on a specific entity (called externally by interaction/click systems).
@@ -2,8 +2,9 @@ use glam::Vec2;
 
 use crate::click::ClickState;
 use crate::ecs::components::{BehaviorState, CatState, Personality, Position, Velocity};
-use crate::ecs::systems::behavior;
-use crate::toy::{Boxes, Glasses, YarnBalls};
+use crate::ecs::systems::{behavior, trust};
+use crate::nav::Navigator;
+use crate::toy::FeatherWand;
 
 /// Startle radius: cats within this of a click get startled.
 const STARTLE_RADIUS: f32 = 100.0;
@@ -12,42 +13,37 @@ const CLICK_FLEE_RADIUS: f32 = 200.0;
 /// Flee impulse strength.
 const CLICK_FLEE_STRENGTH: f32 = 80.0;
 
-/// Treat attraction radius.
-const TREAT_ATTRACT_RADIUS: f32 = 300.0;
-/// Treat approach speed (base, scaled by curiosity).
-const TREAT_APPROACH_SPEED: f32 = 60.0;
-
 /// Laser pointer chase speed multiplier.
 const LASER_CHASE_SPEED: f32 = 200.0;
 /// Laser jitter amplitude.
 const LASER_JITTER: f32 = 40.0;
 
-/// Yarn ball attraction radius.
-const YARN_ATTRACT_RADIUS: f32 = 250.0;
-/// Yarn ball chase speed.
-const YARN_CHASE_SPEED: f32 = 100.0;
-/// Yarn ball bat impulse when a cat reaches it.
-const YARN_BAT_IMPULSE: f32 = 250.0;
-
-/// Box attraction radius.
-const BOX_ATTRACT_RADIUS: f32 = 200.0;
-/// Box approach speed (curiosity-scaled).
-const BOX_APPROACH_SPEED: f32 = 40.0;
-
-/// Glass nudge radius: cats close to a glass will push it.
-const GLASS_NUDGE_RADIUS: f32 = 30.0;
-/// Glass push strength when a cat walks into it.
-const GLASS_PUSH_STRENGTH: f32 = 80.0;
-
-/// Process click interactions: startle, treats, laser pointer, yarn balls, boxes.
+/// Feather wand attraction radius.
+const WAND_ATTRACT_RADIUS: f32 = 220.0;
+/// Cats wait this far below a feather that isn't swinging low.
+const WAND_WATCH_DIST: f32 = 25.0;
+/// Speed of a cat heading under the feather (energy-scaled).
+const WAND_APPROACH_SPEED: f32 = 90.0;
+/// A cat bats a low-swinging feather within this reach...
+const WAND_BAT_REACH: f32 = 30.0;
+/// ...and jumps for one above it within this reach.
+const WAND_JUMP_REACH: f32 = 80.0;
+/// Impulse a bat gives the feather.
+const WAND_BAT_IMPULSE: f32 = 300.0;
+/// Speed of a cat's jump at the feather.
+const WAND_JUMP_SPEED: f32 = 260.0;
+
+/// Process cursor interactions: click startles, the laser pointer and the
+/// feather wand. Cats chasing the laser or the wand route around obstacles
+/// via `nav`. Toys and treats dropped by clicking are played with in
+/// [`super::toys::play`].
 pub fn update(
     world: &mut hecs::World,
     click: &ClickState,
     mouse_pos: Vec2,
     rng: &mut fastrand::Rng,
-    yarn_balls: &mut YarnBalls,
-    boxes: &mut Boxes,
-    glasses: &mut Glasses,
+    wand: &mut FeatherWand,
+    nav: &mut Navigator,
 ) {
     // --- Left click: startle nearest cat + flee impulse ---
     if click.left_clicked {
@@ -55,7 +51,7 @@ pub fn update(
         let mut nearest_entity = None;
         let mut nearest_dist_sq = STARTLE_RADIUS * STARTLE_RADIUS;
 
-        for (entity, pos) in world.query::<&Position>().iter() {
+        for (entity, pos) in world.query::<&Position>().with::<&CatState>().iter() {
             let dist_sq = (pos.0 - mouse_pos).length_squared();
             if dist_sq < nearest_dist_sq {
                 nearest_dist_sq = dist_sq;
@@ -63,13 +59,14 @@ pub fn update(
             }
         }
 
-        // Startle the nearest cat
+        // Startle the nearest cat, who trusts the cursor a little less for it
         if let Some(entity) = nearest_entity {
-            if let Ok((mut state, mut vel)) =
+            if let Ok((state, vel)) =
                 world.query_one_mut::<(&mut CatState, &mut Velocity)>(entity)
             {
-                behavior::trigger_startle(&mut state, &mut vel, rng);
+                behavior::trigger_startle(state, vel, rng);
             }
+            trust::adjust(world, entity, -trust::STARTLE_DISTRUST);
         }
 
         // Flee impulse for all cats in wider radius
@@ -97,42 +94,6 @@ pub fn update(
         }
     }
 
-    // --- Treats: attract nearby idle/walking cats ---
-    if !click.treats.is_empty() {
-        for (_, (pos, vel, state, personality)) in world
-            .query_mut::<(&Position, &mut Velocity, &mut CatState, &Personality)>()
-        {
-            if !matches!(
-                state.state,
-                BehaviorState::Idle | BehaviorState::Walking
-            ) {
-                continue;
-            }
-
-            let mut best_treat: Option<Vec2> = None;
-            let mut best_dist_sq = TREAT_ATTRACT_RADIUS * TREAT_ATTRACT_RADIUS;
-            for treat in &click.treats {
-                let dist_sq = (treat.pos - pos.0).length_squared();
-                if dist_sq < best_dist_sq {
-                    best_dist_sq = dist_sq;
-                    best_treat = Some(treat.pos);
-                }
-            }
-
-            if let Some(treat_pos) = best_treat {
-                let to_treat = treat_pos - pos.0;
-                let dist = to_treat.length();
-                if dist > 5.0 {
-                    let dir = to_treat / dist;
-                    let speed = TREAT_APPROACH_SPEED * (0.5 + personality.curiosity * 1.0);
-                    vel.0 = dir * speed;
-                    state.state = BehaviorState::Walking;
-                    state.timer = 0.5;
-                }
-            }
-        }
-    }
-
     // --- Double click laser pointer: frenzied chasing ---
     if click.laser_active {
         for (_, (pos, vel, state, personality)) in world
@@ -154,7 +115,7 @@ pub fn update(
             let to_laser = mouse_pos - pos.0;
             let dist = to_laser.length();
             if dist > 10.0 && dist < 500.0 {
-                let dir = to_laser / dist;
+                let dir = nav.steer(pos.0, mouse_pos);
                 let jitter = Vec2::new(
                     (rng.f32() - 0.5) * LASER_JITTER,
                     (rng.f32() - 0.5) * LASER_JITTER,
@@ -167,162 +128,65 @@ pub fn update(
         }
     }
 
-    // --- Right click: always spawn a new yarn ball ---
-    if click.right_clicked {
-        yarn_balls.spawn(mouse_pos);
-    }
-
-    // --- Yarn balls: attract nearby cats, let them bat ---
-    if yarn_balls.any_active() {
-        let attract_sq = YARN_ATTRACT_RADIUS * YARN_ATTRACT_RADIUS;
-        // Collect bat impulses per ball
-        let mut bat_impulses: Vec<(usize, Vec2)> = Vec::new();
+    // --- Feather wand: cats gather under it, bat and jump at low swings ---
+    if wand.active {
+        let feather = wand.feather();
+        let swinging_low = wand.swinging_low();
+        let attract_sq = WAND_ATTRACT_RADIUS * WAND_ATTRACT_RADIUS;
+        let mut bats: Vec<Vec2> = Vec::new();
 
         for (_, (pos, vel, state, personality)) in world
             .query_mut::<(&Position, &mut Velocity, &mut CatState, &Personality)>()
         {
             if !matches!(
                 state.state,
-                BehaviorState::Idle | BehaviorState::Walking | BehaviorState::Running
+                BehaviorState::Idle
+                    | BehaviorState::Walking
+                    | BehaviorState::Running
+                    | BehaviorState::ChasingMouse
             ) {
                 continue;
             }
             if personality.energy < 0.3 {
                 continue;
             }
-
-            // Find nearest yarn ball
-            let mut best_idx: Option<usize> = None;
-            let mut best_dist_sq = attract_sq;
-            for (i, ball) in yarn_balls.balls.iter().enumerate() {
-                let dist_sq = (ball.pos - pos.0).length_squared();
-                if dist_sq < best_dist_sq {
-                    best_dist_sq = dist_sq;
-                    best_idx = Some(i);
-                }
-            }
-
-            if let Some(idx) = best_idx {
-                let yarn_pos = yarn_balls.balls[idx].pos;
-                let to_yarn = yarn_pos - pos.0;
-                let dist = best_dist_sq.sqrt();
-
-                if dist < 20.0 {
-                    // Bat the ball
-                    let bat_dir = Vec2::new(rng.f32() - 0.5, rng.f32() - 0.5).normalize_or_zero();
-                    bat_impulses.push((idx, bat_dir * YARN_BAT_IMPULSE * (0.5 + personality.energy * 0.5)));
-                    // Cat runs away after batting
-                    let away = -bat_dir;
-                    vel.0 = away * 120.0 * personality.energy;
-                    state.state = BehaviorState::Running;
-                    state.timer = 0.8 + rng.f32() * 0.7;
-                } else {
-                    // Chase toward nearest yarn ball
-                    let dir = to_yarn / dist;
-                    let speed = YARN_CHASE_SPEED * (0.5 + personality.energy * 0.5);
-                    vel.0 = dir * speed;
-                    state.state = BehaviorState::Running;
-                    state.timer = 0.5;
-                }
-            }
-        }
-
-        // Apply bat impulses
-        for (idx, impulse) in bat_impulses {
-            yarn_balls.bat(idx, impulse);
-        }
-    }
-
-    // --- Cardboard boxes: attract idle/walking cats, they sit inside ---
-    if !boxes.boxes.is_empty() {
-        let attract_sq = BOX_ATTRACT_RADIUS * BOX_ATTRACT_RADIUS;
-
-        for (_, (pos, vel, state, personality)) in world
-            .query_mut::<(&Position, &mut Velocity, &mut CatState, &Personality)>()
-        {
-            if !matches!(
-                state.state,
-                BehaviorState::Idle | BehaviorState::Walking
-            ) {
-                continue;
-            }
-            if personality.curiosity < 0.3 {
+            let to_feather = feather - pos.0;
+            let dist_sq = to_feather.length_squared();
+            if dist_sq > attract_sq {
                 continue;
             }
-
-            // Find nearest box with room
-            let mut best_idx: Option<usize> = None;
-            let mut best_dist_sq = attract_sq;
-            for (i, cbox) in boxes.boxes.iter().enumerate() {
-                if cbox.occupants >= 2 {
-                    continue;
-                }
-                let dist_sq = (cbox.pos - pos.0).length_squared();
-                if dist_sq < best_dist_sq {
-                    best_dist_sq = dist_sq;
-                    best_idx = Some(i);
-                }
-            }
-
-            if let Some(idx) = best_idx {
-                let box_pos = boxes.boxes[idx].pos;
-                let to_box = box_pos - pos.0;
-                let dist = best_dist_sq.sqrt();
-
-                if dist < 25.0 {
-                    // Cat sits in the box (becomes idle/grooming at box position)
+            let dist = dist_sq.sqrt();
+            let eagerness = 0.5 + personality.energy * 0.5;
+
+            if swinging_low && dist < WAND_BAT_REACH {
+                // Swat it sideways, away from the cat
+                let side = if to_feather.x >= 0.0 { 1.0 } else { -1.0 };
+                bats.push(Vec2::new(side, -0.5).normalize() * WAND_BAT_IMPULSE * eagerness);
+                vel.0 = Vec2::ZERO;
+                state.state = BehaviorState::Pouncing;
+                state.timer = 0.3;
+            } else if swinging_low && dist < WAND_JUMP_REACH && to_feather.y < 0.0 {
+                // Leap up at it
+                vel.0 = to_feather / dist * WAND_JUMP_SPEED * eagerness;
+                state.state = BehaviorState::Running;
+                state.timer = 0.35;
+            } else if state.state != BehaviorState::Running {
+                // Wait underneath, eyes on the feather
+                let spot = feather + Vec2::Y * WAND_WATCH_DIST;
+                if (spot - pos.0).length_squared() > 100.0 {
+                    let dir = nav.steer(pos.0, spot);
+                    vel.0 = dir * WAND_APPROACH_SPEED * eagerness;
+                    state.state = BehaviorState::ChasingMouse;
+                } else {
                     vel.0 = Vec2::ZERO;
                     state.state = BehaviorState::Idle;
-                    state.timer = 5.0 + rng.f32() * 10.0; // sit for a while
-                    boxes.boxes[idx].occupants = (boxes.boxes[idx].occupants + 1).min(2);
-                } else {
-                    // Walk toward box
-                    let dir = to_box / dist;
-                    let speed = BOX_APPROACH_SPEED * (0.5 + personality.curiosity * 0.5);
-                    vel.0 = dir * speed;
-                    state.state = BehaviorState::Walking;
-                    state.timer = 0.5;
-                }
-            }
-        }
-    }
-
-    // --- Water glasses: cats nudge them toward screen edges ---
-    if !glasses.glasses.is_empty() {
-        let nudge_sq = GLASS_NUDGE_RADIUS * GLASS_NUDGE_RADIUS;
-        let mut pushes: Vec<(usize, Vec2)> = Vec::new();
-
-        for (_, (pos, state, personality)) in world
-            .query_mut::<(&Position, &CatState, &Personality)>()
-        {
-            // Only walking/running/zoomies cats push glasses
-            if !matches!(
-                state.state,
-                BehaviorState::Walking
-                    | BehaviorState::Running
-                    | BehaviorState::Zoomies
-                    | BehaviorState::ChasingMouse
-            ) {
-                continue;
-            }
-
-            for (i, glass) in glasses.glasses.iter().enumerate() {
-                if glass.shattered {
-                    continue;
-                }
-                let delta = glass.pos - pos.0;
-                let dist_sq = delta.length_squared();
-                if dist_sq < nudge_sq && dist_sq > 1.0 {
-                    let dist = dist_sq.sqrt();
-                    let push_dir = delta / dist;
-                    let strength = GLASS_PUSH_STRENGTH * (0.5 + personality.energy * 0.5);
-                    pushes.push((i, push_dir * strength));
                 }
+                state.timer = 0.5;
             }
         }
 
-        for (idx, impulse) in pushes {
-            glasses.push(idx, impulse);
+        for impulse in bats {
+            wand.bat(impulse);
         }
     }
 }
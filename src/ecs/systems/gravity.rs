@@ -0,0 +1,351 @@
+use glam::Vec2;
+
+use crate::config::GravityConfig;
+use crate::desktop::Desktop;
+use crate::ecs::components::{
+    Airborne, Appearance, BehaviorState, CatState, Position, PrevPosition, SpawnAnimation, Stacked,
+    Velocity,
+};
+use crate::ecs::systems::physics::GRAVITY;
+use crate::ecs::systems::spawn::{self, BounceEvent};
+use crate::ecs::systems::window_aware::DesktopWindow;
+
+/// A full-size cat's feet are this far below its centre.
+const FEET: f32 = 20.0;
+/// A cat stays on a surface within this distance of its feet.
+const GROUND_SNAP: f32 = 6.0;
+/// The floor sits this far above a monitor's bottom edge.
+const FLOOR_INSET: f32 = 4.0;
+/// Cats keep at least this far from the end of a platform they jump to.
+const LEDGE_MARGIN: f32 = 10.0;
+/// Widest gap a cat will jump across.
+const JUMP_REACH: f32 = 250.0;
+/// A jump arcs this much higher than the higher of its two ends.
+const JUMP_CLEARANCE: f32 = 40.0;
+/// Cats trying to move up (or down) at least this fast look for a way there.
+const CLIMB_INTENT: f32 = 80.0;
+/// Chance per tick that a cat trying to get up or down makes its move.
+const CLIMB_CHANCE: f32 = 0.1;
+/// Upward speed of a cat hopping off a ledge.
+const HOP_SPEED: f32 = 120.0;
+
+/// Something to stand on: a window's top edge or a monitor floor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Surface {
+    pub left: f32,
+    pub right: f32,
+    pub y: f32,
+    /// Top of the focused window: cats stay if they're on it, but don't
+    /// jump onto it.
+    pub focused: bool,
+}
+
+impl Surface {
+    fn under(&self, x: f32) -> bool {
+        (self.left..=self.right).contains(&x)
+    }
+}
+
+/// Every surface a cat can stand on: monitor floors (except bottom edges
+/// that open onto another monitor) and the tops of `windows`, leaving out
+/// minimized and tool windows and window tops with no room above them on
+/// screen (maximized windows).
+pub fn surfaces(desktop: &Desktop, windows: &[DesktopWindow], out: &mut Vec<Surface>) {
+    out.clear();
+    for (i, m) in desktop.monitors().iter().enumerate() {
+        if !desktop.open_edges(i).bottom {
+            out.push(Surface {
+                left: m.left,
+                right: m.right,
+                y: m.bottom - FLOOR_INSET,
+                focused: false,
+            });
+        }
+    }
+    for w in windows.iter().filter(|w| w.is_platform()) {
+        let headroom = Vec2::new((w.left + w.right) * 0.5, w.top - FEET * 2.0);
+        if desktop.monitor_at(headroom).is_some() {
+            out.push(Surface {
+                left: w.left,
+                right: w.right,
+                y: w.top,
+                focused: w.state.focused,
+            });
+        }
+    }
+}
+
+/// Launch velocity for a jump from `from` to `to` (positions of the cat's
+/// feet), arcing [`JUMP_CLEARANCE`] over the higher end.
+pub fn jump_velocity(from: Vec2, to: Vec2) -> Vec2 {
+    let rise = (from.y - to.y).max(0.0);
+    let apex = rise + JUMP_CLEARANCE;
+    let up = (2.0 * apex / GRAVITY).sqrt();
+    let down = (2.0 * (apex + to.y - from.y).max(0.0) / GRAVITY).sqrt();
+    Vec2::new((to.x - from.x) / (up + down), -GRAVITY * up)
+}
+
+/// Platformer physics for cats while gravity is on. Grounded cats keep the
+/// sideways half of whatever the other systems made of their movement and
+/// stay on their surface; walking off its end (or having the window under
+/// them close or move) drops them. Cats trying to get up or down, and now and
+/// then a walking cat, jump to another surface in reach. Airborne cats fly a
+/// ballistic arc and bounce to a stop where they land, kicking up dust like
+/// a spawn landing. With gravity off, any cat still in the air just stops
+/// falling.
+#[allow(clippy::too_many_arguments)]
+pub fn update(
+    world: &mut hecs::World,
+    desktop: &Desktop,
+    windows: &[DesktopWindow],
+    rng: &mut fastrand::Rng,
+    dt: f32,
+    config: &GravityConfig,
+    bounces: &mut Vec<BounceEvent>,
+) {
+    if !config.enabled {
+        let grounded: Vec<hecs::Entity> = world
+            .query::<()>()
+            .with::<&Airborne>()
+            .iter()
+            .map(|(e, _)| e)
+            .collect();
+        for entity in grounded {
+            let _ = world.remove_one::<Airborne>(entity);
+        }
+        return;
+    }
+
+    let mut surfaces = Vec::new();
+    self::surfaces(desktop, windows, &mut surfaces);
+    let surfaces = &surfaces[..];
+    let mut took_off: Vec<(hecs::Entity, Airborne)> = Vec::new();
+    let mut landed: Vec<hecs::Entity> = Vec::new();
+
+    for (entity, (pos, prev, vel, state, appearance, airborne)) in world
+        .query_mut::<(
+            &mut Position,
+            &PrevPosition,
+            &mut Velocity,
+            &mut CatState,
+            &Appearance,
+            Option<&mut Airborne>,
+        )>()
+        .without::<&SpawnAnimation>()
+        .without::<&Stacked>()
+    {
+        let feet = FEET * appearance.size;
+
+        if let Some(air) = airborne {
+            air.vel.y += GRAVITY * dt;
+            let from = prev.0;
+            let to = desktop.confine(from, from + air.vel * dt, 8.0);
+            // Only surfaces it comes down onto from above catch it
+            let (from_feet, to_feet) = (from.y + feet, to.y + feet);
+            let floor = surfaces
+                .iter()
+                .filter(|s| s.under(to.x) && from_feet <= s.y + 0.5 && to_feet >= s.y)
+                .map(|s| s.y)
+                .min_by(f32::total_cmp);
+            if (to.x - from.x - air.vel.x * dt).abs() > 0.01 {
+                // Hit a wall
+                air.vel.x = 0.0;
+            }
+            pos.0 = to;
+            if let Some(y) = floor {
+                pos.0.y = y - feet;
+                if spawn::land(&mut air.vel.y, Vec2::new(to.x, y), bounces) {
+                    landed.push(entity);
+                    air.vel = Vec2::ZERO;
+                }
+            }
+            vel.0 = air.vel;
+            continue;
+        }
+
+        let feet_y = prev.0.y + feet;
+        let ground = surfaces
+            .iter()
+            .filter(|s| s.under(pos.0.x) && (s.y - feet_y).abs() <= GROUND_SNAP)
+            .min_by(|a, b| (a.y - feet_y).abs().total_cmp(&(b.y - feet_y).abs()));
+        let Some(ground) = ground else {
+            // Walked off the edge, or the window underneath went away
+            pos.0.y = prev.0.y;
+            took_off.push((
+                entity,
+                Airborne {
+                    vel: Vec2::new(vel.0.x, 0.0),
+                },
+            ));
+            continue;
+        };
+        pos.0.y = ground.y - feet;
+
+        let mobile = matches!(
+            state.state,
+            BehaviorState::Idle
+                | BehaviorState::Walking
+                | BehaviorState::Running
+                | BehaviorState::ChasingMouse
+        );
+        let wants_up = vel.0.y < -CLIMB_INTENT;
+        let wants_down = vel.0.y > CLIMB_INTENT && ground.y < lowest_under(surfaces, ground);
+        let wander = state.state == BehaviorState::Walking && rng.f32() < config.jump_chance;
+        let climb = (wants_up || wants_down) && rng.f32() < CLIMB_CHANCE;
+        vel.0.y = 0.0;
+        if !mobile || !(wander || climb) {
+            continue;
+        }
+
+        let here = Vec2::new(pos.0.x, ground.y);
+        let launch = if wants_down && !wants_up {
+            // Hop off the ledge and let gravity do the rest
+            Some(Vec2::new(vel.0.x, -HOP_SPEED))
+        } else {
+            pick_ledge(surfaces, ground, here, vel.0.x, wants_up, config, rng)
+                .map(|to| jump_velocity(here, to))
+        };
+        if let Some(launch) = launch {
+            state.state = BehaviorState::Running;
+            state.timer = state.timer.max(1.0);
+            took_off.push((entity, Airborne { vel: launch }));
+        }
+    }
+
+    for entity in landed {
+        let _ = world.remove_one::<Airborne>(entity);
+    }
+    for (entity, airborne) in took_off {
+        let _ = world.insert_one(entity, airborne);
+    }
+}
+
+/// Height of the lowest surface overlapping `ground`, for telling whether a
+/// cat on it has anywhere lower to go.
+fn lowest_under(surfaces: &[Surface], ground: &Surface) -> f32 {
+    surfaces
+        .iter()
+        .filter(|s| s.right >= ground.left && s.left <= ground.right)
+        .map(|s| s.y)
+        .fold(ground.y, f32::max)
+}
+
+/// Where on another surface in reach a cat at `here` on `ground` jumps to:
+/// the nearest one above in the direction it's heading when it `wants_up`,
+/// otherwise any in reach at random.
+fn pick_ledge(
+    surfaces: &[Surface],
+    ground: &Surface,
+    here: Vec2,
+    heading: f32,
+    wants_up: bool,
+    config: &GravityConfig,
+    rng: &mut fastrand::Rng,
+) -> Option<Vec2> {
+    let reachable = surfaces.iter().filter(|s| *s != ground).filter_map(|s| {
+        if s.focused || s.right - s.left < LEDGE_MARGIN * 2.0 {
+            return None;
+        }
+        let to = Vec2::new(
+            here.x.clamp(s.left + LEDGE_MARGIN, s.right - LEDGE_MARGIN),
+            s.y,
+        );
+        let rise = here.y - to.y;
+        let reach = (to.x - here.x).abs() <= JUMP_REACH && rise <= config.jump_height;
+        (reach && rise.abs() > GROUND_SNAP).then_some(to)
+    });
+
+    if wants_up {
+        reachable
+            .filter(|to| to.y < here.y && (to.x - here.x) * heading >= 0.0)
+            .min_by(|a, b| {
+                a.distance_squared(here)
+                    .total_cmp(&b.distance_squared(here))
+            })
+    } else {
+        let options: Vec<Vec2> = reachable.collect();
+        (!options.is_empty()).then(|| options[rng.usize(..options.len())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::TestCat;
+
+    fn cat(world: &mut hecs::World, pos: Vec2) -> hecs::Entity {
+        TestCat::at(pos).spawn(world)
+    }
+
+    /// One tick of movement as `systems::tick` would run it, then gravity.
+    fn step(world: &mut hecs::World, desktop: &Desktop, windows: &[DesktopWindow]) {
+        let dt = 1.0 / 60.0;
+        for (_, (pos, prev, vel)) in
+            world.query_mut::<(&mut Position, &mut PrevPosition, &Velocity)>()
+        {
+            prev.0 = pos.0;
+            pos.0 += vel.0 * dt;
+        }
+        let config = GravityConfig {
+            enabled: true,
+            jump_chance: 0.0,
+            ..GravityConfig::default()
+        };
+        let mut rng = fastrand::Rng::with_seed(1);
+        update(
+            world,
+            desktop,
+            windows,
+            &mut rng,
+            dt,
+            &config,
+            &mut Vec::new(),
+        );
+    }
+
+    #[test]
+    fn cats_fall_onto_windows_and_drop_when_they_close() {
+        let desktop = Desktop::single(800, 600);
+        let window = DesktopWindow {
+            id: 1,
+            left: 200.0,
+            top: 300.0,
+            right: 600.0,
+            bottom: 500.0,
+            ..DesktopWindow::default()
+        };
+        let mut world = hecs::World::new();
+        let cat = cat(&mut world, Vec2::new(400.0, 100.0));
+
+        for _ in 0..120 {
+            step(&mut world, &desktop, std::slice::from_ref(&window));
+        }
+        assert!(world.get::<&Airborne>(cat).is_err(), "landed");
+        let pos = world.get::<&Position>(cat).unwrap().0;
+        assert_eq!(pos.y, 300.0 - FEET);
+
+        // The window closes: down to the floor
+        for _ in 0..120 {
+            step(&mut world, &desktop, &[]);
+        }
+        assert!(world.get::<&Airborne>(cat).is_err(), "landed again");
+        let pos = world.get::<&Position>(cat).unwrap().0;
+        assert_eq!(pos.y, 600.0 - FLOOR_INSET - FEET);
+    }
+
+    #[test]
+    fn jumps_land_where_they_aim() {
+        let from = Vec2::new(100.0, 500.0);
+        for to in [Vec2::new(300.0, 250.0), Vec2::new(0.0, 580.0)] {
+            let vel = jump_velocity(from, to);
+            let (mut pos, mut v) = (from, vel);
+            let dt = 1.0 / 600.0;
+            // Fly until coming back down through the target height
+            while !(v.y > 0.0 && pos.y >= to.y) {
+                v.y += GRAVITY * dt;
+                pos += v * dt;
+            }
+            assert!(pos.distance(to) < 5.0, "{pos} vs {to}");
+        }
+    }
+}
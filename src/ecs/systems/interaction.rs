@@ -1,9 +1,13 @@
 use glam::Vec2;
 
+use crate::config::InteractionConfig;
 use crate::ecs::components::{
-    BehaviorState, CatState, GiftCarrier, InteractionTarget, Position, SleepingPile, Velocity,
-    Personality,
+    BehaviorState, CatState, GiftCarrier, Hunting, InteractionTarget, Personality, Position,
+    SleepingPile, Velocity,
 };
+use crate::ecs::systems::trust;
+use crate::journal::{self, JournalEvent};
+use crate::relations::{self, Relationships};
 use crate::spatial::{CatSnapshot, SpatialHash};
 
 // ---------------------------------------------------------------------------
@@ -33,26 +37,11 @@ const MAX_ALIGNMENT: f32 = 12.0;
 /// Social interactions happen within this range.
 const INTERACTION_RADIUS: f32 = 60.0;
 const INTERACTION_RADIUS_SQ: f32 = INTERACTION_RADIUS * INTERACTION_RADIUS;
+// Per-tick interaction chances live in `InteractionConfig`.
 
-/// Per-tick probability two idle cats start playing.
-const PLAY_CHANCE: f32 = 0.008;
-/// Per-tick probability one cat chases another.
-const CHASE_CHANCE: f32 = 0.005;
-/// Per-tick probability a cat pounces on another.
-const POUNCE_CHANCE: f32 = 0.002;
-/// Per-tick probability two nearby cats start fighting.
-const FIGHT_CHANCE: f32 = 0.001;
 /// Pounce leap speed.
 const POUNCE_LEAP_SPEED: f32 = 350.0;
-/// Per-tick probability an idle cat joins a sleeping neighbor.
-const NAP_CLUSTER_CHANCE: f32 = 0.015;
-
-/// Zoomie contagion: chance a nearby idle/walking cat catches zoomies.
-const ZOOMIE_CONTAGION_CHANCE: f32 = 0.05;
-/// Contagious yawn: chance a nearby idle/grooming cat yawns.
-const YAWN_CONTAGION_CHANCE: f32 = 0.30;
-/// Sleeping cat chance per tick to start yawning (seed for cascade).
-const YAWN_SEED_CHANCE: f32 = 0.001;
+
 /// Contagion interaction range.
 const CONTAGION_RADIUS: f32 = 80.0;
 const CONTAGION_RADIUS_SQ: f32 = CONTAGION_RADIUS * CONTAGION_RADIUS;
@@ -151,6 +140,8 @@ pub struct InteractionBuffers {
     parade_follow_pos: Vec<Vec2>,
     parade_follow_dist_sq: Vec<f32>,
     sleeping_neighbor_count: Vec<u32>,
+    /// Snapshot index pairs asleep within pile range of each other.
+    pile_pairs: Vec<(usize, usize)>,
     active: Vec<ActiveInteraction>,
 }
 
@@ -168,6 +159,7 @@ impl InteractionBuffers {
             parade_follow_pos: vec![Vec2::ZERO; capacity],
             parade_follow_dist_sq: vec![f32::MAX; capacity],
             sleeping_neighbor_count: vec![0; capacity],
+            pile_pairs: Vec::with_capacity(64),
             active: Vec::with_capacity(64),
         }
     }
@@ -177,13 +169,12 @@ impl InteractionBuffers {
 // Main entry point
 // ---------------------------------------------------------------------------
 
-/// Gift spawn chance per tick (for idle curious cats).
-const GIFT_SPAWN_CHANCE: f32 = 0.0003;
 /// Gift carry speed.
 const GIFT_CARRY_SPEED: f32 = 55.0;
 /// Gift drop distance to cursor.
 const GIFT_DROP_DIST: f32 = 60.0;
 
+#[allow(clippy::too_many_arguments)]
 pub fn update(
     world: &mut hecs::World,
     snapshots: &[CatSnapshot],
@@ -192,21 +183,23 @@ pub fn update(
     rng: &mut fastrand::Rng,
     dt: f32,
     mouse_pos: Vec2,
+    tuning: &InteractionConfig,
+    relations: &mut Relationships,
 ) {
     // Phase A: Steer cats already in ChasingCat/Playing states
-    steer_active(world, bufs, rng);
+    steer_active(world, bufs, rng, dt, relations);
 
     // Phase B: Pure-data read pass — separation + new interaction decisions
-    phase_read(snapshots, grid, bufs, rng, dt);
+    phase_read(snapshots, grid, bufs, rng, dt, tuning, relations);
 
     // Phase C: Apply results to the ECS world
-    phase_write(world, bufs, snapshots, rng);
+    phase_write(world, bufs, snapshots, rng, relations);
 
     // Phase D: Sleeping pile management
-    phase_sleeping_piles(world, snapshots, bufs, rng);
+    phase_sleeping_piles(world, snapshots, bufs, rng, dt, relations);
 
     // Phase E: Gift giving — cats carry gifts to cursor
-    phase_gifts(world, rng, dt, mouse_pos);
+    phase_gifts(world, rng, dt, mouse_pos, tuning, relations);
 }
 
 fn phase_gifts(
@@ -214,6 +207,8 @@ fn phase_gifts(
     rng: &mut fastrand::Rng,
     dt: f32,
     mouse_pos: Vec2,
+    tuning: &InteractionConfig,
+    relations: &mut Relationships,
 ) {
     // Steer existing gift carriers toward cursor
     let mut delivered: Vec<(hecs::Entity, Vec2)> = Vec::new();
@@ -242,8 +237,23 @@ fn phase_gifts(
     }
 
     // Remove gift component from delivered/expired cats
-    for (entity, _pos) in &delivered {
+    for (entity, pos) in &delivered {
         let _ = world.remove_one::<GiftCarrier>(*entity);
+        journal::record(world, *entity, JournalEvent::DeliveredGift);
+        trust::adjust(world, *entity, trust::GIFT_TRUST);
+        // Cats around to see the gift warm to the giver.
+        let witnesses: Vec<hecs::Entity> = world
+            .query::<&Position>()
+            .with::<&CatState>()
+            .iter()
+            .filter(|(e, p)| {
+                *e != *entity && (p.0 - *pos).length_squared() < INTERACTION_RADIUS_SQ
+            })
+            .map(|(e, _)| e)
+            .collect();
+        for witness in witnesses {
+            relations.adjust_cats(world, *entity, witness, relations::GIFT_BOND);
+        }
         if let Ok(mut state) = world.get::<&mut CatState>(*entity) {
             state.state = BehaviorState::Idle;
             state.timer = 1.0 + rng.f32() * 2.0;
@@ -276,7 +286,7 @@ fn phase_gifts(
         if personality.curiosity < 0.6 {
             continue;
         }
-        if rng.f32() < GIFT_SPAWN_CHANCE {
+        if rng.f32() < tuning.gift_spawn_chance {
             new_carrier = Some(entity);
             break;
         }
@@ -299,6 +309,8 @@ fn steer_active(
     world: &mut hecs::World,
     bufs: &mut InteractionBuffers,
     rng: &mut fastrand::Rng,
+    dt: f32,
+    relations: &mut Relationships,
 ) {
     bufs.active.clear();
 
@@ -353,10 +365,10 @@ fn steer_active(
                         state.timer = 0.3;
                     }
                     // Startle the target
-                    if let Ok((mut target_state, mut target_vel)) =
+                    if let Ok((target_state, target_vel)) =
                         world.query_one_mut::<(&mut CatState, &mut Velocity)>(ai.target)
                     {
-                        crate::ecs::systems::behavior::trigger_startle(&mut target_state, &mut target_vel, rng);
+                        crate::ecs::systems::behavior::trigger_startle(target_state, target_vel, rng);
                     }
                 }
             }
@@ -391,6 +403,9 @@ fn steer_active(
                 if dist_sq > PLAY_GIVE_UP_DIST_SQ {
                     give_up(world, ai.entity, rng);
                 } else {
+                    // Each partner adds half, so a pair bonds at PLAY_BOND.
+                    let bond = relations::PLAY_BOND * 0.5 * dt;
+                    relations.adjust_cats(world, ai.entity, ai.target, bond);
                     // Jitter around partner
                     let jitter = Vec2::new(
                         rng.f32() * 2.0 - 1.0,
@@ -432,8 +447,11 @@ fn phase_read(
     bufs: &mut InteractionBuffers,
     rng: &mut fastrand::Rng,
     _dt: f32,
+    tuning: &InteractionConfig,
+    relations: &Relationships,
 ) {
     bufs.commands.clear();
+    bufs.pile_pairs.clear();
 
     // Ensure buffers are big enough and zeroed
     let len = snapshots.len();
@@ -521,15 +539,19 @@ fn phase_read(
             {
                 let my_dir = me.vel.normalize();
                 let dot = my_dir.dot(them.vel.normalize());
-                if dot > 0.7 {
+                // Rivals won't march together.
+                let affinity = relations.between(me.id, them.id);
+                if dot > 0.7 && affinity > relations::RIVAL {
                     bufs.parade_dir_sum[my_idx] += them.vel.normalize();
                     bufs.parade_count[my_idx] += 1;
 
-                    // Follow-the-leader: track nearest aligned cat ahead of me
+                    // Follow-the-leader: track nearest aligned cat ahead of me,
+                    // friends counting as closer than they are
                     let rel_pos = them.pos - me.pos;
                     let ahead = rel_pos.dot(my_dir);
-                    if ahead > 5.0 && dist_sq < bufs.parade_follow_dist_sq[my_idx] {
-                        bufs.parade_follow_dist_sq[my_idx] = dist_sq;
+                    let follow_dist_sq = dist_sq * (1.0 - affinity * 0.5);
+                    if ahead > 5.0 && follow_dist_sq < bufs.parade_follow_dist_sq[my_idx] {
+                        bufs.parade_follow_dist_sq[my_idx] = follow_dist_sq;
                         bufs.parade_follow_pos[my_idx] = them.pos;
                     }
                 }
@@ -541,6 +563,9 @@ fn phase_read(
                 && them.state == BehaviorState::Sleeping
             {
                 bufs.sleeping_neighbor_count[my_idx] += 1;
+                if my_idx < ni {
+                    bufs.pile_pairs.push((my_idx, ni));
+                }
             }
 
             // --- Social interactions (only process each pair once) ---
@@ -561,14 +586,18 @@ fn phase_read(
                     | BehaviorState::Grooming
                     | BehaviorState::Sleeping
             );
+            // Friends (up to 1.0) play and nap together more; rivals
+            // (down to -1.0) chase and fight more.
+            let affinity = relations.between(me.id, them.id);
 
             // Play: both idle/walking
             if matches!(me.state, BehaviorState::Idle | BehaviorState::Walking)
                 && matches!(them.state, BehaviorState::Idle | BehaviorState::Walking)
             {
-                let chance = PLAY_CHANCE
+                let chance = tuning.play_chance
                     * (1.0 - me.personality.skittishness)
-                    * (1.0 - them.personality.skittishness);
+                    * (1.0 - them.personality.skittishness)
+                    * (1.0 + affinity);
                 if rng.f32() < chance {
                     bufs.commands.push(InteractionCmd::StartPlay {
                         entity_a: me.entity,
@@ -582,7 +611,10 @@ fn phase_read(
             if matches!(me.state, BehaviorState::Idle | BehaviorState::Walking)
                 && matches!(them.state, BehaviorState::Walking | BehaviorState::Running)
             {
-                let chance = CHASE_CHANCE * me.personality.curiosity * me.personality.energy;
+                let chance = tuning.chase_chance
+                    * me.personality.curiosity
+                    * me.personality.energy
+                    * (1.0 - affinity * 0.5);
                 if rng.f32() < chance {
                     bufs.commands.push(InteractionCmd::StartChase {
                         chaser: me.entity,
@@ -604,7 +636,10 @@ fn phase_read(
                 && matches!(me.state, BehaviorState::Walking | BehaviorState::Running)
                 && their_interactable
             {
-                let chance = CHASE_CHANCE * them.personality.curiosity * them.personality.energy;
+                let chance = tuning.chase_chance
+                    * them.personality.curiosity
+                    * them.personality.energy
+                    * (1.0 - affinity * 0.5);
                 if rng.f32() < chance {
                     bufs.commands.push(InteractionCmd::StartChase {
                         chaser: them.entity,
@@ -626,7 +661,7 @@ fn phase_read(
                 && me.personality.energy > 0.5
                 && me.personality.curiosity > 0.3
             {
-                let chance = POUNCE_CHANCE * me.personality.energy;
+                let chance = tuning.pounce_chance * me.personality.energy;
                 if rng.f32() < chance {
                     bufs.commands.push(InteractionCmd::StartPounce {
                         pouncer: me.entity,
@@ -644,7 +679,10 @@ fn phase_read(
                 && me.personality.skittishness < 0.4
                 && them.personality.skittishness < 0.4
             {
-                let chance = FIGHT_CHANCE * me.personality.energy * them.personality.energy;
+                let chance = tuning.fight_chance
+                    * me.personality.energy
+                    * them.personality.energy
+                    * (1.0 - affinity);
                 if rng.f32() < chance {
                     bufs.commands.push(InteractionCmd::StartFight {
                         cat_a: me.entity,
@@ -658,7 +696,8 @@ fn phase_read(
             if me.state == BehaviorState::Sleeping
                 && matches!(them.state, BehaviorState::Idle | BehaviorState::Grooming)
             {
-                let chance = NAP_CLUSTER_CHANCE * them.personality.laziness;
+                let chance =
+                    tuning.nap_cluster_chance * them.personality.laziness * (1.0 + affinity);
                 if rng.f32() < chance {
                     bufs.commands.push(InteractionCmd::JoinNap {
                         entity: them.entity,
@@ -669,7 +708,8 @@ fn phase_read(
             if them.state == BehaviorState::Sleeping
                 && matches!(me.state, BehaviorState::Idle | BehaviorState::Grooming)
             {
-                let chance = NAP_CLUSTER_CHANCE * me.personality.laziness;
+                let chance =
+                    tuning.nap_cluster_chance * me.personality.laziness * (1.0 + affinity);
                 if rng.f32() < chance {
                     bufs.commands.push(InteractionCmd::JoinNap {
                         entity: me.entity,
@@ -686,46 +726,42 @@ fn phase_read(
             // Zoomie contagion: zooming cat near idle/walking cat
             if me.state == BehaviorState::Zoomies
                 && matches!(them.state, BehaviorState::Idle | BehaviorState::Walking)
+                && rng.f32() < tuning.zoomie_contagion_chance
             {
-                if rng.f32() < ZOOMIE_CONTAGION_CHANCE {
-                    bufs.commands.push(InteractionCmd::CatchZoomies {
-                        entity: them.entity,
-                    });
-                }
+                bufs.commands.push(InteractionCmd::CatchZoomies {
+                    entity: them.entity,
+                });
             }
             if them.state == BehaviorState::Zoomies
                 && matches!(me.state, BehaviorState::Idle | BehaviorState::Walking)
+                && rng.f32() < tuning.zoomie_contagion_chance
             {
-                if rng.f32() < ZOOMIE_CONTAGION_CHANCE {
-                    bufs.commands.push(InteractionCmd::CatchZoomies {
-                        entity: me.entity,
-                    });
-                }
+                bufs.commands.push(InteractionCmd::CatchZoomies {
+                    entity: me.entity,
+                });
             }
 
             // Contagious yawn: yawning cat near idle/grooming cat
             if me.state == BehaviorState::Yawning
                 && matches!(them.state, BehaviorState::Idle | BehaviorState::Grooming)
+                && rng.f32() < tuning.yawn_contagion_chance
             {
-                if rng.f32() < YAWN_CONTAGION_CHANCE {
-                    bufs.commands.push(InteractionCmd::ContagiousYawn {
-                        entity: them.entity,
-                    });
-                }
+                bufs.commands.push(InteractionCmd::ContagiousYawn {
+                    entity: them.entity,
+                });
             }
             if them.state == BehaviorState::Yawning
                 && matches!(me.state, BehaviorState::Idle | BehaviorState::Grooming)
+                && rng.f32() < tuning.yawn_contagion_chance
             {
-                if rng.f32() < YAWN_CONTAGION_CHANCE {
-                    bufs.commands.push(InteractionCmd::ContagiousYawn {
-                        entity: me.entity,
-                    });
-                }
+                bufs.commands.push(InteractionCmd::ContagiousYawn {
+                    entity: me.entity,
+                });
             }
         });
 
         // Seed yawns: sleeping cats occasionally start yawning
-        if me.state == BehaviorState::Sleeping && rng.f32() < YAWN_SEED_CHANCE {
+        if me.state == BehaviorState::Sleeping && rng.f32() < tuning.yawn_seed_chance {
             bufs.commands.push(InteractionCmd::SeedYawn {
                 entity: me.entity,
             });
@@ -742,6 +778,7 @@ fn phase_write(
     bufs: &mut InteractionBuffers,
     snapshots: &[CatSnapshot],
     rng: &mut fastrand::Rng,
+    relations: &mut Relationships,
 ) {
     // Apply separation + cohesion + alignment velocities
     for (idx, snap) in snapshots.iter().enumerate() {
@@ -790,6 +827,10 @@ fn phase_write(
             continue;
         }
         let parade_dir = avg_dir.normalize();
+        // Stalkers keep their mind on the prey.
+        if world.get::<&Hunting>(snap.entity).is_ok() {
+            continue;
+        }
 
         if bufs.parade_follow_dist_sq[idx] < f32::MAX {
             // Follower: steer toward a point PARADE_FOLLOW_DIST behind the leader
@@ -842,6 +883,7 @@ fn phase_write(
                 }
                 let timer = 2.0 + rng.f32() * 4.0;
                 set_interaction_state(world, chaser, BehaviorState::ChasingCat, timer, target);
+                relations.adjust_cats(world, chaser, target, -relations::CHASE_GRUDGE);
                 // Steer toward target immediately
                 if let (Ok(chaser_pos), Ok(target_pos)) = (
                     world.get::<&Position>(chaser),
@@ -895,6 +937,7 @@ fn phase_write(
                     let angle = rng.f32() * std::f32::consts::TAU;
                     vel.0 = Vec2::new(angle.cos(), angle.sin()) * 300.0;
                 }
+                journal::record(world, entity, JournalEvent::Zoomies);
             }
             InteractionCmd::ContagiousYawn { entity } => {
                 if !can_start_interaction(world, entity) {
@@ -924,27 +967,7 @@ fn phase_write(
                 let _ = world.insert_one(pouncer, InteractionTarget(target));
             }
             InteractionCmd::StartFight { cat_a, cat_b } => {
-                if !can_start_interaction(world, cat_a)
-                    || !can_start_interaction(world, cat_b)
-                {
-                    continue;
-                }
-                // Both cats enter Fighting state, facing each other
-                let fight_duration = 1.0 + rng.f32() * 1.5;
-                // Cat A fights slightly longer (winner)
-                if let Ok(mut state) = world.get::<&mut CatState>(cat_a) {
-                    state.state = BehaviorState::Fighting;
-                    state.timer = fight_duration;
-                }
-                // Cat B is the loser — shorter timer, gets startled when done
-                let loser_duration = fight_duration * 0.6;
-                if let Ok(mut state) = world.get::<&mut CatState>(cat_b) {
-                    state.state = BehaviorState::Fighting;
-                    state.timer = loser_duration;
-                }
-                // Store targets so they face each other
-                let _ = world.insert_one(cat_a, InteractionTarget(cat_b));
-                let _ = world.insert_one(cat_b, InteractionTarget(cat_a));
+                start_fight(world, cat_a, cat_b, rng, relations);
             }
             InteractionCmd::SeedYawn { entity } => {
                 // Sleeping cat starts yawning (seed for cascade)
@@ -962,8 +985,45 @@ fn phase_write(
     }
 }
 
+/// Start a scrap between two cats free to fight; `cat_a` holds on longer
+/// and counts as the winner. Returns whether the fight started.
+pub fn start_fight(
+    world: &mut hecs::World,
+    cat_a: hecs::Entity,
+    cat_b: hecs::Entity,
+    rng: &mut fastrand::Rng,
+    relations: &mut Relationships,
+) -> bool {
+    if !can_start_interaction(world, cat_a) || !can_start_interaction(world, cat_b) {
+        return false;
+    }
+    // Both cats enter Fighting state, facing each other
+    let fight_duration = 1.0 + rng.f32() * 1.5;
+    // Cat A fights slightly longer (winner)
+    if let Ok(mut state) = world.get::<&mut CatState>(cat_a) {
+        state.state = BehaviorState::Fighting;
+        state.timer = fight_duration;
+    }
+    // Cat B is the loser — shorter timer, gets startled when done
+    let loser_duration = fight_duration * 0.6;
+    if let Ok(mut state) = world.get::<&mut CatState>(cat_b) {
+        state.state = BehaviorState::Fighting;
+        state.timer = loser_duration;
+    }
+    // Store targets so they face each other
+    let _ = world.insert_one(cat_a, InteractionTarget(cat_b));
+    let _ = world.insert_one(cat_b, InteractionTarget(cat_a));
+    relations.adjust_cats(world, cat_a, cat_b, -relations::FIGHT_GRUDGE);
+    journal::record(world, cat_a, JournalEvent::WonFight);
+    journal::record(world, cat_b, JournalEvent::LostFight);
+    true
+}
+
 /// Check if a cat is in a state that allows starting a new interaction.
 fn can_start_interaction(world: &hecs::World, entity: hecs::Entity) -> bool {
+    if world.get::<&Hunting>(entity).is_ok() {
+        return false;
+    }
     if let Ok(state) = world.get::<&CatState>(entity) {
         matches!(
             state.state,
@@ -1009,6 +1069,8 @@ fn phase_sleeping_piles(
     snapshots: &[CatSnapshot],
     bufs: &InteractionBuffers,
     rng: &mut fastrand::Rng,
+    dt: f32,
+    relations: &mut Relationships,
 ) {
     // Step 1: Wake cascade — find pile members that are no longer sleeping
     // (woken by behavior transitions, clicks, mouse interactions, etc.)
@@ -1068,10 +1130,24 @@ fn phase_sleeping_piles(
                         breathing_offset: rng.f32() * std::f32::consts::TAU,
                     },
                 );
+                journal::record(world, snap.entity, JournalEvent::JoinedPile);
             }
         } else {
             // Not in a pile — remove component if present
             let _ = world.remove_one::<SleepingPile>(snap.entity);
         }
     }
+
+    // Step 3: Pile-mates asleep side by side grow closer
+    for &(a, b) in &bufs.pile_pairs {
+        let (a, b) = (&snapshots[a], &snapshots[b]);
+        let (Some(id_a), Some(id_b)) = (a.id, b.id) else {
+            continue;
+        };
+        if world.get::<&SleepingPile>(a.entity).is_ok()
+            && world.get::<&SleepingPile>(b.entity).is_ok()
+        {
+            relations.adjust(id_a, id_b, relations::PILE_BOND * dt);
+        }
+    }
 }
@@ -0,0 +1,282 @@
+use glam::Vec2;
+
+use crate::cat::{self, CatIds, KITTEN_START_SCALE};
+use crate::config::LifecycleConfig;
+use crate::ecs::components::{
+    Age, Appearance, BehaviorState, Bond, CatState, Clan, InteractionTarget, Kitten, Personality,
+    Position,
+};
+
+/// Kittens appear within this distance of their parents' midpoint.
+const LITTER_SPREAD: f32 = 20.0;
+
+/// Age every cat, grow kittens, and let bonded adults at play have litters.
+/// At most `room` kittens are born, so the population cap holds. Appends
+/// each newborn's position to `births`.
+pub fn update(
+    world: &mut hecs::World,
+    dt: f32,
+    rng: &mut fastrand::Rng,
+    config: &LifecycleConfig,
+    ids: &mut CatIds,
+    mut room: usize,
+    births: &mut Vec<Vec2>,
+) {
+    // Aging + kitten growth
+    let mut grown = Vec::new();
+    for (entity, (age, kitten, look)) in
+        world.query_mut::<(&mut Age, Option<&Kitten>, &mut Appearance)>()
+    {
+        age.0 += dt;
+        if let Some(kitten) = kitten {
+            let t = (age.0 / config.grow_time).min(1.0);
+            look.size = kitten.adult_size * (KITTEN_START_SCALE + (1.0 - KITTEN_START_SCALE) * t);
+            if t >= 1.0 {
+                grown.push(entity);
+            }
+        }
+    }
+    for entity in grown {
+        let _ = world.remove_one::<Kitten>(entity);
+    }
+
+    // Play partners: each mutually playing pair once, lower entity first.
+    let pairs: Vec<(hecs::Entity, hecs::Entity)> = world
+        .query::<(&CatState, &InteractionTarget)>()
+        .iter()
+        .filter(|(e, (state, target))| {
+            state.state == BehaviorState::Playing
+                && *e < target.0
+                && world
+                    .get::<&CatState>(target.0)
+                    .is_ok_and(|s| s.state == BehaviorState::Playing)
+                && world
+                    .get::<&InteractionTarget>(target.0)
+                    .is_ok_and(|t| t.0 == *e)
+        })
+        .map(|(e, (_, target))| (e, target.0))
+        .collect();
+
+    for (a, b) in pairs {
+        let play_a = strengthen(world, a, b, dt);
+        let play_b = strengthen(world, b, a, dt);
+
+        let bonded = play_a >= config.bond_time && play_b >= config.bond_time;
+        let adults = world.get::<&Kitten>(a).is_err() && world.get::<&Kitten>(b).is_err();
+        if !(config.breeding && bonded && adults && room > 0) || rng.f32() >= config.breed_chance {
+            continue;
+        }
+
+        let (Some(parent_a), Some(parent_b)) = (parent(world, a), parent(world, b)) else {
+            continue;
+        };
+        let (Ok(pos_a), Ok(pos_b)) = (world.get::<&Position>(a), world.get::<&Position>(b)) else {
+            continue;
+        };
+        let nest = (pos_a.0 + pos_b.0) * 0.5;
+        drop((pos_a, pos_b));
+        // The litter joins one parent's clan.
+        let clan = world.get::<&Clan>(if rng.bool() { a } else { b }).map(|c| *c);
+
+        let litter = (1 + rng.usize(0..config.litter_max)).min(room);
+        for _ in 0..litter {
+            let offset = Vec2::new(rng.f32() * 2.0 - 1.0, rng.f32() * 2.0 - 1.0) * LITTER_SPREAD;
+            let kitten = cat::spawn_kitten(
+                world,
+                nest + offset,
+                [parent_a, parent_b],
+                config.mutation,
+                ids,
+                rng,
+            );
+            if let Ok(clan) = clan {
+                let _ = world.insert_one(kitten, clan);
+            }
+            births.push(nest + offset);
+        }
+        room -= litter;
+
+        // Parents need another full bond before the next litter.
+        for parent in [a, b] {
+            if let Ok(mut bond) = world.get::<&mut Bond>(parent) {
+                bond.play_time = 0.0;
+            }
+        }
+    }
+}
+
+/// Add a tick of play with `partner` to `cat`'s bond, starting a new bond
+/// if it was playing with someone else. Returns the bond's play time.
+fn strengthen(world: &mut hecs::World, cat: hecs::Entity, partner: hecs::Entity, dt: f32) -> f32 {
+    if let Ok(mut bond) = world.get::<&mut Bond>(cat) {
+        if bond.partner == partner {
+            bond.play_time += dt;
+        } else {
+            *bond = Bond {
+                partner,
+                play_time: dt,
+            };
+        }
+        return bond.play_time;
+    }
+    let _ = world.insert_one(
+        cat,
+        Bond {
+            partner,
+            play_time: dt,
+        },
+    );
+    dt
+}
+
+/// The traits a parent passes on.
+fn parent(world: &hecs::World, cat: hecs::Entity) -> Option<(Personality, Appearance)> {
+    let personality = *world.get::<&Personality>(cat).ok()?;
+    let appearance = *world.get::<&Appearance>(cat).ok()?;
+    Some((personality, appearance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::Velocity;
+
+    fn adult(world: &mut hecs::World, x: f32, laziness: f32, color: u32) -> hecs::Entity {
+        world.spawn((
+            Position(Vec2::new(x, 100.0)),
+            Velocity(Vec2::ZERO),
+            CatState {
+                state: BehaviorState::Playing,
+                timer: 5.0,
+            },
+            Personality {
+                laziness,
+                energy: 0.5,
+                curiosity: 0.5,
+                skittishness: 0.5,
+            },
+            Appearance {
+                color,
+                pattern: 1,
+                size: 1.0,
+            },
+            Age(0.0),
+        ))
+    }
+
+    fn playing_pair(world: &mut hecs::World) -> (hecs::Entity, hecs::Entity) {
+        let a = adult(world, 100.0, 0.0, 0x000000FF);
+        let b = adult(world, 130.0, 1.0, 0xFFFFFFFF);
+        world.insert_one(a, InteractionTarget(b)).unwrap();
+        world.insert_one(b, InteractionTarget(a)).unwrap();
+        (a, b)
+    }
+
+    fn eager() -> LifecycleConfig {
+        LifecycleConfig {
+            bond_time: 1.0,
+            breed_chance: 1.0,
+            litter_max: 2,
+            mutation: 0.0,
+            ..LifecycleConfig::default()
+        }
+    }
+
+    #[test]
+    fn bonded_players_have_blended_kittens() {
+        let mut world = hecs::World::new();
+        let (a, _) = playing_pair(&mut world);
+        let mut rng = fastrand::Rng::with_seed(7);
+        let mut births = Vec::new();
+        let mut ids = CatIds::default();
+        let config = eager();
+
+        // Not bonded yet: a second of play is needed.
+        for _ in 0..50 {
+            update(&mut world, 1.0 / 60.0, &mut rng, &config, &mut ids, 100, &mut births);
+        }
+        assert!(births.is_empty());
+        assert!(world.get::<&Bond>(a).unwrap().play_time > 0.8);
+
+        for _ in 0..20 {
+            update(&mut world, 1.0 / 60.0, &mut rng, &config, &mut ids, 100, &mut births);
+            if !births.is_empty() {
+                break;
+            }
+        }
+        assert!((1..=2).contains(&births.len()));
+        assert_eq!(world.get::<&Bond>(a).unwrap().play_time, 0.0);
+
+        for (_, (p, look, kitten)) in world.query::<(&Personality, &Appearance, &Kitten)>().iter() {
+            assert!((0.0..=1.0).contains(&p.laziness));
+            assert_eq!(look.pattern, 1);
+            assert_eq!(kitten.adult_size, 1.0);
+            assert!((look.size - KITTEN_START_SCALE).abs() < 0.01);
+            // Black x white gives a gray.
+            let r = look.color >> 24;
+            assert_eq!(look.color >> 16 & 0xFF, r);
+        }
+    }
+
+    #[test]
+    fn litters_respect_the_population_cap() {
+        let mut world = hecs::World::new();
+        playing_pair(&mut world);
+        let mut rng = fastrand::Rng::with_seed(1);
+        let mut births = Vec::new();
+        let mut ids = CatIds::default();
+        let config = LifecycleConfig {
+            litter_max: 3,
+            ..eager()
+        };
+
+        for _ in 0..600 {
+            update(&mut world, 1.0 / 60.0, &mut rng, &config, &mut ids, 0, &mut births);
+        }
+        assert!(births.is_empty());
+
+        // A bonded pair with room for one has a litter of one.
+        update(&mut world, 1.0 / 60.0, &mut rng, &config, &mut ids, 1, &mut births);
+        assert_eq!(births.len(), 1);
+    }
+
+    #[test]
+    fn kittens_grow_up_and_do_not_breed() {
+        let mut world = hecs::World::new();
+        let mut rng = fastrand::Rng::with_seed(3);
+        let mut ids = CatIds::default();
+        let parents = [(
+            Personality {
+                laziness: 0.5,
+                energy: 0.5,
+                curiosity: 0.5,
+                skittishness: 0.5,
+            },
+            Appearance {
+                color: 0x808080FF,
+                pattern: 0,
+                size: 1.2,
+            },
+        ); 2];
+        let a = cat::spawn_kitten(&mut world, Vec2::ZERO, parents, 0.0, &mut ids, &mut rng);
+        let b = cat::spawn_kitten(&mut world, Vec2::X, parents, 0.0, &mut ids, &mut rng);
+        for (cat, partner) in [(a, b), (b, a)] {
+            world.get::<&mut CatState>(cat).unwrap().state = BehaviorState::Playing;
+            world.insert_one(cat, InteractionTarget(partner)).unwrap();
+        }
+
+        let config = LifecycleConfig {
+            grow_time: 10.0,
+            ..eager()
+        };
+        let mut births = Vec::new();
+        update(&mut world, 5.0, &mut rng, &config, &mut ids, 100, &mut births);
+        let half = world.get::<&Appearance>(a).unwrap().size;
+        assert!(half > 1.2 * KITTEN_START_SCALE && half < 1.2);
+        assert!(births.is_empty(), "kittens can't breed");
+
+        update(&mut world, 5.0, &mut rng, &config, &mut ids, 100, &mut births);
+        assert_eq!(world.get::<&Appearance>(a).unwrap().size, 1.2);
+        assert!(world.get::<&Kitten>(a).is_err());
+    }
+}
@@ -1,24 +1,40 @@
 pub mod behavior;
 pub mod click;
+pub mod gravity;
 pub mod interaction;
+pub mod lifecycle;
 pub mod mouse;
 pub mod movement;
+pub mod needs;
+pub mod physics;
+pub mod prey;
 pub mod spatial;
+pub mod spawn;
+pub mod territory;
+pub mod toys;
 pub mod towers;
+pub mod trust;
 pub mod window_aware;
 
-use crate::debug::timer::{SystemPhase, SystemTimers};
+use crate::config::Config;
+use crate::desktop::Desktop;
 use crate::heatmap::Heatmap;
+use crate::nav::Navigator;
+use crate::relations::Relationships;
+use crate::scent::ScentField;
 use crate::spatial::{CatSnapshot, SpatialHash};
+use crate::timer::{SystemPhase, SystemTimers};
 use interaction::InteractionBuffers;
 use mouse::CursorState;
+use prey::Wildlife;
+use spawn::BounceEvent;
 
 /// Run all simulation systems for one fixed tick.
+#[allow(clippy::too_many_arguments)]
 pub fn tick(
     world: &mut hecs::World,
     dt: f32,
-    screen_w: f32,
-    screen_h: f32,
+    desktop: &Desktop,
     mouse_x: f32,
     mouse_y: f32,
     cursor: &mut CursorState,
@@ -31,23 +47,42 @@ pub fn tick(
     edge_affinity: f32,
     platforms: &[window_aware::DesktopWindow],
     energy_scale: f32,
+    config: &Config,
+    relations: &mut Relationships,
+    scent: &mut ScentField,
+    nav: &mut Navigator,
+    wildlife: &mut Wildlife,
+    bounces: &mut Vec<BounceEvent>,
 ) {
     // 0. Update cursor tracking
     cursor.update(mouse_x, mouse_y, dt);
 
     // 1. Mouse tracking + chase/flee behavior
     timers.begin();
-    mouse::update_mouse_pos(world, mouse_x, mouse_y, cursor, rng);
+    mouse::update_mouse_pos(world, mouse_x, mouse_y, cursor, rng, &config.mouse, nav);
+    let mouse_pos = glam::Vec2::new(mouse_x, mouse_y);
+    trust::update(world, mouse_pos, cursor, dt, &config.mouse);
     timers.end(SystemPhase::Mouse);
 
-    // 2. Behavior state machine transitions
+    // 2. Needs drift, then behavior state machine transitions
     timers.begin();
-    behavior::update(world, dt, rng, energy_scale);
+    needs::update(world, dt);
+    behavior::update(world, dt, rng, energy_scale, &config.behavior);
     timers.end(SystemPhase::Behavior);
 
     // 3. Movement integration (apply velocity, friction, bounds, heatmap avoidance, edge affinity)
     timers.begin();
-    movement::integrate(world, dt, screen_w, screen_h, heatmap, edge_affinity);
+    movement::integrate(world, dt, desktop, heatmap, edge_affinity);
+    // Gravity mode: keep cats on their platforms, or flying between them
+    gravity::update(
+        world,
+        desktop,
+        platforms,
+        rng,
+        dt,
+        &config.gravity,
+        bounces,
+    );
     timers.end(SystemPhase::Movement);
 
     // 4. Rebuild spatial hash + snapshot cache
@@ -57,8 +92,22 @@ pub fn tick(
 
     // 5. Cat-to-cat interactions
     timers.begin();
-    let mouse_pos = glam::Vec2::new(mouse_x, mouse_y);
-    interaction::update(world, snapshots, grid, interaction_bufs, rng, dt, mouse_pos);
+    interaction::update(
+        world,
+        snapshots,
+        grid,
+        interaction_bufs,
+        rng,
+        dt,
+        mouse_pos,
+        &config.interaction,
+        relations,
+    );
+    // Scent marking and turf disputes between clans
+    territory::update(world, snapshots, grid, scent, rng, dt, relations);
+    // Prey wander and flee; cats stalk, pounce on and catch them
+    prey::update(world, wildlife, snapshots, grid, desktop, rng, dt, &config.prey);
+    prey::hunt(world, wildlife, snapshots, rng, &config.prey);
     timers.end(SystemPhase::Interaction);
 
     // 6. Circle collision resolution (push overlapping cats apart)
@@ -67,6 +116,9 @@ pub fn tick(
     // 7. Cat tower management (stacking, collapse, new climbers)
     towers::update(world, snapshots, grid, rng);
 
-    // 8. Window awareness (cats perch on titlebars)
-    window_aware::update(world, platforms, rng);
+    // 8. Window awareness (cats perch on titlebars; gravity mode stands
+    // them there already)
+    if !config.gravity.enabled {
+        window_aware::update(world, platforms, rng);
+    }
 }
@@ -1,28 +1,15 @@
 use glam::Vec2;
 
-use crate::ecs::components::{BehaviorState, CatState, Personality, Position, Velocity};
-
-/// Distance within which cats notice the mouse.
-const MOUSE_NOTICE_RADIUS: f32 = 200.0;
-/// Speed at which cats chase the mouse.
-const CHASE_SPEED: f32 = 100.0;
-/// Chance per tick that a curious cat starts chasing (if in idle-ish state).
-const CHASE_CHANCE_PER_TICK: f32 = 0.02;
-
-/// Moses Effect: radius of cursor repulsion when cursor moves fast.
-const MOSES_RADIUS: f32 = 400.0;
-/// Cursor speed threshold (px/s) to trigger Moses Effect.
-const MOSES_SPEED_THRESHOLD: f32 = 100.0;
-/// Moses repulsion strength multiplier.
-const MOSES_STRENGTH: f32 = 3.5;
-/// Max Moses repulsion velocity applied per tick.
-const MOSES_MAX_PUSH: f32 = 300.0;
+use crate::config::MouseConfig;
+use crate::ecs::components::{BehaviorState, CatState, Personality, Position, Trust, Velocity};
+use crate::ecs::systems::trust;
+use crate::nav::Navigator;
+
+// Notice/chase/Moses/flee-radius tuning lives in `MouseConfig`.
 
 /// Flee speed range for skittish cats.
 const FLEE_SPEED_MIN: f32 = 80.0;
 const FLEE_SPEED_MAX: f32 = 140.0;
-/// Flee trigger radius.
-const FLEE_RADIUS: f32 = 200.0;
 
 /// Cursor speed threshold below which it's considered "still".
 const CURSOR_STILL_THRESHOLD: f32 = 5.0;
@@ -32,18 +19,33 @@ const CURSOR_STILL_CREEP_TIME: f32 = 5.0;
 const CAUTIOUS_FLEE_SPEED: f32 = 100.0;
 /// Cautious cat: creep speed toward still cursor.
 const CREEP_SPEED: f32 = 25.0;
+/// Trusting cats that reach the cursor weave around it inside this radius.
+const RUB_RADIUS: f32 = 24.0;
+/// Speed of a trusting cat rubbing against the cursor.
+const RUB_SPEED: f32 = 30.0;
+/// Speed at which wary cats edge away from the cursor.
+const KEEP_AWAY_SPEED: f32 = 50.0;
 
 /// Persistent state for cursor tracking between frames.
 pub struct CursorState {
     pub prev_pos: Vec2,
+    /// Cursor velocity over the last tick, in pixels/second.
+    pub velocity: Vec2,
     pub speed: f32,
     pub still_timer: f32,
 }
 
+impl Default for CursorState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CursorState {
     pub fn new() -> Self {
         Self {
             prev_pos: Vec2::ZERO,
+            velocity: Vec2::ZERO,
             speed: 0.0,
             still_timer: 0.0,
         }
@@ -52,8 +54,8 @@ impl CursorState {
     /// Update cursor speed and still timer. Call once per tick.
     pub fn update(&mut self, mouse_x: f32, mouse_y: f32, dt: f32) {
         let current = Vec2::new(mouse_x, mouse_y);
-        let delta = current - self.prev_pos;
-        self.speed = delta.length() / dt.max(0.001);
+        self.velocity = (current - self.prev_pos) / dt.max(0.001);
+        self.speed = self.velocity.length();
         self.prev_pos = current;
 
         if self.speed < CURSOR_STILL_THRESHOLD {
@@ -64,31 +66,42 @@ impl CursorState {
     }
 }
 
-/// Track global mouse position and update chase/flee targets.
+/// Track global mouse position and update chase/flee targets. Chasers route
+/// to the cursor around obstacles via `nav`. Each cat's [`Trust`] tempers
+/// its skittishness: trusting cats come over, follow and rub against the
+/// cursor, wary ones keep their distance.
 pub fn update_mouse_pos(
     world: &mut hecs::World,
     mouse_x: f32,
     mouse_y: f32,
     cursor: &CursorState,
     rng: &mut fastrand::Rng,
+    tuning: &MouseConfig,
+    nav: &mut Navigator,
 ) {
     let mouse = Vec2::new(mouse_x, mouse_y);
     let cursor_speed = cursor.speed;
     let cursor_still = cursor.still_timer >= CURSOR_STILL_CREEP_TIME;
-    let moses_active = cursor_speed > MOSES_SPEED_THRESHOLD;
-
-    for (_, (pos, vel, state, personality)) in world
-        .query_mut::<(&Position, &mut Velocity, &mut CatState, &Personality)>()
-    {
+    let moses_active = cursor_speed > tuning.moses_speed_threshold;
+
+    for (_, (pos, vel, state, personality, cat_trust)) in world.query_mut::<(
+        &Position,
+        &mut Velocity,
+        &mut CatState,
+        &Personality,
+        Option<&Trust>,
+    )>() {
         let to_mouse = mouse - pos.0;
         let dist = to_mouse.length();
+        let trust = cat_trust.map_or(Trust::NEUTRAL, |t| t.0);
+        let skittishness = trust::wariness(personality.skittishness, trust);
 
         // --- Moses Effect: fast cursor scatters everyone nearby ---
-        if moses_active && dist < MOSES_RADIUS && dist > 1.0 {
+        if moses_active && dist < tuning.moses_radius && dist > 1.0 {
             let away = -to_mouse / dist;
-            let falloff = 1.0 - (dist / MOSES_RADIUS);
-            let strength = (cursor_speed / MOSES_SPEED_THRESHOLD) * MOSES_STRENGTH * falloff;
-            let push = away * strength.min(MOSES_MAX_PUSH);
+            let falloff = 1.0 - (dist / tuning.moses_radius);
+            let strength = (cursor_speed / tuning.moses_speed_threshold) * tuning.moses_strength * falloff;
+            let push = away * strength.min(tuning.moses_max_push);
             vel.0 += push;
 
             // Force non-stationary state if pushed hard
@@ -106,9 +119,16 @@ pub fn update_mouse_pos(
 
         // --- Handle cats already in cursor-related states ---
         if state.state == BehaviorState::ChasingMouse {
-            if dist > 10.0 {
-                let dir = to_mouse / dist;
-                let speed = CHASE_SPEED * (0.7 + personality.curiosity * 0.6);
+            if trust >= trust::TRUSTING && dist <= RUB_RADIUS {
+                // Rub against the cursor, circling it until the chase times out
+                if dist > 1.0 {
+                    let around = Vec2::new(-to_mouse.y, to_mouse.x) / dist;
+                    let hold = to_mouse / dist * (dist - RUB_RADIUS * 0.5);
+                    vel.0 = around * RUB_SPEED + hold;
+                }
+            } else if dist > 10.0 {
+                let dir = nav.steer(pos.0, mouse);
+                let speed = tuning.chase_speed * (0.7 + personality.curiosity * 0.6);
                 vel.0 = dir * speed;
             } else {
                 state.state = BehaviorState::Idle;
@@ -119,10 +139,9 @@ pub fn update_mouse_pos(
 
         if state.state == BehaviorState::FleeingCursor {
             // Keep fleeing away from cursor
-            if dist < FLEE_RADIUS * 1.5 && dist > 1.0 {
+            if dist < tuning.flee_radius * 1.5 && dist > 1.0 {
                 let away = -to_mouse / dist;
-                let speed = FLEE_SPEED_MIN
-                    + (FLEE_SPEED_MAX - FLEE_SPEED_MIN) * personality.skittishness;
+                let speed = FLEE_SPEED_MIN + (FLEE_SPEED_MAX - FLEE_SPEED_MIN) * skittishness;
                 vel.0 = away * speed;
             }
             // Timer handles transition back (in behavior system)
@@ -137,6 +156,25 @@ pub fn update_mouse_pos(
             continue;
         }
 
+        // --- Trust: wary cats edge away, trusting ones come over ---
+        if trust < trust::WARY && dist < tuning.flee_radius {
+            if dist > 1.0 {
+                state.state = BehaviorState::Walking;
+                state.timer = 0.5;
+                vel.0 = -to_mouse / dist * KEEP_AWAY_SPEED;
+            }
+            continue;
+        }
+        if trust >= trust::TRUSTING && dist > RUB_RADIUS && dist < tuning.notice_radius * 1.5 {
+            if rng.f32() < tuning.chase_chance * trust {
+                state.state = BehaviorState::ChasingMouse;
+                state.timer = 4.0 + rng.f32() * 4.0;
+                let speed = tuning.chase_speed * (0.7 + personality.curiosity * 0.6);
+                vel.0 = nav.steer(pos.0, mouse) * speed;
+            }
+            continue;
+        }
+
         // --- Personality-driven reactions ---
 
         // Lazy cats ignore cursor entirely
@@ -145,15 +183,14 @@ pub fn update_mouse_pos(
         }
 
         // Skittish cats flee when cursor is nearby
-        if personality.skittishness > 0.6 && dist < FLEE_RADIUS {
-            let flee_chance = 0.05 * personality.skittishness;
+        if skittishness > 0.6 && dist < tuning.flee_radius {
+            let flee_chance = 0.05 * skittishness;
             if rng.f32() < flee_chance {
                 state.state = BehaviorState::FleeingCursor;
                 state.timer = 1.0 + rng.f32() * 1.5;
                 if dist > 1.0 {
                     let away = -to_mouse / dist;
-                    let speed = FLEE_SPEED_MIN
-                        + (FLEE_SPEED_MAX - FLEE_SPEED_MIN) * personality.skittishness;
+                    let speed = FLEE_SPEED_MIN + (FLEE_SPEED_MAX - FLEE_SPEED_MIN) * skittishness;
                     vel.0 = away * speed;
                 }
                 continue;
@@ -161,14 +198,14 @@ pub fn update_mouse_pos(
         }
 
         // Curious, non-skittish cats chase the cursor
-        if personality.curiosity > 0.5 && personality.skittishness < 0.4 {
-            if dist < MOUSE_NOTICE_RADIUS {
-                let chance = CHASE_CHANCE_PER_TICK * (0.5 + personality.curiosity);
+        if personality.curiosity > 0.5 && skittishness < 0.4 {
+            if dist < tuning.notice_radius {
+                let chance = tuning.chase_chance * (0.5 + personality.curiosity);
                 if rng.f32() < chance {
                     state.state = BehaviorState::ChasingMouse;
                     state.timer = 2.0 + rng.f32() * 3.0;
-                    let dir = to_mouse / dist.max(1.0);
-                    let speed = CHASE_SPEED * (0.7 + personality.curiosity * 0.6);
+                    let dir = nav.steer(pos.0, mouse);
+                    let speed = tuning.chase_speed * (0.7 + personality.curiosity * 0.6);
                     vel.0 = dir * speed;
                 }
             }
@@ -177,7 +214,7 @@ pub fn update_mouse_pos(
 
         // Everyone else: Cautious behavior
         // Flee when cursor is fast and close
-        if cursor_speed > CAUTIOUS_FLEE_SPEED && dist < FLEE_RADIUS * 0.8 {
+        if cursor_speed > CAUTIOUS_FLEE_SPEED && dist < tuning.flee_radius * 0.8 {
             let flee_chance = 0.03;
             if rng.f32() < flee_chance {
                 state.state = BehaviorState::FleeingCursor;
@@ -191,14 +228,13 @@ pub fn update_mouse_pos(
         }
 
         // Creep toward still cursor (brave cats only)
-        if cursor_still && personality.curiosity > 0.4 && dist < MOUSE_NOTICE_RADIUS * 1.5 {
+        if cursor_still && personality.curiosity > 0.4 && dist < tuning.notice_radius * 1.5 {
             let creep_chance = 0.005 * personality.curiosity;
             if rng.f32() < creep_chance {
                 state.state = BehaviorState::ChasingMouse;
                 state.timer = 3.0 + rng.f32() * 3.0;
                 if dist > 1.0 {
-                    let dir = to_mouse / dist;
-                    vel.0 = dir * CREEP_SPEED;
+                    vel.0 = nav.steer(pos.0, mouse) * CREEP_SPEED;
                 }
             }
         }
@@ -1,5 +1,6 @@
 use glam::Vec2;
 
+use crate::desktop::Desktop;
 use crate::ecs::components::{BehaviorState, CatState, Position, PrevPosition, Velocity};
 use crate::heatmap::Heatmap;
 use crate::spatial::{CatSnapshot, SpatialHash};
@@ -17,20 +18,19 @@ const HEAT_AVOIDANCE: f32 = 40.0;
 const HEAT_THRESHOLD: f32 = 0.3;
 /// Edge affinity pull strength.
 const EDGE_PULL: f32 = 12.0;
-/// Edge repulsion zone — cats within this distance of screen edge get pushed inward.
+/// Edge repulsion zone — cats within this distance of a monitor wall get pushed inward.
 const EDGE_REPULSION_ZONE: f32 = 80.0;
 /// Edge repulsion strength (scales linearly as cat approaches edge).
 const EDGE_REPULSION_STRENGTH: f32 = 60.0;
 
 /// Integrate velocity into position. Apply friction/damping.
-/// Screen bounds clamping keeps cats on-screen.
+/// Desktop confinement keeps cats on a monitor (crossing open edges freely).
 /// Heatmap avoidance biases mobile cats away from hot zones.
-/// Edge affinity pulls walking cats toward screen edges (Work mode).
+/// Edge affinity pulls walking cats toward their monitor's edges (Work mode).
 pub fn integrate(
     world: &mut hecs::World,
     dt: f32,
-    screen_w: f32,
-    screen_h: f32,
+    desktop: &Desktop,
     heatmap: &Heatmap,
     edge_affinity: f32,
 ) {
@@ -40,6 +40,10 @@ pub fn integrate(
         // Store previous position for render interpolation
         prev_pos.0 = pos.0;
 
+        let home = desktop.home(pos.0);
+        let screen = desktop.monitors()[home];
+        let open = desktop.open_edges(home);
+
         let mobile = matches!(
             cat_state.state,
             BehaviorState::Idle
@@ -65,41 +69,43 @@ pub fn integrate(
 
         // Edge affinity: pull walking cats toward screen edges (Work mode)
         if edge_affinity > 0.01 && cat_state.state == BehaviorState::Walking {
-            let center = Vec2::new(screen_w * 0.5, screen_h * 0.5);
+            let center = screen.center();
             let to_edge = pos.0 - center;
             if to_edge.length_squared() > 1.0 {
                 vel.0 += to_edge.normalize() * edge_affinity * EDGE_PULL;
             }
         }
 
-        // Soft edge repulsion: push cats away from screen edges to prevent bunching.
+        // Soft edge repulsion: push cats away from monitor walls to prevent bunching.
         // Strength ramps linearly from 0 at EDGE_REPULSION_ZONE to full at margin.
+        // Edges shared with a neighbouring monitor don't repel.
         {
             let margin = 8.0;
             let zone = EDGE_REPULSION_ZONE;
             // Left edge
-            if pos.0.x < margin + zone {
-                let t = 1.0 - ((pos.0.x - margin) / zone).clamp(0.0, 1.0);
+            if !open.left && pos.0.x < screen.left + margin + zone {
+                let t = 1.0 - ((pos.0.x - screen.left - margin) / zone).clamp(0.0, 1.0);
                 vel.0.x += t * EDGE_REPULSION_STRENGTH;
             }
             // Right edge
-            if pos.0.x > screen_w - margin - zone {
-                let t = 1.0 - ((screen_w - margin - pos.0.x) / zone).clamp(0.0, 1.0);
+            if !open.right && pos.0.x > screen.right - margin - zone {
+                let t = 1.0 - ((screen.right - margin - pos.0.x) / zone).clamp(0.0, 1.0);
                 vel.0.x -= t * EDGE_REPULSION_STRENGTH;
             }
             // Top edge
-            if pos.0.y < margin + zone {
-                let t = 1.0 - ((pos.0.y - margin) / zone).clamp(0.0, 1.0);
+            if !open.top && pos.0.y < screen.top + margin + zone {
+                let t = 1.0 - ((pos.0.y - screen.top - margin) / zone).clamp(0.0, 1.0);
                 vel.0.y += t * EDGE_REPULSION_STRENGTH;
             }
             // Bottom edge
-            if pos.0.y > screen_h - margin - zone {
-                let t = 1.0 - ((screen_h - margin - pos.0.y) / zone).clamp(0.0, 1.0);
+            if !open.bottom && pos.0.y > screen.bottom - margin - zone {
+                let t = 1.0 - ((screen.bottom - margin - pos.0.y) / zone).clamp(0.0, 1.0);
                 vel.0.y -= t * EDGE_REPULSION_STRENGTH;
             }
         }
 
         // Integrate velocity
+        let from = pos.0;
         pos.0 += vel.0 * dt;
 
         // Apply friction
@@ -110,10 +116,9 @@ pub fn integrate(
             vel.0 = Vec2::ZERO;
         }
 
-        // Hard clamp to screen bounds (safety net, should rarely trigger now)
-        let margin = 8.0;
-        pos.0.x = pos.0.x.clamp(margin, screen_w - margin);
-        pos.0.y = pos.0.y.clamp(margin, screen_h - margin);
+        // Keep on a monitor: walls clamp, open edges and small gaps can be
+        // crossed (safety net for walls, should rarely trigger now)
+        pos.0 = desktop.confine(from, pos.0, 8.0);
     }
 }
 
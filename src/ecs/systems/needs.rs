@@ -0,0 +1,238 @@
+use crate::ecs::components::{BehaviorState, CatState, Need, Needs, Personality, SleepingPile};
+
+/// Hunger drain per second — about ten minutes from full to starving.
+const HUNGER_DECAY: f32 = 1.0 / 600.0;
+/// Social drain per second when alone.
+const SOCIAL_DECAY: f32 = 1.0 / 240.0;
+/// Play drain per second when bored.
+const PLAY_DECAY: f32 = 1.0 / 180.0;
+/// Comfort drain per second.
+const COMFORT_DECAY: f32 = 1.0 / 300.0;
+
+/// Energy regained per second asleep.
+const SLEEP_RESTORE: f32 = 0.08;
+/// Energy regained per second sitting, grooming or yawning.
+const REST_RESTORE: f32 = 0.01;
+/// Energy spent per second walking.
+const WALK_DRAIN: f32 = 0.004;
+/// Energy spent per second running, chasing, fighting or zooming.
+const RUN_DRAIN: f32 = 0.02;
+
+/// Play satisfied per second of chasing, pouncing or zoomies.
+const PLAY_RESTORE: f32 = 0.1;
+/// Social satisfied per second with other cats.
+const SOCIAL_RESTORE: f32 = 0.05;
+/// Comfort regained per second of grooming.
+const GROOM_RESTORE: f32 = 0.06;
+/// Comfort regained per second asleep in a pile.
+const PILE_RESTORE: f32 = 0.08;
+/// Comfort lost per second while scared or fighting.
+const FRIGHT_DRAIN: f32 = 0.3;
+
+/// Hunger filled by one treat.
+pub const TREAT_FILL: f32 = 0.4;
+
+/// Random spread added to every score so equal needs don't always pick
+/// the same activity.
+const SCORE_NOISE: f32 = 0.2;
+
+/// Let each cat's needs wear off or recover with what it's doing now.
+pub fn update(world: &mut hecs::World, dt: f32) {
+    for (_, (needs, state, pile)) in
+        world.query_mut::<(&mut Needs, &CatState, Option<&SleepingPile>)>()
+    {
+        needs.hunger -= HUNGER_DECAY * dt;
+        needs.social -= SOCIAL_DECAY * dt;
+        needs.play -= PLAY_DECAY * dt;
+        needs.comfort -= COMFORT_DECAY * dt;
+
+        needs.energy += dt
+            * match state.state {
+                BehaviorState::Sleeping => SLEEP_RESTORE,
+                BehaviorState::Idle | BehaviorState::Grooming | BehaviorState::Yawning => {
+                    REST_RESTORE
+                }
+                BehaviorState::Walking | BehaviorState::Parading => -WALK_DRAIN,
+                _ => -RUN_DRAIN,
+            };
+
+        match state.state {
+            BehaviorState::Playing | BehaviorState::ChasingCat | BehaviorState::Pouncing => {
+                needs.play += PLAY_RESTORE * dt;
+                needs.social += SOCIAL_RESTORE * dt;
+            }
+            BehaviorState::ChasingMouse | BehaviorState::Zoomies => {
+                needs.play += PLAY_RESTORE * dt;
+            }
+            BehaviorState::Parading => needs.social += SOCIAL_RESTORE * dt,
+            BehaviorState::Grooming => needs.comfort += GROOM_RESTORE * dt,
+            BehaviorState::Startled | BehaviorState::FleeingCursor | BehaviorState::Fighting => {
+                needs.comfort -= FRIGHT_DRAIN * dt;
+            }
+            _ => {}
+        }
+
+        if pile.is_some() {
+            needs.comfort += PILE_RESTORE * dt;
+            needs.social += SOCIAL_RESTORE * dt;
+        }
+
+        for need in [
+            &mut needs.energy,
+            &mut needs.hunger,
+            &mut needs.social,
+            &mut needs.play,
+            &mut needs.comfort,
+        ] {
+            *need = need.clamp(0.0, 1.0);
+        }
+    }
+}
+
+/// Utility scorer: rate each self-directed activity by how well it serves
+/// the cat's needs, shaded by personality, size and `energy_scale` (mode
+/// preset × day/night). Returns the winner and the need behind it, if any.
+pub fn choose(
+    needs: &Needs,
+    personality: &Personality,
+    size: f32,
+    energy_scale: f32,
+    rng: &mut fastrand::Rng,
+) -> (BehaviorState, Option<Need>) {
+    // Bigger cats are lazier, small cats more energetic; <1 energy_scale = sleepier.
+    let size_lazy = ((size - 1.0) * 0.5).clamp(-0.15, 0.15);
+    let laziness = (personality.laziness + size_lazy + (1.0 - energy_scale) * 0.2).clamp(0.0, 1.0);
+    let drive = ((personality.energy - size_lazy) * energy_scale).clamp(0.0, 1.0);
+    // Tired cats don't feel like doing much of anything.
+    let stamina = 0.3 + needs.energy * 0.7;
+
+    let tired = needs.urgency(Need::Energy);
+    let (roam_need, roam) = [Need::Hunger, Need::Social]
+        .map(|n| (n, needs.urgency(n)))
+        .into_iter()
+        .fold(
+            (Need::Hunger, 0.0),
+            |best, cur| if cur.1 > best.1 { cur } else { best },
+        );
+
+    let options = [
+        (BehaviorState::Idle, None, 0.2 + laziness * 0.2),
+        (
+            BehaviorState::Sleeping,
+            Some(Need::Energy),
+            tired * (0.6 + laziness * 0.8) + needs.urgency(Need::Comfort) * 0.2,
+        ),
+        (
+            BehaviorState::Grooming,
+            Some(Need::Comfort),
+            needs.urgency(Need::Comfort) * 0.9,
+        ),
+        (
+            BehaviorState::Walking,
+            Some(roam_need),
+            (roam * 0.6 + drive * 0.2) * stamina,
+        ),
+        (
+            BehaviorState::Running,
+            Some(Need::Play),
+            (needs.urgency(Need::Play) * 0.8 + drive * 0.1) * drive * stamina,
+        ),
+    ];
+
+    let mut best = (BehaviorState::Idle, None, f32::MIN);
+    for (state, motive, score) in options {
+        let score = score + rng.f32() * SCORE_NOISE;
+        if score > best.2 {
+            best = (state, motive, score);
+        }
+    }
+    (best.0, best.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cat(world: &mut hecs::World, state: BehaviorState, needs: Needs) -> hecs::Entity {
+        world.spawn((CatState { state, timer: 5.0 }, needs))
+    }
+
+    fn average() -> Personality {
+        Personality {
+            laziness: 0.5,
+            energy: 0.5,
+            curiosity: 0.5,
+            skittishness: 0.5,
+        }
+    }
+
+    #[test]
+    fn activities_feed_their_needs() {
+        let low = Needs {
+            energy: 0.2,
+            play: 0.2,
+            comfort: 0.2,
+            ..Needs::default()
+        };
+        let mut world = hecs::World::new();
+        let sleeper = cat(&mut world, BehaviorState::Sleeping, low);
+        let player = cat(&mut world, BehaviorState::Playing, low);
+        let piled = cat(&mut world, BehaviorState::Sleeping, low);
+        world
+            .insert_one(
+                piled,
+                SleepingPile {
+                    breathing_offset: 0.0,
+                },
+            )
+            .unwrap();
+
+        for _ in 0..60 {
+            update(&mut world, 1.0 / 6.0);
+        }
+        let needs = |e| *world.get::<&Needs>(e).unwrap();
+        assert!(needs(sleeper).energy > 0.9);
+        assert!(needs(player).play > 0.9);
+        assert!(needs(player).energy < 0.2);
+        assert!(needs(piled).comfort > needs(sleeper).comfort + 0.5);
+        assert!(needs(sleeper).hunger < low.hunger);
+    }
+
+    #[test]
+    fn urgent_needs_win() {
+        let mut rng = fastrand::Rng::with_seed(11);
+        let exhausted = Needs {
+            energy: 0.0,
+            ..Needs::default()
+        };
+        let bored = Needs {
+            play: 0.0,
+            ..Needs::default()
+        };
+        let lively = Personality {
+            energy: 1.0,
+            ..average()
+        };
+
+        let mut sleeps = 0;
+        let mut runs = 0;
+        for _ in 0..100 {
+            if choose(&exhausted, &average(), 1.0, 1.0, &mut rng).0 == BehaviorState::Sleeping {
+                sleeps += 1;
+            }
+            if choose(&bored, &lively, 1.0, 1.0, &mut rng)
+                == (BehaviorState::Running, Some(Need::Play))
+            {
+                runs += 1;
+            }
+        }
+        assert!(sleeps > 90, "exhausted cats sleep ({sleeps}/100)");
+        assert!(runs > 70, "bored lively cats run ({runs}/100)");
+
+        // At night the same bored cat would rather rest.
+        let night_runs = (0..100)
+            .filter(|_| choose(&bored, &lively, 1.0, 0.3, &mut rng).0 == BehaviorState::Running)
+            .count();
+        assert!(night_runs < runs / 2);
+    }
+}
@@ -0,0 +1,341 @@
+use glam::Vec2;
+
+use crate::desktop::Desktop;
+use crate::ecs::components::{Collider, Position, RigidBody, Shape, Velocity};
+use crate::ecs::systems::window_aware::DesktopWindow;
+use crate::spatial::{CatSnapshot, SpatialHash};
+
+/// Downward pull on anything falling (pixels/second²).
+pub const GRAVITY: f32 = 1500.0;
+/// Bodies slower than this come to rest.
+const REST_SPEED: f32 = 5.0;
+/// Collision radius of a full-size cat, for bumping into toys.
+const CAT_RADIUS: f32 = 16.0;
+/// Body spatial hash cell size: wider than any two colliders side by side.
+const BODY_CELL_SIZE: f32 = 128.0;
+/// Body spatial hash table size; there are a few dozen at most.
+const BODY_TABLE_SIZE: usize = 256;
+
+/// Where and how deep two shapes overlap. `normal` points from the first
+/// shape towards the second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    pub normal: Vec2,
+    pub depth: f32,
+}
+
+/// Overlap between shape `a` at `pa` and shape `b` at `pb`, if any.
+pub fn contact(pa: Vec2, a: Shape, pb: Vec2, b: Shape) -> Option<Contact> {
+    match (a, b) {
+        (Shape::Circle { radius: ra }, Shape::Circle { radius: rb }) => {
+            let delta = pb - pa;
+            let dist = delta.length();
+            let depth = ra + rb - dist;
+            (depth > 0.0).then(|| Contact {
+                normal: if dist > 1e-3 { delta / dist } else { Vec2::X },
+                depth,
+            })
+        }
+        (Shape::Rect { half }, Shape::Circle { radius }) => {
+            circle_rect(pb, radius, pa, half).map(|c| Contact {
+                normal: -c.normal,
+                ..c
+            })
+        }
+        (Shape::Circle { radius }, Shape::Rect { half }) => circle_rect(pa, radius, pb, half),
+        (Shape::Rect { half: ha }, Shape::Rect { half: hb }) => {
+            let delta = pb - pa;
+            let overlap = ha + hb - delta.abs();
+            if overlap.x <= 0.0 || overlap.y <= 0.0 {
+                None
+            } else if overlap.x < overlap.y {
+                Some(Contact {
+                    normal: Vec2::new(delta.x.signum(), 0.0),
+                    depth: overlap.x,
+                })
+            } else {
+                Some(Contact {
+                    normal: Vec2::new(0.0, delta.y.signum()),
+                    depth: overlap.y,
+                })
+            }
+        }
+    }
+}
+
+/// Contact from a circle to a box, normal pointing towards the box.
+fn circle_rect(center: Vec2, radius: f32, rect: Vec2, half: Vec2) -> Option<Contact> {
+    let local = center - rect;
+    let closest = local.clamp(-half, half);
+    if closest == local {
+        // Centre inside the box: push out through the nearest side.
+        let gap = half - local.abs();
+        return Some(if gap.x < gap.y {
+            Contact {
+                normal: Vec2::new(-local.x.signum(), 0.0),
+                depth: gap.x + radius,
+            }
+        } else {
+            Contact {
+                normal: Vec2::new(0.0, -local.y.signum()),
+                depth: gap.y + radius,
+            }
+        });
+    }
+    let delta = closest - local;
+    let dist = delta.length();
+    (dist < radius).then(|| Contact {
+        normal: delta / dist,
+        depth: radius - dist,
+    })
+}
+
+/// Velocity change for body `a` and body `b` that stops them closing along
+/// `normal` (pointing from `a` to `b`), with `restitution` of the closing
+/// speed bounced back. Split by inverse mass; zero if they're separating.
+pub fn impulse(va: Vec2, inv_a: f32, vb: Vec2, inv_b: f32, normal: Vec2, restitution: f32) -> f32 {
+    let closing = (vb - va).dot(normal);
+    if closing >= 0.0 || inv_a + inv_b <= 0.0 {
+        return 0.0;
+    }
+    -(1.0 + restitution) * closing / (inv_a + inv_b)
+}
+
+/// Velocity after `dt` seconds of `damping` (see [`RigidBody::damping`]).
+pub fn damp(vel: Vec2, damping: f32, dt: f32) -> Vec2 {
+    vel * (-damping * dt).exp()
+}
+
+/// One physics body during a step.
+struct Body {
+    entity: hecs::Entity,
+    pos: Vec2,
+    vel: Vec2,
+    body: RigidBody,
+    collider: Collider,
+}
+
+/// Reusable per-step buffers.
+pub struct PhysicsWorld {
+    grid: SpatialHash,
+    bodies: Vec<Body>,
+    /// Indices of bodies close enough to touch, lower index first.
+    pairs: Vec<(usize, usize)>,
+}
+
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhysicsWorld {
+    pub fn new() -> Self {
+        Self {
+            grid: SpatialHash::new(BODY_CELL_SIZE, BODY_TABLE_SIZE),
+            bodies: Vec::new(),
+            pairs: Vec::new(),
+        }
+    }
+}
+
+/// Advance every rigid body by `dt`: damp and move it, bounce it off monitor
+/// walls and the tops of `windows`, then separate it from the bodies and
+/// cats it overlaps. Cats are looked up through the colony's `grid` and
+/// `snapshots`, and are never moved themselves — a cat walking into a toy
+/// knocks it along at the cat's speed.
+#[allow(clippy::too_many_arguments)]
+pub fn step(
+    world: &mut hecs::World,
+    physics: &mut PhysicsWorld,
+    desktop: &Desktop,
+    windows: &[DesktopWindow],
+    snapshots: &[CatSnapshot],
+    grid: &SpatialHash,
+    dt: f32,
+) {
+    let bodies = &mut physics.bodies;
+    bodies.clear();
+    for (entity, (pos, vel, body, collider)) in world
+        .query::<(&Position, &Velocity, &RigidBody, &Collider)>()
+        .iter()
+    {
+        bodies.push(Body {
+            entity,
+            pos: pos.0,
+            vel: vel.0,
+            body: *body,
+            collider: *collider,
+        });
+    }
+    if bodies.is_empty() {
+        return;
+    }
+
+    // Integrate and bounce off the walls and window tops
+    for b in bodies.iter_mut().filter(|b| b.body.inv_mass > 0.0) {
+        b.vel = damp(b.vel, b.body.damping, dt);
+        let from = b.pos;
+        b.pos += b.vel * dt;
+        let restitution = b.collider.restitution;
+
+        if b.body.walls {
+            let confined = desktop.confine(from, b.pos, b.collider.shape.bound());
+            if confined.x != b.pos.x {
+                b.vel.x = (confined.x - b.pos.x).signum() * b.vel.x.abs() * restitution;
+            }
+            if confined.y != b.pos.y {
+                b.vel.y = (confined.y - b.pos.y).signum() * b.vel.y.abs() * restitution;
+            }
+            b.pos = confined;
+        }
+
+        // Window tops are one-way: only what comes down onto them lands
+        let below = b.collider.shape.half_height();
+        for w in windows.iter().filter(|w| w.is_platform()) {
+            let top = w.top - below;
+            if from.y <= top && b.pos.y > top && b.pos.x >= w.left && b.pos.x <= w.right {
+                b.pos.y = top;
+                b.vel.y = -b.vel.y.abs() * restitution;
+            }
+        }
+    }
+
+    // Bodies against each other
+    physics.grid.clear();
+    for (i, b) in bodies.iter().enumerate() {
+        physics.grid.insert(b.pos, i as u32);
+    }
+    let pairs = &mut physics.pairs;
+    pairs.clear();
+    for (i, b) in bodies.iter().enumerate() {
+        physics.grid.query_neighbors(b.pos, |j| {
+            if j as usize > i {
+                pairs.push((i, j as usize));
+            }
+        });
+    }
+    for &(i, j) in pairs.iter() {
+        let (left, right) = bodies.split_at_mut(j);
+        let (a, b) = (&mut left[i], &mut right[0]);
+        let (inv_a, inv_b) = (a.body.inv_mass, b.body.inv_mass);
+        if inv_a + inv_b <= 0.0 {
+            continue;
+        }
+        let Some(c) = contact(a.pos, a.collider.shape, b.pos, b.collider.shape) else {
+            continue;
+        };
+        let push = c.normal * c.depth / (inv_a + inv_b);
+        a.pos -= push * inv_a;
+        b.pos += push * inv_b;
+        let restitution = a.collider.restitution.max(b.collider.restitution);
+        let j = impulse(a.vel, inv_a, b.vel, inv_b, c.normal, restitution);
+        a.vel -= c.normal * j * inv_a;
+        b.vel += c.normal * j * inv_b;
+    }
+
+    // Cats bumping into bodies
+    for b in bodies
+        .iter_mut()
+        .filter(|b| b.body.inv_mass > 0.0 && b.collider.hit_by_cats)
+    {
+        grid.query_neighbors(b.pos, |i| {
+            let Some(cat) = snapshots.get(i as usize) else {
+                return;
+            };
+            let shape = Shape::Circle {
+                radius: CAT_RADIUS * cat.size,
+            };
+            let Some(c) = contact(cat.pos, shape, b.pos, b.collider.shape) else {
+                return;
+            };
+            b.pos += c.normal * c.depth;
+            let restitution = b.collider.restitution;
+            b.vel += c.normal
+                * impulse(cat.vel, 0.0, b.vel, b.body.inv_mass, c.normal, restitution)
+                * b.body.inv_mass;
+        });
+    }
+
+    for b in bodies.iter_mut() {
+        if b.vel.length_squared() < REST_SPEED * REST_SPEED {
+            b.vel = Vec2::ZERO;
+        }
+        if let Ok((pos, vel)) = world.query_one_mut::<(&mut Position, &mut Velocity)>(b.entity) {
+            pos.0 = b.pos;
+            vel.0 = b.vel;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_touch_where_they_overlap() {
+        let circle = Shape::Circle { radius: 10.0 };
+        let rect = Shape::Rect {
+            half: Vec2::new(20.0, 10.0),
+        };
+        let c = contact(Vec2::ZERO, circle, Vec2::new(15.0, 0.0), circle).unwrap();
+        assert_eq!(c.normal, Vec2::X);
+        assert!((c.depth - 5.0).abs() < 1e-4);
+
+        // A circle resting on top of a box is pushed straight back up.
+        let c = contact(Vec2::new(0.0, -18.0), circle, Vec2::ZERO, rect).unwrap();
+        assert_eq!(c.normal, Vec2::Y);
+        assert!((c.depth - 2.0).abs() < 1e-4);
+        let c = contact(Vec2::ZERO, rect, Vec2::new(0.0, -18.0), circle).unwrap();
+        assert_eq!(c.normal, -Vec2::Y);
+
+        assert!(contact(Vec2::ZERO, rect, Vec2::new(41.0, 0.0), rect).is_none());
+        assert!(contact(Vec2::ZERO, circle, Vec2::new(0.0, 25.0), rect).is_none());
+    }
+
+    #[test]
+    fn yarn_bounces_off_a_box_and_a_running_cat_knocks_a_glass() {
+        use crate::toy::{self, ToyKind};
+
+        let desktop = Desktop::single(800, 600);
+        let mut world = hecs::World::new();
+        let mut physics = PhysicsWorld::new();
+        let grid = SpatialHash::new(128.0, 64);
+        let dt = 1.0 / 60.0;
+
+        toy::spawn(&mut world, ToyKind::Box, Vec2::new(400.0, 300.0));
+        let yarn = toy::spawn(&mut world, ToyKind::Yarn, Vec2::new(250.0, 300.0));
+        world.get::<&mut Velocity>(yarn).unwrap().0 = Vec2::new(300.0, 0.0);
+        for _ in 0..60 {
+            step(&mut world, &mut physics, &desktop, &[], &[], &grid, dt);
+        }
+        let pos = world.get::<&Position>(yarn).unwrap().0;
+        let vel = world.get::<&Velocity>(yarn).unwrap().0;
+        assert!(
+            pos.x < 400.0 - 34.0 - 22.0 + 1.0 && vel.x < 0.0,
+            "{pos} {vel}"
+        );
+
+        // A cat running into a glass sends it flying faster than it ran.
+        let glass = toy::spawn(&mut world, ToyKind::Glass, Vec2::new(600.0, 100.0));
+        let mut grid = SpatialHash::new(128.0, 64);
+        grid.insert(Vec2::new(575.0, 100.0), 0);
+        let cat = CatSnapshot {
+            entity: hecs::Entity::DANGLING,
+            id: None,
+            pos: Vec2::new(575.0, 100.0),
+            vel: Vec2::new(200.0, 0.0),
+            state: crate::ecs::components::BehaviorState::Running,
+            personality: crate::ecs::components::Personality {
+                laziness: 0.0,
+                energy: 1.0,
+                curiosity: 1.0,
+                skittishness: 0.0,
+            },
+            size: 1.0,
+            is_stacked: false,
+        };
+        step(&mut world, &mut physics, &desktop, &[], &[cat], &grid, dt);
+        assert!(world.get::<&Velocity>(glass).unwrap().0.x > 200.0);
+    }
+}
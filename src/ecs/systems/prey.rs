@@ -0,0 +1,506 @@
+use glam::Vec2;
+
+use crate::config::PreyConfig;
+use crate::desktop::Desktop;
+use crate::ecs::components::{
+    BehaviorState, CatState, GiftCarrier, Hunting, InteractionTarget, Personality, Position,
+    PrevPosition, Prey, PreyKind, Velocity,
+};
+use crate::journal::{self, JournalEvent};
+use crate::spatial::{CatSnapshot, SpatialHash};
+
+/// Seconds a critter sticks around before wandering off.
+const PREY_LIFETIME: f32 = 90.0;
+/// Prey keep this far inside monitor walls.
+const EDGE_MARGIN: f32 = 10.0;
+/// Prey spatial hash table size; there are only ever a handful.
+const PREY_TABLE_SIZE: usize = 64;
+
+/// Cats notice prey within this range, scaled by curiosity (0.5x-1.5x).
+const NOTICE_RADIUS: f32 = 150.0;
+/// Creeping speed of a stalking cat, scaled by energy.
+const STALK_SPEED: f32 = 45.0;
+/// Seconds a cat stalks before losing interest.
+const STALK_TIME: f32 = 10.0;
+/// A stalking cat this close crouches to pounce.
+const POUNCE_RANGE: f32 = 45.0;
+/// Stalkers give up on prey that gets this far away.
+const GIVE_UP_DIST: f32 = 250.0;
+/// A leaping cat this close to its prey either catches it or just misses.
+const CATCH_RADIUS: f32 = 24.0;
+/// Chance a leap that lands catches the prey, plus up to `CATCH_ENERGY`
+/// for an energetic cat.
+const CATCH_BASE: f32 = 0.3;
+const CATCH_ENERGY: f32 = 0.5;
+/// Prey only notice a stalking or crouching cat this fraction as far away.
+const STEALTH: f32 = 0.4;
+/// Seconds a cat has to bring its catch to the cursor.
+const GIFT_TIME: f32 = 15.0;
+
+/// How one kind of prey gets about.
+struct Gait {
+    /// Wandering speed in pixels/second.
+    wander: f32,
+    /// Speed when running from a cat.
+    flee: f32,
+    /// Notices cats within this range.
+    sense: f32,
+}
+
+fn gait(kind: PreyKind) -> Gait {
+    match kind {
+        PreyKind::Mouse => Gait {
+            wander: 70.0,
+            flee: 160.0,
+            sense: 90.0,
+        },
+        PreyKind::Bug => Gait {
+            wander: 15.0,
+            flee: 35.0,
+            sense: 40.0,
+        },
+        PreyKind::Butterfly => Gait {
+            wander: 45.0,
+            flee: 100.0,
+            sense: 70.0,
+        },
+    }
+}
+
+/// Every live prey animal, indexed in a spatial hash for the hunters, and
+/// the clock for the next one to turn up.
+pub struct Wildlife {
+    grid: SpatialHash,
+    /// Entity and position of each prey animal, as indexed by `grid`.
+    pub index: Vec<(hecs::Entity, Vec2)>,
+    spawn_timer: f32,
+}
+
+impl Default for Wildlife {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Wildlife {
+    pub fn new() -> Self {
+        Self {
+            grid: SpatialHash::new(NOTICE_RADIUS * 1.5, PREY_TABLE_SIZE),
+            index: Vec::new(),
+            spawn_timer: 0.0,
+        }
+    }
+
+    /// Number of prey animals on the desktop.
+    pub fn count(&self) -> usize {
+        self.index.len()
+    }
+
+    fn rebuild(&mut self, world: &hecs::World) {
+        self.grid.clear();
+        self.index.clear();
+        for (entity, pos) in world.query::<&Position>().with::<&Prey>().iter() {
+            self.grid.insert(pos.0, self.index.len() as u32);
+            self.index.push((entity, pos.0));
+        }
+    }
+
+    /// Closest prey to `pos` within `radius`.
+    fn nearest(&self, pos: Vec2, radius: f32) -> Option<(hecs::Entity, Vec2)> {
+        let mut best: Option<(hecs::Entity, Vec2, f32)> = None;
+        self.grid.query_neighbors(pos, |i| {
+            let (entity, at) = self.index[i as usize];
+            let dist_sq = at.distance_squared(pos);
+            if dist_sq < radius * radius && best.is_none_or(|(_, _, d)| dist_sq < d) {
+                best = Some((entity, at, dist_sq));
+            }
+        });
+        best.map(|(entity, at, _)| (entity, at))
+    }
+}
+
+/// Bring in new prey now and then, move every animal (wandering, or fleeing
+/// the nearest cat it notices), let old ones wander off and re-index the rest.
+#[allow(clippy::too_many_arguments)]
+pub fn update(
+    world: &mut hecs::World,
+    wildlife: &mut Wildlife,
+    snapshots: &[CatSnapshot],
+    grid: &SpatialHash,
+    desktop: &Desktop,
+    rng: &mut fastrand::Rng,
+    dt: f32,
+    config: &PreyConfig,
+) {
+    if config.enabled {
+        wildlife.spawn_timer -= dt;
+        if wildlife.spawn_timer <= 0.0 {
+            wildlife.spawn_timer = config.spawn_interval * (0.5 + rng.f32());
+            if wildlife.count() < config.max_prey {
+                spawn(world, desktop, rng);
+            }
+        }
+    }
+
+    // Crouched or creeping cats are hard to spot, and prey caught out by a
+    // leaping one freeze instead of running.
+    let hunters: Vec<(hecs::Entity, bool)> = world
+        .query::<&CatState>()
+        .with::<&Hunting>()
+        .iter()
+        .map(|(e, s)| (e, s.state == BehaviorState::Running))
+        .collect();
+
+    let mut gone = Vec::new();
+    for (entity, (prey, pos, prev_pos, vel)) in
+        world.query_mut::<(&mut Prey, &mut Position, &mut PrevPosition, &mut Velocity)>()
+    {
+        prey.lifetime -= dt;
+        if prey.lifetime <= 0.0 {
+            gone.push(entity);
+            continue;
+        }
+        let gait = gait(prey.kind);
+
+        let mut threat: Option<(Vec2, f32, bool)> = None;
+        grid.query_neighbors(pos.0, |i| {
+            let Some(cat) = snapshots.get(i as usize) else {
+                return;
+            };
+            let hunter = hunters.iter().find(|(e, _)| *e == cat.entity);
+            let range = match hunter {
+                Some((_, false)) => gait.sense * STEALTH,
+                _ => gait.sense,
+            };
+            let dist_sq = cat.pos.distance_squared(pos.0);
+            if dist_sq < range * range && threat.is_none_or(|(_, d, _)| dist_sq < d) {
+                threat = Some((
+                    cat.pos,
+                    dist_sq,
+                    hunter.is_some_and(|&(_, leaping)| leaping),
+                ));
+            }
+        });
+
+        prey.timer -= dt;
+        match threat {
+            Some((_, _, true)) => vel.0 = Vec2::ZERO,
+            Some((cat, _, false)) => {
+                let mut away = (pos.0 - cat).normalize_or(prey.heading);
+                if prey.kind == PreyKind::Butterfly {
+                    // Butterflies escape upward.
+                    away = (away + Vec2::new(0.0, -0.7)).normalize();
+                }
+                prey.heading = away;
+                vel.0 = away * gait.flee;
+            }
+            None => {
+                if prey.timer <= 0.0 {
+                    wander(prey, rng);
+                }
+                vel.0 = prey.heading * gait.wander;
+                if prey.kind == PreyKind::Butterfly {
+                    vel.0.y += (prey.lifetime * 8.0).sin() * 30.0;
+                }
+            }
+        }
+
+        prev_pos.0 = pos.0;
+        pos.0 = desktop.confine(pos.0, pos.0 + vel.0 * dt, EDGE_MARGIN);
+    }
+    for entity in gone {
+        let _ = world.despawn(entity);
+    }
+
+    wildlife.rebuild(world);
+}
+
+/// A new prey animal of a random kind somewhere on the desktop.
+fn spawn(world: &mut hecs::World, desktop: &Desktop, rng: &mut fastrand::Rng) -> hecs::Entity {
+    let kind = match rng.u8(0..3) {
+        0 => PreyKind::Mouse,
+        1 => PreyKind::Bug,
+        _ => PreyKind::Butterfly,
+    };
+    let spot = desktop.random_monitor(rng).random_point(rng);
+    let pos = desktop.confine(spot, spot, EDGE_MARGIN);
+    let mut prey = Prey {
+        kind,
+        heading: Vec2::ZERO,
+        timer: 0.0,
+        lifetime: PREY_LIFETIME,
+    };
+    wander(&mut prey, rng);
+    world.spawn((Position(pos), PrevPosition(pos), Velocity(Vec2::ZERO), prey))
+}
+
+/// Pick the next stretch of wandering: mice alternate dashes and pauses,
+/// bugs zigzag, butterflies drift off somewhere new.
+fn wander(prey: &mut Prey, rng: &mut fastrand::Rng) {
+    let random_heading =
+        |rng: &mut fastrand::Rng| Vec2::from_angle(rng.f32() * std::f32::consts::TAU);
+    match prey.kind {
+        PreyKind::Mouse => {
+            prey.heading = if prey.heading == Vec2::ZERO {
+                random_heading(rng)
+            } else {
+                Vec2::ZERO
+            };
+            prey.timer = 0.5 + rng.f32();
+        }
+        PreyKind::Bug => {
+            let turn = (rng.f32() - 0.5) * std::f32::consts::FRAC_PI_2;
+            prey.heading = if prey.heading == Vec2::ZERO {
+                random_heading(rng)
+            } else {
+                Vec2::from_angle(turn).rotate(prey.heading)
+            };
+            prey.timer = 0.2 + rng.f32() * 0.4;
+        }
+        PreyKind::Butterfly => {
+            prey.heading = random_heading(rng);
+            prey.timer = 1.0 + rng.f32() * 2.0;
+        }
+    }
+}
+
+/// Cats hunting prey: idle cats that notice a critter start stalking it
+/// (curious, energetic cats notice more and keep at it), creep in close,
+/// crouch and pounce through the usual pounce wind-up, and catch it if the
+/// leap lands and they're quick enough. A catch becomes a gift for the
+/// cursor.
+pub fn hunt(
+    world: &mut hecs::World,
+    wildlife: &mut Wildlife,
+    snapshots: &[CatSnapshot],
+    rng: &mut fastrand::Rng,
+    config: &PreyConfig,
+) {
+    // Hunts under way.
+    let hunts: Vec<(hecs::Entity, hecs::Entity, Vec2, BehaviorState, f32, bool)> = world
+        .query::<(
+            &Hunting,
+            &Position,
+            &CatState,
+            &Personality,
+            Option<&InteractionTarget>,
+        )>()
+        .iter()
+        .map(|(e, (h, pos, s, p, t))| (e, h.prey, pos.0, s.state, p.energy, t.is_some()))
+        .collect();
+    for (cat, prey, pos, state, energy, winding_up) in hunts {
+        let Some(prey_pos) = world.get::<&Position>(prey).ok().map(|p| p.0) else {
+            let _ = world.remove_one::<Hunting>(cat);
+            continue;
+        };
+        let to_prey = prey_pos - pos;
+        let dist = to_prey.length();
+        match state {
+            BehaviorState::Walking if !winding_up => {
+                if dist > GIVE_UP_DIST {
+                    let _ = world.remove_one::<Hunting>(cat);
+                } else if dist <= POUNCE_RANGE {
+                    // Crouch; the interaction system leaps when the timer runs out.
+                    if let Ok((state, vel)) =
+                        world.query_one_mut::<(&mut CatState, &mut Velocity)>(cat)
+                    {
+                        state.state = BehaviorState::Pouncing;
+                        state.timer = 0.4;
+                        vel.0 = Vec2::ZERO;
+                    }
+                    let _ = world.insert_one(cat, InteractionTarget(prey));
+                } else if let Ok(mut vel) = world.get::<&mut Velocity>(cat) {
+                    vel.0 = to_prey / dist * STALK_SPEED * (0.5 + energy * 0.5);
+                }
+            }
+            BehaviorState::Pouncing => {}
+            // Mid-leap.
+            BehaviorState::Running if dist > CATCH_RADIUS => {}
+            BehaviorState::Running => {
+                let _ = world.remove_one::<Hunting>(cat);
+                if rng.f32() < CATCH_BASE + CATCH_ENERGY * energy {
+                    catch(world, cat, prey);
+                }
+            }
+            // Distracted, startled or the leap fell short.
+            _ => {
+                let _ = world.remove_one::<Hunting>(cat);
+            }
+        }
+    }
+
+    if wildlife.index.is_empty() {
+        return;
+    }
+    // New hunts.
+    let mut starts = Vec::new();
+    for me in snapshots {
+        if !matches!(me.state, BehaviorState::Idle | BehaviorState::Walking) || me.is_stacked {
+            continue;
+        }
+        let p = &me.personality;
+        if p.curiosity < 0.3 || p.energy < 0.3 {
+            continue;
+        }
+        let Some((prey, at)) = wildlife.nearest(me.pos, NOTICE_RADIUS * (0.5 + p.curiosity)) else {
+            continue;
+        };
+        if rng.f32() < config.hunt_chance * p.curiosity * p.energy {
+            starts.push((me.entity, prey, at));
+        }
+    }
+    for (cat, prey, at) in starts {
+        let busy = world.get::<&Hunting>(cat).is_ok() || world.get::<&GiftCarrier>(cat).is_ok();
+        let Ok((state, vel, pos)) =
+            world.query_one_mut::<(&mut CatState, &mut Velocity, &Position)>(cat)
+        else {
+            continue;
+        };
+        if busy || !matches!(state.state, BehaviorState::Idle | BehaviorState::Walking) {
+            continue;
+        }
+        state.state = BehaviorState::Walking;
+        state.timer = STALK_TIME;
+        vel.0 = (at - pos.0).normalize_or_zero() * STALK_SPEED;
+        let _ = world.insert_one(cat, Hunting { prey });
+    }
+}
+
+/// `cat` has `prey` in its paws and sets off to show the cursor.
+fn catch(world: &mut hecs::World, cat: hecs::Entity, prey: hecs::Entity) {
+    let _ = world.despawn(prey);
+    journal::record(world, cat, JournalEvent::CaughtPrey);
+    let _ = world.insert_one(cat, GiftCarrier { timer: GIFT_TIME });
+    if let Ok(mut state) = world.get::<&mut CatState>(cat) {
+        state.state = BehaviorState::Walking;
+        state.timer = GIFT_TIME;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BehaviorConfig, InteractionConfig};
+    use crate::ecs::components::TestCat;
+    use crate::ecs::systems::interaction::InteractionBuffers;
+    use crate::ecs::systems::{behavior, interaction, spatial};
+    use crate::relations::Relationships;
+
+    fn hunter(world: &mut hecs::World, pos: Vec2) -> hecs::Entity {
+        TestCat::at(pos)
+            .laziness(0.0)
+            .energy(1.0)
+            .curiosity(1.0)
+            .skittishness(0.0)
+            .spawn(world)
+    }
+
+    #[test]
+    fn prey_flee_cats_they_notice() {
+        let desktop = Desktop::single(800, 600);
+        let mut world = hecs::World::new();
+        let mut rng = fastrand::Rng::with_seed(1);
+        let config = PreyConfig {
+            enabled: false,
+            ..PreyConfig::default()
+        };
+        let mouse = spawn(&mut world, &desktop, &mut rng);
+        world.get::<&mut Position>(mouse).unwrap().0 = Vec2::new(400.0, 300.0);
+        hunter(&mut world, Vec2::new(340.0, 300.0));
+
+        let mut wildlife = Wildlife::new();
+        let mut grid = SpatialHash::new(128.0, 64);
+        let mut snapshots = Vec::new();
+        spatial::rebuild(&world, &mut grid, &mut snapshots);
+        let dt = 1.0 / 60.0;
+        update(
+            &mut world,
+            &mut wildlife,
+            &snapshots,
+            &grid,
+            &desktop,
+            &mut rng,
+            dt,
+            &config,
+        );
+
+        let kind = world.get::<&Prey>(mouse).unwrap().kind;
+        let vel = world.get::<&Velocity>(mouse).unwrap().0;
+        assert!(
+            vel.x > 0.0 && vel.length() >= gait(kind).flee * 0.99,
+            "{kind:?} {vel}"
+        );
+        assert_eq!(wildlife.count(), 1);
+        assert_eq!(
+            wildlife.nearest(Vec2::new(350.0, 300.0), 100.0).unwrap().0,
+            mouse
+        );
+    }
+
+    #[test]
+    fn cats_stalk_pounce_and_bring_back_their_catch() {
+        let mut world = hecs::World::new();
+        let mut rng = fastrand::Rng::with_seed(7);
+        let config = PreyConfig {
+            enabled: false,
+            hunt_chance: 1.0,
+            ..PreyConfig::default()
+        };
+        // A bug that has stopped dead, so the outcome only hangs on the cat.
+        let bug = world.spawn((
+            Position(Vec2::new(400.0, 300.0)),
+            PrevPosition(Vec2::new(400.0, 300.0)),
+            Velocity(Vec2::ZERO),
+            Prey {
+                kind: PreyKind::Bug,
+                heading: Vec2::ZERO,
+                timer: f32::INFINITY,
+                lifetime: PREY_LIFETIME,
+            },
+        ));
+        let cat = hunter(&mut world, Vec2::new(300.0, 300.0));
+
+        let mut wildlife = Wildlife::new();
+        let mut grid = SpatialHash::new(128.0, 64);
+        let mut snapshots = Vec::new();
+        let mut bufs = InteractionBuffers::new(4);
+        let mut relations = Relationships::default();
+        let dt = 1.0 / 60.0;
+        let mut pounced = false;
+        for _ in 0..600 {
+            behavior::update(&mut world, dt, &mut rng, 1.0, &BehaviorConfig::default());
+            for (_, (pos, vel)) in world.query_mut::<(&mut Position, &Velocity)>() {
+                pos.0 += vel.0 * dt;
+            }
+            spatial::rebuild(&world, &mut grid, &mut snapshots);
+            // Keep the bug still: only the hunt is under test.
+            if world.contains(bug) {
+                world.get::<&mut Velocity>(bug).unwrap().0 = Vec2::ZERO;
+            }
+            interaction::update(
+                &mut world,
+                &snapshots,
+                &grid,
+                &mut bufs,
+                &mut rng,
+                dt,
+                Vec2::new(700.0, 100.0),
+                &InteractionConfig {
+                    gift_spawn_chance: 0.0,
+                    ..InteractionConfig::default()
+                },
+                &mut relations,
+            );
+            wildlife.rebuild(&world);
+            hunt(&mut world, &mut wildlife, &snapshots, &mut rng, &config);
+            pounced |= world.get::<&CatState>(cat).unwrap().state == BehaviorState::Pouncing;
+            if !world.contains(bug) {
+                break;
+            }
+        }
+        assert!(pounced);
+        assert!(!world.contains(bug), "caught");
+        assert!(world.get::<&GiftCarrier>(cat).is_ok());
+        assert!(world.get::<&Hunting>(cat).is_err());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::ecs::components::{Appearance, CatState, Personality, Position, Stacked, Velocity};
+use crate::ecs::components::{
+    Appearance, CatId, CatState, Personality, Position, Stacked, Velocity,
+};
 use crate::spatial::{CatSnapshot, SpatialHash};
 
 /// Rebuild the spatial hash grid and snapshot cache from current positions.
@@ -9,12 +11,22 @@ pub fn rebuild(
 ) {
     grid.clear();
     snapshots.clear();
-    for (entity, (pos, vel, cat_state, personality, appearance, stacked)) in
-        world.query::<(&Position, &Velocity, &CatState, &Personality, &Appearance, Option<&Stacked>)>().iter()
+    for (entity, (pos, vel, cat_state, personality, appearance, stacked, id)) in world
+        .query::<(
+            &Position,
+            &Velocity,
+            &CatState,
+            &Personality,
+            &Appearance,
+            Option<&Stacked>,
+            Option<&CatId>,
+        )>()
+        .iter()
     {
         let idx = snapshots.len() as u32;
         snapshots.push(CatSnapshot {
             entity,
+            id: id.copied(),
             pos: pos.0,
             vel: vel.0,
             state: cat_state.state,
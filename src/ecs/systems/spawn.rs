@@ -0,0 +1,69 @@
+use glam::Vec2;
+
+use crate::ecs::components::{Position, PrevPosition, SpawnAnimation};
+use crate::ecs::systems::physics::GRAVITY;
+
+/// Restitution of a landing cat (speed kept on each bounce).
+const BOUNCE_RESTITUTION: f32 = 0.4;
+/// After this many bounces, animation is done.
+const MAX_BOUNCES: u8 = 3;
+/// If velocity is below this after a bounce, stop early.
+const BOUNCE_VEL_THRESHOLD: f32 = 30.0;
+
+/// A bounce impact event — position + intensity for dust particles.
+#[derive(Debug, Clone, Copy)]
+pub struct BounceEvent {
+    pub pos: Vec2,
+    pub intensity: f32, // 0.0–1.0, higher = bigger impact
+}
+
+/// Advance spawn drop-in animations with real physics.
+/// Gravity accelerates cats downward. On impact at target_y, velocity reverses
+/// with damping for a natural bounce. Appends bounce events for particle effects.
+pub fn update(world: &mut hecs::World, dt: f32, bounces: &mut Vec<BounceEvent>) {
+    let mut done = Vec::new();
+
+    for (entity, (pos, prev_pos, anim)) in
+        world.query_mut::<(&mut Position, &mut PrevPosition, &mut SpawnAnimation)>()
+    {
+        // Apply gravity (positive Y = downward on screen)
+        anim.vel_y += GRAVITY * dt;
+        prev_pos.0.y = pos.0.y;
+        pos.0.y += anim.vel_y * dt;
+
+        // Ground collision detection
+        if pos.0.y >= anim.target_y {
+            pos.0.y = anim.target_y;
+            anim.has_landed = true;
+            anim.bounce_count += 1;
+            let settled = land(&mut anim.vel_y, pos.0, bounces);
+
+            // Check if animation is done (enough bounces or too little energy)
+            if anim.bounce_count >= MAX_BOUNCES || settled {
+                prev_pos.0.y = anim.target_y;
+                done.push(entity);
+            }
+        }
+    }
+
+    for entity in done {
+        let _ = world.remove_one::<SpawnAnimation>(entity);
+    }
+}
+
+/// A falling cat hitting the ground at `at` with downward speed `vel_y`:
+/// bounces it back up with some of that speed, kicks up dust in proportion
+/// to the impact and returns whether it has come to rest.
+pub fn land(vel_y: &mut f32, at: Vec2, bounces: &mut Vec<BounceEvent>) -> bool {
+    // Record bounce intensity from impact velocity (before damping)
+    let intensity = (vel_y.abs() / 800.0).clamp(0.0, 1.0);
+
+    // Reverse velocity, losing some to the impact
+    *vel_y = -vel_y.abs() * BOUNCE_RESTITUTION;
+
+    // Emit bounce event for dust particles
+    if intensity > 0.05 {
+        bounces.push(BounceEvent { pos: at, intensity });
+    }
+    vel_y.abs() < BOUNCE_VEL_THRESHOLD
+}
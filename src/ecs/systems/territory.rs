@@ -0,0 +1,306 @@
+use glam::Vec2;
+
+use crate::ecs::components::{BehaviorState, CatState, Clan, Kitten, Position, Velocity};
+use crate::ecs::systems::{behavior, interaction};
+use crate::relations::{self, Relationships};
+use crate::scent::ScentField;
+use crate::spatial::{CatSnapshot, SpatialHash};
+
+/// Scent laid down per second by a cat on the move.
+const MARK_RATE: f32 = 0.01;
+/// Sitting, grooming or sleeping cats mark this many times faster.
+const REST_MARK: f32 = 3.0;
+/// Rival scent has to beat a cat's own by this much before it's trespassing.
+const TRESPASS_MARGIN: f32 = 0.15;
+/// A cat on home turf notices intruders within this range.
+const CHALLENGE_RADIUS: f32 = 80.0;
+const CHALLENGE_RADIUS_SQ: f32 = CHALLENGE_RADIUS * CHALLENGE_RADIUS;
+/// Chance per second that a fully territorial cat confronts an intruder.
+const CHALLENGE_RATE: f32 = 0.5;
+/// Territoriality above which a confrontation comes to blows instead of a hiss.
+const FIGHT_TERRITORIALITY: f32 = 0.5;
+/// Speed of a cat backing off rival ground or a hiss.
+const RETREAT_SPEED: f32 = 60.0;
+
+enum Reaction {
+    /// `owner` puffs up at `intruder`, who backs off.
+    Hiss {
+        owner: hecs::Entity,
+        intruder: hecs::Entity,
+    },
+    Fight {
+        owner: hecs::Entity,
+        intruder: hecs::Entity,
+    },
+    /// A timid trespasser turns around, heading `dir` (normalized).
+    Retreat { entity: hecs::Entity, dir: Vec2 },
+}
+
+/// Territoriality in [0, 1]: bold, energetic cats defend their ground.
+fn territoriality(skittishness: f32, energy: f32) -> f32 {
+    (1.0 - skittishness) * energy
+}
+
+/// Lay down and fade scent, then let cats react to whose ground they're on:
+/// timid trespassers retreat, and cats on home turf hiss at or fight
+/// intruders from other clans (friends are tolerated).
+pub fn update(
+    world: &mut hecs::World,
+    snapshots: &[CatSnapshot],
+    grid: &SpatialHash,
+    scent: &mut ScentField,
+    rng: &mut fastrand::Rng,
+    dt: f32,
+    relations: &mut Relationships,
+) {
+    // Kittens don't hold territory yet.
+    for (_, (pos, clan, state)) in world
+        .query_mut::<(&Position, &Clan, &CatState)>()
+        .without::<&Kitten>()
+    {
+        let rate = match state.state {
+            BehaviorState::Idle | BehaviorState::Grooming | BehaviorState::Sleeping => {
+                MARK_RATE * REST_MARK
+            }
+            _ => MARK_RATE,
+        };
+        scent.deposit(clan.0 as usize, pos.0, rate * dt);
+    }
+    scent.fade(dt);
+
+    let clan_of = |entity| world.get::<&Clan>(entity).ok().map(|c| c.0 as usize);
+    let mut reactions = Vec::new();
+    for me in snapshots {
+        if !matches!(me.state, BehaviorState::Idle | BehaviorState::Walking) || me.is_stacked {
+            continue;
+        }
+        let Some(clan) = clan_of(me.entity) else {
+            continue;
+        };
+        if world.get::<&Kitten>(me.entity).is_ok() {
+            continue;
+        }
+        let own = scent.sample(clan, me.pos);
+        let rival = scent.strongest_rival(clan, me.pos).map_or(0.0, |(_, s)| s);
+
+        if rival > own + TRESPASS_MARGIN {
+            // Someone else's ground: the timid turn back the way they came.
+            let away = -scent.rival_gradient(clan, me.pos);
+            if me.state == BehaviorState::Walking
+                && me.personality.skittishness > 0.5
+                && away != Vec2::ZERO
+                && me.vel.dot(away) <= 0.0
+            {
+                reactions.push(Reaction::Retreat {
+                    entity: me.entity,
+                    dir: away.normalize(),
+                });
+            }
+            continue;
+        }
+        if own <= rival {
+            continue;
+        }
+
+        let boldness = territoriality(me.personality.skittishness, me.personality.energy);
+        if rng.f32() >= CHALLENGE_RATE * boldness * dt {
+            continue;
+        }
+        // Nearest cat of another clan standing on our side of the border.
+        let mut intruder: Option<(hecs::Entity, f32)> = None;
+        grid.query_neighbors(me.pos, |ni| {
+            let Some(them) = snapshots.get(ni as usize) else {
+                return;
+            };
+            let dist_sq = them.pos.distance_squared(me.pos);
+            if them.entity == me.entity || dist_sq > CHALLENGE_RADIUS_SQ {
+                return;
+            }
+            let Some(their_clan) = clan_of(them.entity) else {
+                return;
+            };
+            if their_clan == clan
+                || relations.between(me.id, them.id) >= relations::FRIEND
+                || scent.sample(their_clan, them.pos) >= scent.sample(clan, them.pos)
+            {
+                return;
+            }
+            if intruder.is_none_or(|(_, best)| dist_sq < best) {
+                intruder = Some((them.entity, dist_sq));
+            }
+        });
+        if let Some((intruder, _)) = intruder {
+            reactions.push(if boldness > FIGHT_TERRITORIALITY {
+                Reaction::Fight {
+                    owner: me.entity,
+                    intruder,
+                }
+            } else {
+                Reaction::Hiss {
+                    owner: me.entity,
+                    intruder,
+                }
+            });
+        }
+    }
+
+    for reaction in reactions {
+        match reaction {
+            Reaction::Fight { owner, intruder } => {
+                // The defender has home advantage.
+                if !interaction::start_fight(world, owner, intruder, rng, relations) {
+                    hiss(world, owner, intruder, rng, relations);
+                }
+            }
+            Reaction::Hiss { owner, intruder } => hiss(world, owner, intruder, rng, relations),
+            Reaction::Retreat { entity, dir } => {
+                if let Ok(mut vel) = world.get::<&mut Velocity>(entity) {
+                    vel.0 = dir * RETREAT_SPEED;
+                }
+            }
+        }
+    }
+}
+
+/// `owner` puffs up and hops (the startle animation) and a free intruder
+/// runs off.
+fn hiss(
+    world: &mut hecs::World,
+    owner: hecs::Entity,
+    intruder: hecs::Entity,
+    rng: &mut fastrand::Rng,
+    relations: &mut Relationships,
+) {
+    let free = |state: &CatState| {
+        matches!(
+            state.state,
+            BehaviorState::Idle | BehaviorState::Walking | BehaviorState::Grooming
+        )
+    };
+    let (Ok(owner_pos), Ok(intruder_pos)) = (
+        world.get::<&Position>(owner),
+        world.get::<&Position>(intruder),
+    ) else {
+        return;
+    };
+    let away = (intruder_pos.0 - owner_pos.0).normalize_or_zero();
+    drop((owner_pos, intruder_pos));
+
+    match world.query_one_mut::<(&mut CatState, &mut Velocity)>(owner) {
+        Ok((state, vel)) if free(state) => {
+            vel.0 = Vec2::ZERO;
+            behavior::trigger_startle(state, vel, rng);
+        }
+        _ => return,
+    }
+    if let Ok(mut state) = world.get::<&mut CatState>(intruder) {
+        if free(&state) {
+            state.state = BehaviorState::Running;
+            state.timer = 1.0 + rng.f32();
+            drop(state);
+            if let Ok(mut vel) = world.get::<&mut Velocity>(intruder) {
+                vel.0 = away * RETREAT_SPEED;
+            }
+        }
+    }
+    relations.adjust_cats(world, owner, intruder, -relations::HISS_GRUDGE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::{CatId, TestCat};
+    use crate::ecs::systems::spatial;
+
+    fn cat(
+        world: &mut hecs::World,
+        x: f32,
+        id: u64,
+        clan: u8,
+        skittishness: f32,
+        vel: Vec2,
+    ) -> hecs::Entity {
+        let cat = TestCat::at(Vec2::new(x, 100.0))
+            .moving(vel)
+            .state(BehaviorState::Walking, 5.0)
+            .energy(1.0)
+            .skittishness(skittishness)
+            .spawn(world);
+        world.insert(cat, (CatId(id), Clan(clan))).unwrap();
+        cat
+    }
+
+    fn run(
+        world: &mut hecs::World,
+        scent: &mut ScentField,
+        rng: &mut fastrand::Rng,
+        relations: &mut Relationships,
+    ) {
+        let mut grid = SpatialHash::new(120.0, 64);
+        let mut snapshots = Vec::new();
+        spatial::rebuild(world, &mut grid, &mut snapshots);
+        update(world, &snapshots, &grid, scent, rng, 1.0, relations);
+    }
+
+    fn claim(scent: &mut ScentField, clan: usize, from: f32, to: f32) {
+        let mut x = from;
+        while x < to {
+            scent.deposit(clan, Vec2::new(x, 100.0), 1.0);
+            x += 5.0;
+        }
+    }
+
+    #[test]
+    fn defenders_fight_intruders_but_not_friends() {
+        let mut scent = ScentField::new(1280.0, 1280.0);
+        claim(&mut scent, 0, 0.0, 400.0);
+        let mut relations = Relationships::default();
+        let mut world = hecs::World::new();
+        let mut rng = fastrand::Rng::with_seed(5);
+        let owner = cat(&mut world, 100.0, 1, 0, 0.0, Vec2::ZERO);
+        cat(&mut world, 140.0, 2, 1, 0.0, Vec2::ZERO);
+        relations.set(CatId(1), CatId(2), 0.8);
+
+        for _ in 0..10 {
+            run(&mut world, &mut scent, &mut rng, &mut relations);
+        }
+        assert_eq!(
+            world.get::<&CatState>(owner).unwrap().state,
+            BehaviorState::Walking
+        );
+
+        let stranger = cat(&mut world, 60.0, 3, 2, 0.0, Vec2::ZERO);
+        for _ in 0..10 {
+            run(&mut world, &mut scent, &mut rng, &mut relations);
+        }
+        assert_eq!(
+            world.get::<&CatState>(owner).unwrap().state,
+            BehaviorState::Fighting
+        );
+        assert_eq!(
+            world.get::<&CatState>(stranger).unwrap().state,
+            BehaviorState::Fighting
+        );
+        assert!(relations.affinity(CatId(1), CatId(3)) < 0.0);
+        assert!(scent.sample(0, Vec2::new(100.0, 100.0)) > 0.9);
+    }
+
+    #[test]
+    fn timid_trespassers_turn_back() {
+        let mut scent = ScentField::new(1280.0, 1280.0);
+        claim(&mut scent, 1, 200.0, 600.0);
+        let mut relations = Relationships::default();
+        let mut world = hecs::World::new();
+        let timid = cat(&mut world, 205.0, 1, 0, 0.9, Vec2::new(40.0, 0.0));
+        let bold = cat(&mut world, 205.0, 2, 3, 0.1, Vec2::new(40.0, 0.0));
+
+        run(
+            &mut world,
+            &mut scent,
+            &mut fastrand::Rng::with_seed(5),
+            &mut relations,
+        );
+        assert!(world.get::<&Velocity>(timid).unwrap().0.x < 0.0);
+        assert!(world.get::<&Velocity>(bold).unwrap().0.x > 0.0);
+    }
+}
@@ -3,6 +3,7 @@ use glam::Vec2;
 use crate::ecs::components::{
     Appearance, BehaviorState, CatState, Position, Stacked, Velocity,
 };
+use crate::journal::{self, JournalEvent};
 use crate::spatial::{CatSnapshot, SpatialHash};
 
 /// Cats within this distance can start stacking.
@@ -187,6 +188,7 @@ pub fn update(
             continue;
         }
         let _ = world.insert_one(climber, Stacked { base });
+        journal::record(world, climber, JournalEvent::ClimbedTower);
         if let Ok(mut state) = world.get::<&mut CatState>(climber) {
             state.state = BehaviorState::Idle;
             state.timer = 5.0 + rng.f32() * 10.0;
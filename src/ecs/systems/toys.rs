@@ -0,0 +1,349 @@
+use glam::Vec2;
+
+use crate::desktop::Desktop;
+use crate::ecs::components::{
+    Appeal, BehaviorState, CatState, Collider, Lifetime, Needs, Occupancy, Personality, Play,
+    Position, RigidBody, Shattered, Toy, ToyBody, Velocity,
+};
+use crate::ecs::systems::{needs, trust};
+use crate::journal::{self, JournalEvent};
+use crate::nav::Navigator;
+
+/// Toys spatial hash cell size: the widest [`Appeal::radius`] of any toy, so
+/// a neighbor lookup sees every toy that could draw a cat in.
+const TOY_CELL_SIZE: f32 = 300.0;
+/// Toys spatial hash table size; there are a few dozen at most.
+const TOY_TABLE_SIZE: usize = 256;
+/// Fragile toys shatter this close to a monitor wall.
+const SHATTER_MARGIN: f32 = 15.0;
+/// Speed of a cat dashing off after batting a toy, scaled by energy.
+const BAT_DASH_SPEED: f32 = 120.0;
+
+/// One toy as a cat sees it.
+#[derive(Debug, Clone, Copy)]
+pub struct ToyEntry {
+    pub entity: hecs::Entity,
+    pub pos: Vec2,
+    pub appeal: Appeal,
+    /// No room left inside.
+    pub full: bool,
+}
+
+/// Every toy still in play (not shattered), indexed in a spatial hash so
+/// cats only look at the toys around them.
+pub struct ToyIndex {
+    grid: crate::spatial::SpatialHash,
+    pub entries: Vec<ToyEntry>,
+}
+
+impl Default for ToyIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToyIndex {
+    pub fn new() -> Self {
+        Self {
+            grid: crate::spatial::SpatialHash::new(TOY_CELL_SIZE, TOY_TABLE_SIZE),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Number of toys in play.
+    pub fn count(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn rebuild(&mut self, world: &hecs::World) {
+        self.grid.clear();
+        self.entries.clear();
+        for (entity, (pos, appeal, occupancy)) in world
+            .query::<(&Position, &Appeal, Option<&Occupancy>)>()
+            .with::<&Toy>()
+            .without::<&Shattered>()
+            .iter()
+        {
+            self.grid.insert(pos.0, self.entries.len() as u32);
+            self.entries.push(ToyEntry {
+                entity,
+                pos: pos.0,
+                appeal: *appeal,
+                full: occupancy.is_some_and(|o| o.occupants >= o.capacity),
+            });
+        }
+    }
+
+    /// Closest toy to `pos` that draws a cat in and passes `want`.
+    fn nearest(&self, pos: Vec2, want: impl Fn(&ToyEntry) -> bool) -> Option<(usize, f32)> {
+        let mut best: Option<(usize, f32)> = None;
+        self.grid.query_neighbors(pos, |i| {
+            let entry = &self.entries[i as usize];
+            let dist_sq = entry.pos.distance_squared(pos);
+            let radius = entry.appeal.radius;
+            if dist_sq < radius * radius
+                && best.is_none_or(|(_, d)| dist_sq < d)
+                && !entry.full
+                && want(entry)
+            {
+                best = Some((i as usize, dist_sq));
+            }
+        });
+        best.map(|(i, dist_sq)| (i, dist_sq.sqrt()))
+    }
+}
+
+/// Age every toy, clearing away expired ones, let the cursor shoo pushable
+/// ones along and shatter fragile ones that have reached a screen edge, then
+/// re-index the rest. Moving them is left to `systems::physics`. Returns
+/// where toys shattered, for particles.
+pub fn update(
+    world: &mut hecs::World,
+    index: &mut ToyIndex,
+    desktop: &Desktop,
+    mouse: Vec2,
+    dt: f32,
+) -> Vec<Vec2> {
+    let mut expired = Vec::new();
+    let mut shattered = Vec::new();
+
+    for (entity, (pos, vel, life, body, broken)) in world
+        .query_mut::<(
+            &Position,
+            &mut Velocity,
+            &mut Lifetime,
+            &ToyBody,
+            Option<&Shattered>,
+        )>()
+        .with::<&Toy>()
+    {
+        life.0 -= dt;
+        if life.0 <= 0.0 {
+            expired.push(entity);
+            continue;
+        }
+        if broken.is_some() {
+            continue;
+        }
+        if body.fragile && !desktop.inside(pos.0, SHATTER_MARGIN) {
+            shattered.push((entity, pos.0));
+            continue;
+        }
+
+        // The cursor shoos it along
+        let to_toy = pos.0 - mouse;
+        let dist = to_toy.length();
+        if dist < body.push_radius && dist > 1.0 {
+            let push = (1.0 - dist / body.push_radius) * body.push_strength;
+            vel.0 += to_toy / dist * push * dt;
+        }
+    }
+
+    for entity in expired {
+        let _ = world.despawn(entity);
+    }
+    let mut shattered_at = Vec::with_capacity(shattered.len());
+    for (entity, pos) in shattered {
+        shatter(world, entity);
+        shattered_at.push(pos);
+    }
+
+    index.rebuild(world);
+    shattered_at
+}
+
+/// Break a fragile toy. It's left lying (unseen, and out of the physics)
+/// until its lifetime runs out.
+pub fn shatter(world: &mut hecs::World, toy: hecs::Entity) {
+    let _ = world.remove::<(RigidBody, Collider)>(toy);
+    let _ = world.insert_one(toy, Shattered);
+}
+
+/// What a cat did with a toy this tick.
+enum Action {
+    Hit(Vec2),
+    SitIn,
+    Eat,
+}
+
+/// Draw cats to the nearest toy that appeals to them, then let those within
+/// reach play with it: bat it, sit in it, eat it or shove it along. Cats
+/// heading for a toy route around obstacles via `nav`.
+pub fn play(
+    world: &mut hecs::World,
+    index: &mut ToyIndex,
+    rng: &mut fastrand::Rng,
+    nav: &mut Navigator,
+) {
+    if index.entries.is_empty() {
+        return;
+    }
+
+    let mut actions: Vec<(usize, hecs::Entity, Action)> = Vec::new();
+    for (entity, (pos, vel, state, personality)) in
+        world.query_mut::<(&Position, &mut Velocity, &mut CatState, &Personality)>()
+    {
+        let cat_state = state.state;
+        let Some((i, dist)) = index.nearest(pos.0, |toy| {
+            let appeal = &toy.appeal;
+            appeal.drive.of(personality) >= appeal.min_drive
+                && match appeal.play {
+                    Play::Bat => matches!(
+                        cat_state,
+                        BehaviorState::Idle | BehaviorState::Walking | BehaviorState::Running
+                    ),
+                    Play::SitIn | Play::Eat => {
+                        matches!(cat_state, BehaviorState::Idle | BehaviorState::Walking)
+                    }
+                    Play::Nudge => matches!(
+                        cat_state,
+                        BehaviorState::Walking
+                            | BehaviorState::Running
+                            | BehaviorState::Zoomies
+                            | BehaviorState::ChasingMouse
+                    ),
+                }
+                // Eaten already by a cat ahead in line
+                && !actions.iter().any(|&(j, _, ref a)| {
+                    matches!(a, Action::Eat) && index.entries[j].entity == toy.entity
+                })
+        }) else {
+            continue;
+        };
+        let toy = index.entries[i];
+        let appeal = toy.appeal;
+        let eagerness = 0.5 + appeal.drive.of(personality) * 0.5;
+
+        if dist <= appeal.reach {
+            match appeal.play {
+                Play::Bat => {
+                    // Bat it off somewhere and dash the other way
+                    let dir = Vec2::new(rng.f32() - 0.5, rng.f32() - 0.5).normalize_or_zero();
+                    actions.push((i, entity, Action::Hit(dir * appeal.strength * eagerness)));
+                    vel.0 = -dir * BAT_DASH_SPEED * personality.energy;
+                    state.state = BehaviorState::Running;
+                    state.timer = 0.8 + rng.f32() * 0.7;
+                }
+                Play::SitIn => {
+                    // Already settled in
+                    if cat_state == BehaviorState::Idle {
+                        continue;
+                    }
+                    vel.0 = Vec2::ZERO;
+                    state.state = BehaviorState::Idle;
+                    state.timer = 5.0 + rng.f32() * 10.0;
+                    actions.push((i, entity, Action::SitIn));
+                }
+                Play::Eat => actions.push((i, entity, Action::Eat)),
+                Play::Nudge => {
+                    if dist > 1.0 {
+                        let push = (toy.pos - pos.0) / dist * appeal.strength * eagerness;
+                        actions.push((i, entity, Action::Hit(push)));
+                    }
+                }
+            }
+        } else if appeal.speed > 0.0 {
+            let dir = nav.steer(pos.0, toy.pos);
+            vel.0 = dir * appeal.speed * eagerness;
+            // Cats chasing something that rolls break into a run
+            state.state = if appeal.play == Play::Bat {
+                BehaviorState::Running
+            } else {
+                BehaviorState::Walking
+            };
+            state.timer = 0.5;
+        }
+    }
+
+    for (i, cat, action) in actions {
+        let toy = index.entries[i].entity;
+        match action {
+            Action::Hit(impulse) => {
+                if let Ok(mut vel) = world.get::<&mut Velocity>(toy) {
+                    vel.0 += impulse;
+                }
+            }
+            Action::SitIn => {
+                if let Ok(mut occupancy) = world.get::<&mut Occupancy>(toy) {
+                    occupancy.occupants = (occupancy.occupants + 1).min(occupancy.capacity);
+                    index.entries[i].full = occupancy.occupants >= occupancy.capacity;
+                }
+            }
+            Action::Eat => {
+                index.entries[i].full = true;
+                let _ = world.despawn(toy);
+                journal::record(world, cat, JournalEvent::AteTreat);
+                trust::adjust(world, cat, trust::TREAT_TRUST);
+                if let Ok(mut cat_needs) = world.get::<&mut Needs>(cat) {
+                    cat_needs.hunger = (cat_needs.hunger + needs::TREAT_FILL).min(1.0);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::TestCat;
+    use crate::ecs::systems::physics::{self, PhysicsWorld};
+    use crate::spatial::SpatialHash;
+    use crate::toy::{self, ToyKind};
+
+    fn cat(world: &mut hecs::World, pos: Vec2) -> hecs::Entity {
+        TestCat::at(pos)
+            .laziness(0.0)
+            .energy(1.0)
+            .curiosity(1.0)
+            .skittishness(0.0)
+            .spawn(world)
+    }
+
+    #[test]
+    fn cats_walk_over_and_eat_treats() {
+        let desktop = Desktop::single(800, 600);
+        let mut world = hecs::World::new();
+        let mut rng = fastrand::Rng::with_seed(1);
+        let mut nav = Navigator::new(&desktop);
+        let mut index = ToyIndex::new();
+        let cat = cat(&mut world, Vec2::new(300.0, 300.0));
+        // One more than fit: the first treat is cleared away.
+        let treats: Vec<_> = (0..=ToyKind::Treat.spec().cap)
+            .map(|_| toy::spawn(&mut world, ToyKind::Treat, Vec2::new(400.0, 300.0)))
+            .collect();
+        assert!(!world.contains(treats[0]));
+
+        let dt = 1.0 / 60.0;
+        let far = Vec2::new(-1000.0, -1000.0);
+        for _ in 0..120 {
+            update(&mut world, &mut index, &desktop, far, dt);
+            play(&mut world, &mut index, &mut rng, &mut nav);
+            let vel = world.get::<&Velocity>(cat).unwrap().0;
+            world.get::<&mut Position>(cat).unwrap().0 += vel * dt;
+        }
+        let left = treats.iter().filter(|&&t| world.contains(t)).count();
+        assert!(left < ToyKind::Treat.spec().cap, "{left} treats left");
+    }
+
+    #[test]
+    fn glasses_shatter_off_the_edge() {
+        let desktop = Desktop::single(800, 600);
+        let mut world = hecs::World::new();
+        let mut index = ToyIndex::new();
+        let glass = toy::spawn(&mut world, ToyKind::Glass, Vec2::new(780.0, 300.0));
+        world.get::<&mut Velocity>(glass).unwrap().0 = Vec2::new(200.0, 0.0);
+
+        let mut physics = PhysicsWorld::new();
+        let grid = SpatialHash::new(128.0, 64);
+        let dt = 1.0 / 60.0;
+        let mut shattered = Vec::new();
+        for _ in 0..30 {
+            physics::step(&mut world, &mut physics, &desktop, &[], &[], &grid, dt);
+            shattered.extend(update(&mut world, &mut index, &desktop, Vec2::ZERO, dt));
+        }
+        assert_eq!(shattered.len(), 1);
+        assert!(world.get::<&Shattered>(glass).is_ok());
+        assert!(world.get::<&RigidBody>(glass).is_err());
+        assert_eq!(index.count(), 0);
+    }
+}
@@ -0,0 +1,128 @@
+use glam::Vec2;
+
+use crate::config::MouseConfig;
+use crate::ecs::components::{BehaviorState, CatState, Needs, Position, Trust};
+use crate::ecs::systems::mouse::CursorState;
+
+/// Trust gained by eating a treat.
+pub const TREAT_TRUST: f32 = 0.05;
+/// Trust gained by delivering a gift to the cursor.
+pub const GIFT_TRUST: f32 = 0.08;
+/// Trust lost by the cat a click startles.
+pub const STARTLE_DISTRUST: f32 = 0.1;
+/// Trust gained per second of petting.
+const PET_TRUST: f32 = 0.03;
+/// Comfort regained per second of petting.
+const PET_COMFORT: f32 = 0.1;
+/// Trust lost per second of being shoved at full strength by the Moses Effect.
+const SHOVE_DISTRUST: f32 = 0.15;
+/// The cursor pets a cat it rests or strokes on within this distance.
+pub const PET_RADIUS: f32 = 30.0;
+
+/// At or above this, a cat comes over, follows the cursor and rubs against it.
+pub const TRUSTING: f32 = 0.7;
+/// Below this, a cat keeps its distance from the cursor.
+pub const WARY: f32 = 0.3;
+
+/// Nudge `entity`'s trust by `delta`, if it keeps track of any.
+pub fn adjust(world: &hecs::World, entity: hecs::Entity, delta: f32) {
+    if let Ok(mut trust) = world.get::<&mut Trust>(entity) {
+        trust.adjust(delta);
+    }
+}
+
+/// How skittish a cat acts around the cursor: its nature, shifted up or
+/// down by how far its trust has strayed from neutral.
+pub fn wariness(skittishness: f32, trust: f32) -> f32 {
+    (skittishness + Trust::NEUTRAL - trust).clamp(0.0, 1.0)
+}
+
+/// Trust earned and lost to the cursor itself: calm cats under a slow or
+/// resting cursor are being petted, while a fast cursor parting the crowd
+/// (the Moses Effect in `mouse`) shoves cats aside.
+pub fn update(
+    world: &mut hecs::World,
+    mouse: Vec2,
+    cursor: &CursorState,
+    dt: f32,
+    tuning: &MouseConfig,
+) {
+    let shoving = cursor.speed > tuning.moses_speed_threshold;
+    for (_, (pos, state, trust, needs)) in
+        world.query_mut::<(&Position, &CatState, &mut Trust, Option<&mut Needs>)>()
+    {
+        let dist = pos.0.distance(mouse);
+        if shoving {
+            if dist < tuning.moses_radius {
+                let falloff = 1.0 - dist / tuning.moses_radius;
+                trust.adjust(-SHOVE_DISTRUST * falloff * dt);
+            }
+            continue;
+        }
+        let calm = matches!(
+            state.state,
+            BehaviorState::Idle
+                | BehaviorState::Walking
+                | BehaviorState::Grooming
+                | BehaviorState::Sleeping
+                | BehaviorState::ChasingMouse
+        );
+        if calm && dist < PET_RADIUS {
+            trust.adjust(PET_TRUST * dt);
+            if let Some(needs) = needs {
+                needs.comfort = (needs.comfort + PET_COMFORT * dt).min(1.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::TestCat;
+
+    fn cat(world: &mut hecs::World, pos: Vec2, trust: f32) -> hecs::Entity {
+        let cat = TestCat::at(pos)
+            .state(BehaviorState::Idle, 5.0)
+            .spawn(world);
+        world.insert(cat, (Trust(trust), Needs::default())).unwrap();
+        cat
+    }
+
+    #[test]
+    fn petting_builds_trust_and_shoving_erodes_it() {
+        let tuning = MouseConfig::default();
+        let mut world = hecs::World::new();
+        let petted = cat(&mut world, Vec2::new(100.0, 100.0), Trust::NEUTRAL);
+        let across = cat(&mut world, Vec2::new(400.0, 100.0), Trust::NEUTRAL);
+
+        let mut cursor = CursorState::new();
+        let mouse = Vec2::new(110.0, 100.0);
+        cursor.update(mouse.x, mouse.y, 1.0);
+        for _ in 0..10 {
+            cursor.update(mouse.x, mouse.y, 1.0);
+            update(&mut world, mouse, &cursor, 1.0, &tuning);
+        }
+        let trust = |world: &hecs::World, e| world.get::<&Trust>(e).unwrap().0;
+        assert!((trust(&world, petted) - (Trust::NEUTRAL + 10.0 * PET_TRUST)).abs() < 1e-4);
+        assert_eq!(trust(&world, across), Trust::NEUTRAL, "out of reach");
+        assert!(world.get::<&Needs>(petted).unwrap().comfort > Needs::default().comfort);
+
+        // A fast swipe shoves both, the nearer one harder.
+        let before = [trust(&world, petted), trust(&world, across)];
+        cursor.update(mouse.x + 300.0, mouse.y, 1.0);
+        update(&mut world, mouse, &cursor, 1.0, &tuning);
+        let lost = [
+            before[0] - trust(&world, petted),
+            before[1] - trust(&world, across),
+        ];
+        assert!(lost[0] > lost[1] && lost[1] > 0.0, "{lost:?}");
+    }
+
+    #[test]
+    fn trust_tempers_skittishness() {
+        assert_eq!(wariness(0.6, Trust::NEUTRAL), 0.6);
+        assert!(wariness(0.6, 1.0) < 0.2);
+        assert_eq!(wariness(0.9, 0.0), 1.0);
+    }
+}
@@ -1,22 +1,284 @@
-use crate::ecs::components::{BehaviorState, CatState, Position, Velocity};
+use glam::Vec2;
 
-/// Platform-agnostic desktop window rectangle.
-#[derive(Debug, Clone, Copy)]
+use crate::ecs::components::{
+    Airborne, BehaviorState, CatState, Personality, Position, PrevPosition, SpawnAnimation,
+    Stacked, Velocity,
+};
+use crate::ecs::systems::behavior;
+
+/// Platform-agnostic desktop window. `id` is the OS handle, which stays the
+/// same while the window lives, so refreshes can be told apart from each
+/// other.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct DesktopWindow {
+    pub id: u64,
     pub left: f32,
     pub top: f32,
     pub right: f32,
     pub bottom: f32,
+    /// Stacking order, 0 for the topmost window.
+    pub z: u32,
+    pub state: WindowState,
+    pub title: String,
+    /// Name of the program that owns the window (e.g. `firefox`,
+    /// `notepad.exe`), or empty if the OS wouldn't say.
+    pub process: String,
+}
+
+/// What the window manager says about a window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowState {
+    /// Has keyboard focus.
+    pub focused: bool,
+    pub minimized: bool,
+    pub maximized: bool,
+    /// Covers its whole monitor, taskbar included.
+    pub fullscreen: bool,
+    /// Palette, toolbar or other helper window rather than a main window.
+    pub tool: bool,
+    /// The desktop shell's own window (wallpaper, taskbar) rather than an
+    /// application's.
+    pub shell: bool,
+}
+
+impl WindowState {
+    /// Packed into a byte, for recordings.
+    pub fn bits(self) -> u8 {
+        [
+            self.focused,
+            self.minimized,
+            self.maximized,
+            self.fullscreen,
+            self.tool,
+            self.shell,
+        ]
+        .iter()
+        .enumerate()
+        .fold(0, |bits, (i, &on)| bits | (on as u8) << i)
+    }
+
+    /// Inverse of [`Self::bits`].
+    pub fn from_bits(bits: u8) -> Self {
+        let on = |i: u8| bits & (1 << i) != 0;
+        Self {
+            focused: on(0),
+            minimized: on(1),
+            maximized: on(2),
+            fullscreen: on(3),
+            tool: on(4),
+            shell: on(5),
+        }
+    }
 }
 
 /// Distance from titlebar top where cats can snap onto it.
 const SNAP_DIST: f32 = 25.0;
 /// Per-tick chance an eligible cat near a titlebar will perch.
 const PERCH_CHANCE: f32 = 0.015;
+/// Perching is this much likelier on a maximized window's titlebar.
+const MAXIMIZED_PERCH_BOOST: f32 = 4.0;
 /// Walk speed along a titlebar.
 const TITLEBAR_WALK_SPEED: f32 = 25.0;
 
-/// Detect cats near window titlebars and snap them onto platforms.
+/// A cat this far (or less) above a window's top edge is standing on it.
+const PERCH_HEIGHT: f32 = 35.0;
+/// A perched cat rides out a move this long; farther and it's sure to fall.
+const TUMBLE_DIST: f32 = 400.0;
+/// Cats this close to a newly opened window can notice it.
+const NOTICE_RADIUS: f32 = 600.0;
+/// Chance a fully curious cat goes to see a new window.
+const NOTICE_CHANCE: f32 = 0.6;
+/// Speed of a cat going over to a new window.
+const INVESTIGATE_SPEED: f32 = 60.0;
+
+impl DesktopWindow {
+    /// Whether cats and toys treat this window as something solid to stand
+    /// on and walk around. Minimized, tool and shell windows don't count.
+    pub fn is_platform(&self) -> bool {
+        !self.state.minimized && !self.state.tool && !self.state.shell
+    }
+
+    /// Whether `other` covers exactly the same rectangle.
+    pub fn same_rect(&self, other: &DesktopWindow) -> bool {
+        (self.left, self.top, self.right, self.bottom)
+            == (other.left, other.top, other.right, other.bottom)
+    }
+
+    /// Whether a cat at `pos` is standing on top of this window.
+    pub fn carries(&self, pos: Vec2) -> bool {
+        self.is_platform()
+            && (self.left - 10.0..=self.right + 10.0).contains(&pos.x)
+            && (0.0..=PERCH_HEIGHT).contains(&(self.top - pos.y))
+    }
+}
+
+/// How a window changed between two refreshes of the window list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowChange {
+    Opened(DesktopWindow),
+    Closed(DesktopWindow),
+    /// Moved or resized.
+    Moved {
+        from: DesktopWindow,
+        to: DesktopWindow,
+    },
+    /// Same place, but restacked, retitled or changed state (e.g. focused
+    /// or minimized).
+    Changed {
+        from: DesktopWindow,
+        to: DesktopWindow,
+    },
+}
+
+/// Append what changed going from window list `old` to `new`, matching
+/// windows up by id: new ids opened, vanished ids closed, ids with a new
+/// rectangle moved and ids that differ otherwise changed.
+pub fn diff(old: &[DesktopWindow], new: &[DesktopWindow], out: &mut Vec<WindowChange>) {
+    for from in old {
+        match new.iter().find(|w| w.id == from.id) {
+            None => out.push(WindowChange::Closed(from.clone())),
+            Some(to) if !to.same_rect(from) => out.push(WindowChange::Moved {
+                from: from.clone(),
+                to: to.clone(),
+            }),
+            Some(to) if to != from => out.push(WindowChange::Changed {
+                from: from.clone(),
+                to: to.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for to in new {
+        if !old.iter().any(|w| w.id == to.id) {
+            out.push(WindowChange::Opened(to.clone()));
+        }
+    }
+}
+
+/// The window list as of the last change, for turning refreshes into
+/// [`WindowChange`]s.
+#[derive(Debug, Default)]
+pub struct WindowTracker {
+    known: Vec<DesktopWindow>,
+    /// Generation `known` was taken from, `None` before the first refresh.
+    generation: Option<u64>,
+}
+
+impl WindowTracker {
+    /// Changes since the last call. `generation` goes up whenever the
+    /// window list changes, so nothing is compared while it stays put. The
+    /// first call only takes note of the windows already open, so they
+    /// don't all count as new.
+    pub fn refresh(
+        &mut self,
+        windows: &[DesktopWindow],
+        generation: u64,
+        out: &mut Vec<WindowChange>,
+    ) {
+        out.clear();
+        match self.generation {
+            Some(known) if known == generation => return,
+            Some(_) => diff(&self.known, windows, out),
+            None => {}
+        }
+        self.generation = Some(generation);
+        self.known.clear();
+        self.known.extend_from_slice(windows);
+    }
+}
+
+/// React to windows changing under and around the cats. Cats on a window
+/// that moved ride along if it didn't go far, and otherwise tumble off
+/// (startled, and falling in gravity mode); cats on a window that closed
+/// are startled. A new window draws curious cats nearby over to look,
+/// unless it's only a tool window.
+pub fn react(
+    world: &mut hecs::World,
+    changes: &[WindowChange],
+    gravity: bool,
+    rng: &mut fastrand::Rng,
+) {
+    if changes.is_empty() {
+        return;
+    }
+
+    let mut tumbled = Vec::new();
+    for (entity, (pos, prev, vel, state, personality)) in world
+        .query_mut::<(
+            &mut Position,
+            &mut PrevPosition,
+            &mut Velocity,
+            &mut CatState,
+            &Personality,
+        )>()
+        .without::<&SpawnAnimation>()
+        .without::<&Stacked>()
+        .without::<&Airborne>()
+    {
+        for change in changes {
+            match change {
+                WindowChange::Moved { from, to } if from.carries(pos.0) => {
+                    let shift = Vec2::new(to.left - from.left, to.top - from.top);
+                    let slip = shift.length() / TUMBLE_DIST;
+                    if to.carries(pos.0 + shift) && rng.f32() >= slip {
+                        // Hold on and ride it
+                        pos.0 += shift;
+                        prev.0 += shift;
+                    } else {
+                        behavior::trigger_startle(state, vel, rng);
+                        tumbled.push((entity, vel.0));
+                    }
+                    break;
+                }
+                WindowChange::Closed(w) if w.carries(pos.0) => {
+                    behavior::trigger_startle(state, vel, rng);
+                    tumbled.push((entity, vel.0));
+                    break;
+                }
+                // Minimized from under the cat; focus and stacking don't
+                // shake anyone off
+                WindowChange::Changed { from, to } if from.carries(pos.0) && !to.is_platform() => {
+                    behavior::trigger_startle(state, vel, rng);
+                    tumbled.push((entity, vel.0));
+                    break;
+                }
+                WindowChange::Opened(w) if w.is_platform() => {
+                    let target = Vec2::new(
+                        w.left + (w.right - w.left) * rng.f32(),
+                        w.top - PERCH_HEIGHT * 0.5,
+                    );
+                    let to_window = target - pos.0;
+                    let distance = to_window.length();
+                    if distance > NOTICE_RADIUS
+                        || !matches!(
+                            state.state,
+                            BehaviorState::Idle | BehaviorState::Walking | BehaviorState::Grooming
+                        )
+                        || rng.f32() >= personality.curiosity * NOTICE_CHANCE
+                    {
+                        continue;
+                    }
+                    state.state = BehaviorState::Walking;
+                    state.timer = distance / INVESTIGATE_SPEED;
+                    vel.0 = to_window.normalize_or_zero() * INVESTIGATE_SPEED;
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Nothing holds them up any more
+    if gravity {
+        for (entity, vel) in tumbled {
+            let _ = world.insert_one(entity, Airborne { vel });
+        }
+    }
+}
+
+/// Detect cats near window titlebars and snap them onto platforms. Cats keep
+/// off the focused window, where they'd be in the way, and crowd onto
+/// maximized windows' titlebars.
 pub fn update(
     world: &mut hecs::World,
     platforms: &[DesktopWindow],
@@ -39,11 +301,14 @@ pub fn update(
 
         // Find nearest platform top edge
         for plat in platforms {
+            if !plat.is_platform() || plat.state.focused {
+                continue;
+            }
             let top_y = plat.top;
             let dy = pos.0.y - top_y;
 
             // Cat must be near the top edge (slightly above or below)
-            if dy < -10.0 || dy > SNAP_DIST {
+            if !(-10.0..=SNAP_DIST).contains(&dy) {
                 continue;
             }
             // Cat must be within the horizontal bounds
@@ -52,7 +317,12 @@ pub fn update(
             }
 
             // Chance to snap to this titlebar
-            if rng.f32() > PERCH_CHANCE {
+            let boost = if plat.state.maximized {
+                MAXIMIZED_PERCH_BOOST
+            } else {
+                1.0
+            };
+            if rng.f32() > PERCH_CHANCE * boost {
                 continue;
             }
 
@@ -82,3 +352,159 @@ pub fn update(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::TestCat;
+
+    fn window(id: u64, left: f32, top: f32) -> DesktopWindow {
+        DesktopWindow {
+            id,
+            left,
+            top,
+            right: left + 400.0,
+            bottom: top + 300.0,
+            ..DesktopWindow::default()
+        }
+    }
+
+    fn cat(world: &mut hecs::World, pos: Vec2) -> hecs::Entity {
+        TestCat::at(pos).curiosity(0.0).spawn(world)
+    }
+
+    #[test]
+    fn refreshes_diff_into_opens_closes_and_moves() {
+        let a = window(1, 0.0, 100.0);
+        let b = window(2, 500.0, 100.0);
+        let c = window(3, 0.0, 0.0);
+        let mut tracker = WindowTracker::default();
+        let mut changes = Vec::new();
+
+        // Windows already open at startup aren't news
+        tracker.refresh(&[a.clone(), b.clone()], 0, &mut changes);
+        assert!(changes.is_empty());
+
+        let moved = window(2, 520.0, 90.0);
+        let now = [c.clone(), moved.clone()];
+        tracker.refresh(&now, 1, &mut changes);
+        assert_eq!(
+            changes,
+            vec![
+                WindowChange::Closed(a),
+                WindowChange::Moved {
+                    from: b,
+                    to: moved.clone()
+                },
+                WindowChange::Opened(c.clone()),
+            ]
+        );
+        tracker.refresh(&now, 1, &mut changes);
+        assert!(changes.is_empty());
+
+        // Focus moving over doesn't move anything
+        let focused = DesktopWindow {
+            state: WindowState {
+                focused: true,
+                ..WindowState::default()
+            },
+            ..moved.clone()
+        };
+        tracker.refresh(&[c, focused.clone()], 2, &mut changes);
+        assert_eq!(
+            changes,
+            vec![WindowChange::Changed {
+                from: moved,
+                to: focused
+            }]
+        );
+    }
+
+    #[test]
+    fn perched_cats_ride_nudged_windows_and_scatter_when_they_close() {
+        let mut world = hecs::World::new();
+        let mut rng = fastrand::Rng::with_seed(3);
+        let from = window(1, 100.0, 300.0);
+        let on_top = cat(&mut world, Vec2::new(200.0, 280.0));
+        let below = cat(&mut world, Vec2::new(200.0, 500.0));
+
+        let to = window(1, 110.0, 295.0);
+        let moved = WindowChange::Moved {
+            from,
+            to: to.clone(),
+        };
+        react(&mut world, &[moved], true, &mut rng);
+        assert_eq!(
+            world.get::<&Position>(on_top).unwrap().0,
+            Vec2::new(210.0, 275.0)
+        );
+        assert_eq!(
+            world.get::<&Position>(below).unwrap().0,
+            Vec2::new(200.0, 500.0)
+        );
+
+        react(&mut world, &[WindowChange::Closed(to)], true, &mut rng);
+        let state = world.get::<&CatState>(on_top).unwrap().state;
+        assert_eq!(state, BehaviorState::Startled);
+        assert!(
+            world.get::<&Airborne>(on_top).is_ok(),
+            "falls in gravity mode"
+        );
+        assert_eq!(
+            world.get::<&CatState>(below).unwrap().state,
+            BehaviorState::Idle
+        );
+    }
+
+    #[test]
+    fn focusing_a_window_leaves_perched_cats_be_but_minimizing_it_doesnt() {
+        let mut world = hecs::World::new();
+        let mut rng = fastrand::Rng::with_seed(4);
+        let plain = window(1, 100.0, 300.0);
+        let on_top = cat(&mut world, Vec2::new(200.0, 280.0));
+
+        let mut focused = plain.clone();
+        focused.state.focused = true;
+        let focus = WindowChange::Changed {
+            from: plain,
+            to: focused.clone(),
+        };
+        react(&mut world, &[focus], true, &mut rng);
+        assert_eq!(
+            world.get::<&CatState>(on_top).unwrap().state,
+            BehaviorState::Idle
+        );
+
+        let mut minimized = focused.clone();
+        minimized.state.minimized = true;
+        let minimize = WindowChange::Changed {
+            from: focused,
+            to: minimized,
+        };
+        react(&mut world, &[minimize], true, &mut rng);
+        assert_eq!(
+            world.get::<&CatState>(on_top).unwrap().state,
+            BehaviorState::Startled
+        );
+        assert!(world.get::<&Airborne>(on_top).is_ok());
+    }
+
+    #[test]
+    fn cats_perch_on_maximized_windows_but_not_the_focused_one() {
+        let mut world = hecs::World::new();
+        let mut rng = fastrand::Rng::with_seed(5);
+        let mut focused = window(1, 0.0, 300.0);
+        focused.state.focused = true;
+        let mut maximized = window(2, 600.0, 0.0);
+        maximized.state.maximized = true;
+        let near_focused = cat(&mut world, Vec2::new(200.0, 310.0));
+        let near_maximized = cat(&mut world, Vec2::new(800.0, 10.0));
+
+        for _ in 0..600 {
+            update(&mut world, &[focused.clone(), maximized.clone()], &mut rng);
+        }
+        let perched = |cat| world.get::<&Position>(cat).unwrap().0.y;
+        assert_eq!(perched(near_focused), 310.0);
+        assert_eq!(perched(near_maximized), -8.0);
+    }
+}
@@ -1,5 +1,5 @@
-/// Cursor heatmap — tracks where the mouse spends time.
-/// Used for visual overlay and movement avoidance.
+//! Cursor heatmap — tracks where the mouse spends time.
+//! Used for visual overlay and movement avoidance.
 
 /// Grid resolution (cells per axis).
 const GRID_SIZE: usize = 64;
@@ -0,0 +1,521 @@
+//! Input actions — named things the user can ask for (quit, cycle mode,
+//! drop a box, ...) bound to key chords from the `[hotkeys]` config.
+//!
+//! The overlay is click-through and never has focus, so keys are polled
+//! system-wide through a [`Keyboard`]. [`ActionState::poll`] turns those
+//! polls into one firing per press: a chord fires on the frame its key goes
+//! down while exactly its modifiers are held, and not again until the key
+//! is released. Holding Shift while typing `b` therefore never triggers a
+//! plain `B` binding, and `Ctrl+Shift+B` never triggers `Shift+B`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::config::HotkeysConfig;
+
+macro_rules! keys {
+    ($($key:ident => $name:literal),* $(,)?) => {
+        /// A physical key, named after its US-layout legend.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Key {
+            $($key),*
+        }
+
+        impl Key {
+            /// Every key, in declaration order ([`Key::index`] order).
+            pub const ALL: &'static [Key] = &[$(Key::$key),*];
+
+            /// Name used in config files, e.g. `"F12"` or `"B"`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Key::$key => $name),*
+                }
+            }
+        }
+    };
+}
+
+keys! {
+    Escape => "Escape", Space => "Space", Tab => "Tab", Enter => "Enter",
+    Backspace => "Backspace", Insert => "Insert", Delete => "Delete",
+    Home => "Home", End => "End", PageUp => "PageUp", PageDown => "PageDown",
+    Up => "Up", Down => "Down", Left => "Left", Right => "Right",
+    Pause => "Pause", ScrollLock => "ScrollLock",
+    F1 => "F1", F2 => "F2", F3 => "F3", F4 => "F4", F5 => "F5", F6 => "F6",
+    F7 => "F7", F8 => "F8", F9 => "F9", F10 => "F10", F11 => "F11", F12 => "F12",
+    A => "A", B => "B", C => "C", D => "D", E => "E", F => "F", G => "G",
+    H => "H", I => "I", J => "J", K => "K", L => "L", M => "M", N => "N",
+    O => "O", P => "P", Q => "Q", R => "R", S => "S", T => "T", U => "U",
+    V => "V", W => "W", X => "X", Y => "Y", Z => "Z",
+    Digit0 => "0", Digit1 => "1", Digit2 => "2", Digit3 => "3", Digit4 => "4",
+    Digit5 => "5", Digit6 => "6", Digit7 => "7", Digit8 => "8", Digit9 => "9",
+    // Modifiers (either side)
+    Ctrl => "Ctrl", Shift => "Shift", Alt => "Alt", Super => "Super",
+}
+
+impl Key {
+    /// Position in [`Key::ALL`], for per-key lookup tables.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Look a key up by name, ignoring case. Accepts a few common aliases.
+    pub fn from_name(name: &str) -> Option<Key> {
+        let alias = match name.to_ascii_lowercase().as_str() {
+            "esc" => Some(Key::Escape),
+            "return" => Some(Key::Enter),
+            "del" => Some(Key::Delete),
+            "control" => Some(Key::Ctrl),
+            "win" | "meta" | "cmd" => Some(Key::Super),
+            _ => None,
+        };
+        alias.or_else(|| {
+            Key::ALL
+                .iter()
+                .copied()
+                .find(|k| k.name().eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// The modifier bit for Ctrl/Shift/Alt/Super, `None` for other keys.
+    fn modifier(self) -> Option<Modifiers> {
+        match self {
+            Key::Ctrl => Some(Modifiers::CTRL),
+            Key::Shift => Some(Modifiers::SHIFT),
+            Key::Alt => Some(Modifiers::ALT),
+            Key::Super => Some(Modifiers::SUPER),
+            _ => None,
+        }
+    }
+}
+
+/// Global keyboard state. Implemented by every platform backend.
+pub trait Keyboard {
+    /// Whether `key` is held right now.
+    fn key_down(&mut self, key: Key) -> bool;
+
+    /// Whether `key` was pressed since the last call for that key, so quick
+    /// taps between polls aren't lost.
+    fn key_pressed(&mut self, key: Key) -> bool;
+}
+
+/// Set of held modifier keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const CTRL: Modifiers = Modifiers(1);
+    pub const SHIFT: Modifiers = Modifiers(2);
+    pub const ALT: Modifiers = Modifiers(4);
+    pub const SUPER: Modifiers = Modifiers(8);
+
+    /// The modifier keys, in the order they're written in a chord.
+    const KEYS: [Key; 4] = [Key::Ctrl, Key::Shift, Key::Alt, Key::Super];
+
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Modifiers held right now.
+    fn held<K: Keyboard + ?Sized>(keys: &mut K) -> Modifiers {
+        Self::KEYS
+            .into_iter()
+            .filter(|&k| keys.key_down(k))
+            .filter_map(Key::modifier)
+            .fold(Modifiers::NONE, |a, b| a | b)
+    }
+}
+
+impl std::ops::BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Modifiers) -> Modifiers {
+        Modifiers(self.0 | rhs.0)
+    }
+}
+
+/// A key plus the exact set of modifiers that must be held with it,
+/// written `Ctrl+Shift+B` in config files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chord {
+    pub key: Key,
+    pub mods: Modifiers,
+}
+
+impl Chord {
+    /// `key` with no modifiers.
+    pub const fn new(key: Key) -> Self {
+        Self {
+            key,
+            mods: Modifiers::NONE,
+        }
+    }
+
+    pub const fn with(key: Key, mods: Modifiers) -> Self {
+        Self { key, mods }
+    }
+}
+
+/// Why a chord string didn't parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChordError(String);
+
+impl fmt::Display for ChordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ChordError {}
+
+impl FromStr for Chord {
+    type Err = ChordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let key_name = parts.pop().unwrap_or_default();
+        let lookup = |name: &str| {
+            Key::from_name(name).ok_or_else(|| ChordError(format!("unknown key {name:?} in {s:?}")))
+        };
+
+        let key = lookup(key_name)?;
+        if key.modifier().is_some() {
+            return Err(ChordError(format!("{s:?} has no key besides modifiers")));
+        }
+        let mut mods = Modifiers::NONE;
+        for name in parts {
+            mods = mods
+                | lookup(name)?
+                    .modifier()
+                    .ok_or_else(|| ChordError(format!("{name:?} in {s:?} is not a modifier")))?;
+        }
+        Ok(Chord { key, mods })
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for key in Modifiers::KEYS {
+            if key.modifier().is_some_and(|m| self.mods.contains(m)) {
+                write!(f, "{}+", key.name())?;
+            }
+        }
+        f.write_str(self.key.name())
+    }
+}
+
+impl Serialize for Chord {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Chord {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Something the user can trigger with a hotkey.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    ToggleDebug,
+    CycleMode,
+    TogglePause,
+    DoNotDisturb,
+    SpawnBox,
+    SpawnGlass,
+    ToggleWand,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::Quit,
+        Action::ToggleDebug,
+        Action::CycleMode,
+        Action::TogglePause,
+        Action::DoNotDisturb,
+        Action::SpawnBox,
+        Action::SpawnGlass,
+        Action::ToggleWand,
+    ];
+
+    /// Key of this action's binding list in the `[hotkeys]` config table.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::ToggleDebug => "toggle_debug",
+            Action::CycleMode => "cycle_mode",
+            Action::TogglePause => "toggle_pause",
+            Action::DoNotDisturb => "do_not_disturb",
+            Action::SpawnBox => "spawn_box",
+            Action::SpawnGlass => "spawn_glass",
+            Action::ToggleWand => "toggle_wand",
+        }
+    }
+
+    /// Toy actions drop things on the desktop or hand out the feather wand,
+    /// and can be switched off as a group (`hotkeys.toys = false`).
+    pub fn is_toy(self) -> bool {
+        matches!(
+            self,
+            Action::SpawnBox | Action::SpawnGlass | Action::ToggleWand
+        )
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A set of actions, e.g. the ones fired by one poll.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActionSet(u8);
+
+impl ActionSet {
+    pub fn contains(self, action: Action) -> bool {
+        self.0 & action.bit() != 0
+    }
+
+    pub fn insert(&mut self, action: Action) {
+        self.0 |= action.bit();
+    }
+
+    /// Remove `action`, returning whether it was in the set.
+    pub fn take(&mut self, action: Action) -> bool {
+        let had = self.contains(action);
+        self.0 &= !action.bit();
+        had
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Add every action in `other`.
+    pub fn extend(&mut self, other: ActionSet) {
+        self.0 |= other.0;
+    }
+}
+
+/// Edge detection for bound keys, shared by every action.
+#[derive(Debug, Clone)]
+pub struct ActionState {
+    /// Whether each key was down at the last poll (indexed by [`Key::index`]).
+    was_down: Vec<bool>,
+}
+
+impl Default for ActionState {
+    fn default() -> Self {
+        Self {
+            was_down: vec![false; Key::ALL.len()],
+        }
+    }
+}
+
+impl ActionState {
+    /// Poll every bound key once and return the actions whose chord was
+    /// pressed since the last poll. Call once per frame.
+    pub fn poll<K: Keyboard + ?Sized>(
+        &mut self,
+        hotkeys: &HotkeysConfig,
+        keys: &mut K,
+    ) -> ActionSet {
+        // A key edges when it's newly down, or was tapped and released
+        // between polls. Each key is read once even if several chords use it.
+        let mut edged = vec![false; Key::ALL.len()];
+        let mut polled = vec![false; Key::ALL.len()];
+        let mut mods = None;
+        let mut fired = ActionSet::default();
+
+        for action in Action::ALL {
+            if action.is_toy() && !hotkeys.toys {
+                continue;
+            }
+            for chord in hotkeys.bindings(action) {
+                let i = chord.key.index();
+                if !polled[i] {
+                    polled[i] = true;
+                    let pressed = keys.key_pressed(chord.key);
+                    let down = keys.key_down(chord.key);
+                    edged[i] = (pressed || down) && !self.was_down[i];
+                    self.was_down[i] = down;
+                }
+                if edged[i] && *mods.get_or_insert_with(|| Modifiers::held(keys)) == chord.mods {
+                    fired.insert(action);
+                }
+            }
+        }
+
+        // Keys nobody is bound to any more start fresh if rebound later.
+        for (was_down, polled) in self.was_down.iter_mut().zip(polled) {
+            *was_down &= polled;
+        }
+        fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Scripted key stream: held keys plus taps that land between polls.
+    #[derive(Default)]
+    struct Keys {
+        held: HashSet<Key>,
+        tapped: HashSet<Key>,
+    }
+
+    impl Keys {
+        fn press(&mut self, key: Key) {
+            self.held.insert(key);
+            self.tapped.insert(key);
+        }
+
+        fn release(&mut self, key: Key) {
+            self.held.remove(&key);
+        }
+    }
+
+    impl Keyboard for Keys {
+        fn key_down(&mut self, key: Key) -> bool {
+            self.held.contains(&key)
+        }
+
+        fn key_pressed(&mut self, key: Key) -> bool {
+            self.tapped.remove(&key)
+        }
+    }
+
+    #[test]
+    fn chords_parse_and_print() {
+        let chord: Chord = " ctrl + SHIFT+b".parse().expect("valid");
+        assert_eq!(
+            chord,
+            Chord::with(Key::B, Modifiers::CTRL | Modifiers::SHIFT)
+        );
+        assert_eq!(chord.to_string(), "Ctrl+Shift+B");
+        assert_eq!("esc".parse::<Chord>(), Ok(Chord::new(Key::Escape)));
+        assert_eq!(
+            "Win+0".parse::<Chord>().map(|c| c.to_string()),
+            Ok("Super+0".into())
+        );
+
+        for bad in ["", "Ctrl+Shift", "B+Ctrl", "Ctrl+Nope", "Hyper+B"] {
+            assert!(bad.parse::<Chord>().is_err(), "{bad:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn held_key_fires_once_per_press() {
+        let hotkeys = HotkeysConfig::default();
+        let mut state = ActionState::default();
+        let mut keys = Keys::default();
+
+        keys.press(Key::F11);
+        assert!(state.poll(&hotkeys, &mut keys).contains(Action::CycleMode));
+        // Auto-repeat sets the press latch again while held.
+        keys.tapped.insert(Key::F11);
+        assert!(state.poll(&hotkeys, &mut keys).is_empty());
+        assert!(state.poll(&hotkeys, &mut keys).is_empty());
+
+        keys.release(Key::F11);
+        assert!(state.poll(&hotkeys, &mut keys).is_empty());
+        keys.press(Key::F11);
+        assert!(state.poll(&hotkeys, &mut keys).contains(Action::CycleMode));
+    }
+
+    #[test]
+    fn quick_tap_between_polls_still_fires() {
+        let hotkeys = HotkeysConfig::default();
+        let mut state = ActionState::default();
+        let mut keys = Keys::default();
+
+        keys.tapped.insert(Key::F12);
+        assert!(state
+            .poll(&hotkeys, &mut keys)
+            .contains(Action::ToggleDebug));
+        assert!(state.poll(&hotkeys, &mut keys).is_empty());
+    }
+
+    #[test]
+    fn modifiers_must_match_exactly() {
+        let hotkeys = HotkeysConfig::default();
+        let mut state = ActionState::default();
+        let mut keys = Keys::default();
+
+        // Typing `b` in another app: no toy.
+        keys.press(Key::B);
+        assert!(state.poll(&hotkeys, &mut keys).is_empty());
+        keys.release(Key::B);
+        assert!(state.poll(&hotkeys, &mut keys).is_empty());
+
+        // Ctrl+Alt+Shift+B is a different chord.
+        keys.held.extend([Key::Ctrl, Key::Shift, Key::Alt]);
+        keys.press(Key::B);
+        assert!(state.poll(&hotkeys, &mut keys).is_empty());
+        keys.release(Key::B);
+        keys.release(Key::Alt);
+        assert!(state.poll(&hotkeys, &mut keys).is_empty());
+
+        keys.press(Key::B);
+        let fired = state.poll(&hotkeys, &mut keys);
+        assert!(fired.contains(Action::SpawnBox) && !fired.contains(Action::SpawnGlass));
+    }
+
+    #[test]
+    fn toy_hotkeys_can_be_disabled() {
+        let hotkeys = HotkeysConfig {
+            toys: false,
+            ..HotkeysConfig::default()
+        };
+        let mut state = ActionState::default();
+        let mut keys = Keys::default();
+
+        keys.held.extend([Key::Ctrl, Key::Shift]);
+        keys.press(Key::G);
+        assert!(state.poll(&hotkeys, &mut keys).is_empty());
+
+        keys.held.clear();
+        keys.press(Key::F12);
+        assert!(state
+            .poll(&hotkeys, &mut keys)
+            .contains(Action::ToggleDebug));
+    }
+
+    #[test]
+    fn rebinding_and_shared_keys() {
+        let hotkeys = HotkeysConfig {
+            toggle_pause: vec![Chord::new(Key::P), Chord::with(Key::F11, Modifiers::SHIFT)],
+            ..HotkeysConfig::default()
+        };
+        let mut state = ActionState::default();
+        let mut keys = Keys::default();
+
+        keys.held.insert(Key::Shift);
+        keys.press(Key::F11);
+        let fired = state.poll(&hotkeys, &mut keys);
+        assert!(fired.contains(Action::TogglePause) && !fired.contains(Action::CycleMode));
+
+        keys = Keys::default();
+        keys.press(Key::P);
+        assert!(state
+            .poll(&hotkeys, &mut keys)
+            .contains(Action::TogglePause));
+    }
+
+    #[test]
+    fn action_set_take_drains() {
+        let mut set = ActionSet::default();
+        set.insert(Action::SpawnBox);
+        assert!(set.take(Action::SpawnBox));
+        assert!(!set.take(Action::SpawnBox));
+        assert!(set.is_empty());
+    }
+}
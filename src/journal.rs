@@ -0,0 +1,219 @@
+//! Per-cat life journals: running totals of the notable things each cat has
+//! done plus a short log of the most recent ones.
+//!
+//! Systems call [`record`] once an event has actually happened (after their
+//! query loops, so the world isn't borrowed). The hover tooltip shows
+//! [`Journal::highlights`], and [`export_json`] dumps every cat's journal,
+//! keyed by its stable [`CatId`].
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+use crate::ecs::components::{Age, CatId, CatName};
+
+/// Recent events kept per cat; older ones survive only in the totals.
+pub const JOURNAL_LEN: usize = 16;
+
+/// Something worth remembering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalEvent {
+    WonFight = 0,
+    LostFight = 1,
+    DeliveredGift = 2,
+    AteTreat = 3,
+    ClimbedTower = 4,
+    JoinedPile = 5,
+    Zoomies = 6,
+    CaughtPrey = 7,
+}
+
+impl JournalEvent {
+    /// Inverse of `as u8`, for save files.
+    pub fn from_u8(v: u8) -> Option<Self> {
+        Some(match v {
+            0 => Self::WonFight,
+            1 => Self::LostFight,
+            2 => Self::DeliveredGift,
+            3 => Self::AteTreat,
+            4 => Self::ClimbedTower,
+            5 => Self::JoinedPile,
+            6 => Self::Zoomies,
+            7 => Self::CaughtPrey,
+            _ => return None,
+        })
+    }
+}
+
+/// One logged event, stamped with the cat's age in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct JournalEntry {
+    pub age: f32,
+    pub event: JournalEvent,
+}
+
+/// A cat's life so far. Totals count every event; `recent` holds the last
+/// [`JOURNAL_LEN`], oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Journal {
+    pub fights_won: u32,
+    pub fights_lost: u32,
+    pub gifts_delivered: u32,
+    pub treats_eaten: u32,
+    pub towers_climbed: u32,
+    pub piles_joined: u32,
+    pub zoomies: u32,
+    pub prey_caught: u32,
+    pub recent: VecDeque<JournalEntry>,
+}
+
+impl Journal {
+    /// Count `event` and log it, dropping the oldest entry when full.
+    pub fn record(&mut self, age: f32, event: JournalEvent) {
+        *self.total_mut(event) += 1;
+        if self.recent.len() == JOURNAL_LEN {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(JournalEntry { age, event });
+    }
+
+    /// Running total for one kind of event.
+    pub fn total(&self, event: JournalEvent) -> u32 {
+        match event {
+            JournalEvent::WonFight => self.fights_won,
+            JournalEvent::LostFight => self.fights_lost,
+            JournalEvent::DeliveredGift => self.gifts_delivered,
+            JournalEvent::AteTreat => self.treats_eaten,
+            JournalEvent::ClimbedTower => self.towers_climbed,
+            JournalEvent::JoinedPile => self.piles_joined,
+            JournalEvent::Zoomies => self.zoomies,
+            JournalEvent::CaughtPrey => self.prey_caught,
+        }
+    }
+
+    fn total_mut(&mut self, event: JournalEvent) -> &mut u32 {
+        match event {
+            JournalEvent::WonFight => &mut self.fights_won,
+            JournalEvent::LostFight => &mut self.fights_lost,
+            JournalEvent::DeliveredGift => &mut self.gifts_delivered,
+            JournalEvent::AteTreat => &mut self.treats_eaten,
+            JournalEvent::ClimbedTower => &mut self.towers_climbed,
+            JournalEvent::JoinedPile => &mut self.piles_joined,
+            JournalEvent::Zoomies => &mut self.zoomies,
+            JournalEvent::CaughtPrey => &mut self.prey_caught,
+        }
+    }
+
+    /// Short tooltip lines for the totals that aren't zero, e.g. "3 fights won".
+    pub fn highlights(&self) -> Vec<String> {
+        const LABELS: [(JournalEvent, &str, &str); 8] = [
+            (JournalEvent::WonFight, "fight won", "fights won"),
+            (JournalEvent::LostFight, "fight lost", "fights lost"),
+            (
+                JournalEvent::DeliveredGift,
+                "gift delivered",
+                "gifts delivered",
+            ),
+            (JournalEvent::AteTreat, "treat eaten", "treats eaten"),
+            (
+                JournalEvent::ClimbedTower,
+                "tower climbed",
+                "towers climbed",
+            ),
+            (JournalEvent::JoinedPile, "pile joined", "piles joined"),
+            (
+                JournalEvent::Zoomies,
+                "case of the zoomies",
+                "cases of the zoomies",
+            ),
+            (JournalEvent::CaughtPrey, "prey caught", "prey caught"),
+        ];
+        LABELS
+            .iter()
+            .filter_map(|&(event, one, many)| match self.total(event) {
+                0 => None,
+                1 => Some(format!("1 {one}")),
+                n => Some(format!("{n} {many}")),
+            })
+            .collect()
+    }
+}
+
+/// Log `event` in `cat`'s journal, if it keeps one.
+pub fn record(world: &hecs::World, cat: hecs::Entity, event: JournalEvent) {
+    let Ok(mut journal) = world.get::<&mut Journal>(cat) else {
+        return;
+    };
+    let age = world.get::<&Age>(cat).map_or(0.0, |a| a.0);
+    journal.record(age, event);
+}
+
+#[derive(Serialize)]
+struct CatRecord<'a> {
+    id: u64,
+    name: &'a str,
+    age: f32,
+    journal: &'a Journal,
+}
+
+/// Every cat's journal as a pretty-printed JSON array, ordered by [`CatId`].
+pub fn export_json(world: &hecs::World) -> String {
+    let mut query = world.query::<(&CatId, &Journal, Option<&CatName>, Option<&Age>)>();
+    let mut cats: Vec<CatRecord> = query
+        .iter()
+        .map(|(_, (id, journal, name, age))| CatRecord {
+            id: id.0,
+            name: name.map_or("", |n| n.0.as_str()),
+            age: age.map_or(0.0, |a| a.0),
+            journal,
+        })
+        .collect();
+    cats.sort_by_key(|c| c.id);
+    serde_json::to_string_pretty(&cats).expect("journals are plain data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_totals_past_the_log_limit() {
+        let mut journal = Journal::default();
+        for i in 0..JOURNAL_LEN + 4 {
+            journal.record(i as f32, JournalEvent::Zoomies);
+        }
+        journal.record(99.0, JournalEvent::WonFight);
+
+        assert_eq!(journal.zoomies as usize, JOURNAL_LEN + 4);
+        assert_eq!(journal.recent.len(), JOURNAL_LEN);
+        assert_eq!(journal.recent.front().unwrap().age, 5.0);
+        assert_eq!(journal.recent.back().unwrap().event, JournalEvent::WonFight);
+        assert_eq!(
+            journal.highlights(),
+            ["1 fight won", "20 cases of the zoomies"]
+        );
+    }
+
+    #[test]
+    fn exports_json_by_id() {
+        let mut world = hecs::World::new();
+        let late = world.spawn((
+            CatId(7),
+            Journal::default(),
+            CatName("Mochi".into()),
+            Age(2.5),
+        ));
+        world.spawn((CatId(3), Journal::default()));
+        record(&world, late, JournalEvent::AteTreat);
+
+        let json: serde_json::Value = serde_json::from_str(&export_json(&world)).unwrap();
+        let cats = json.as_array().unwrap();
+        assert_eq!(cats.len(), 2);
+        assert_eq!(cats[0]["id"], 3);
+        assert_eq!(cats[1]["name"], "Mochi");
+        assert_eq!(cats[1]["journal"]["treats_eaten"], 1);
+        assert_eq!(cats[1]["journal"]["recent"][0]["event"], "ate_treat");
+        assert_eq!(cats[1]["journal"]["recent"][0]["age"], 2.5);
+    }
+}
@@ -0,0 +1,30 @@
+//! PetToy colony simulation.
+//!
+//! Everything that decides what the cats do lives here, free of winit, wgpu
+//! and Win32. [`sim::Simulation`] owns the colony and advances it one fixed
+//! tick at a time from an explicit [`sim::SimInput`], so tests and tooling can
+//! run thousands of ticks with no monitor or GPU. The overlay binary is a thin
+//! shell that polls the platform, feeds input in, and renders the world.
+
+pub mod automation;
+pub mod cat;
+pub mod click;
+mod codec;
+pub mod config;
+pub mod daynight;
+pub mod desktop;
+pub mod ecs;
+pub mod heatmap;
+pub mod input;
+pub mod journal;
+pub mod mode;
+pub mod nav;
+pub mod presence;
+pub mod relations;
+pub mod replay;
+pub mod save;
+pub mod scent;
+pub mod sim;
+pub mod spatial;
+pub mod timer;
+pub mod toy;
@@ -1,24 +1,23 @@
 mod app;
-mod cat;
-mod click;
-mod daynight;
 mod debug;
-mod ecs;
-mod heatmap;
-mod mode;
 mod particles;
 mod platform;
 mod render;
-mod spatial;
-mod toy;
-mod tray;
 
 
 fn main() {
     env_logger::init();
     log::info!("PetToy starting up");
 
-    if let Err(e) = app::run() {
+    let opts = match app::Options::from_args() {
+        Ok(opts) => opts,
+        Err(e) => {
+            eprintln!("{e}\nusage: pettoy [--seed N] [--record PATH] [--save PATH | --no-save] [--config PATH]");
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(e) = app::run(opts) {
         log::error!("Fatal error: {e}");
         std::process::exit(1);
     }
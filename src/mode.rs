@@ -1,5 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AfkConfig, ModePreset, ModesConfig, PresetOverride};
+
 /// Application mode — determines how cats behave relative to user activity.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AppMode {
     Work,
     Play,
@@ -34,8 +39,6 @@ pub struct ModeState {
     pub mode: AppMode,
     /// Seconds since last user input (from GetLastInputInfo).
     pub idle_seconds: f64,
-    /// Whether to auto-transition to Zen on AFK.
-    pub auto_zen: bool,
     /// Edge affinity: 0.0 = uniform distribution, 1.0 = edges only.
     pub edge_affinity: f32,
     /// Multiplier on behavior energy (walk/run weights).
@@ -44,14 +47,25 @@ pub struct ModeState {
     pub chase_enabled: bool,
     /// Extra cats spawned from AFK escalation.
     pub bonus_cats_spawned: usize,
-    /// Max bonus cats from AFK.
-    pub bonus_cats_cap: usize,
+    /// Fractional bonus cats carried between ticks.
+    bonus_spawn_accumulator: f64,
+    /// Per-mode parameters (from config).
+    pub presets: ModesConfig,
+    /// Preset keys pinned by the mode rule in effect (see
+    /// [`crate::automation`]), on top of the mode's own preset.
+    pub overrides: PresetOverride,
+    /// AFK escalation thresholds (from config).
+    pub afk: AfkConfig,
     /// Mode before AFK auto-transition (to restore on return).
     prev_mode: Option<AppMode>,
     /// Whether AFK escalation is actively running.
     pub afk_active: bool,
-    /// F11 edge detection.
-    f11_was_down: bool,
+}
+
+impl Default for ModeState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ModeState {
@@ -59,73 +73,78 @@ impl ModeState {
         let mut s = Self {
             mode: AppMode::Play,
             idle_seconds: 0.0,
-            auto_zen: true,
             edge_affinity: 0.0,
             behavior_energy_scale: 1.0,
             chase_enabled: true,
             bonus_cats_spawned: 0,
-            bonus_cats_cap: 1000,
+            bonus_spawn_accumulator: 0.0,
+            presets: ModesConfig::default(),
+            overrides: PresetOverride::default(),
+            afk: AfkConfig::default(),
             prev_mode: None,
             afk_active: false,
-            f11_was_down: false,
         };
         s.apply_mode_preset();
         s
     }
 
+    /// Preset for a mode.
+    pub fn preset(&self, mode: AppMode) -> ModePreset {
+        match mode {
+            AppMode::Work => self.presets.work,
+            AppMode::Play => self.presets.play,
+            AppMode::Zen => self.presets.zen,
+            AppMode::Chaos => self.presets.chaos,
+        }
+    }
+
     /// Apply preset values for the current mode.
     fn apply_mode_preset(&mut self) {
-        match self.mode {
-            AppMode::Work => {
-                self.edge_affinity = 0.7;
-                self.behavior_energy_scale = 0.3;
-                self.chase_enabled = false;
-            }
-            AppMode::Play => {
-                self.edge_affinity = 0.0;
-                self.behavior_energy_scale = 1.0;
-                self.chase_enabled = true;
-            }
-            AppMode::Zen => {
-                self.edge_affinity = 0.0;
-                self.behavior_energy_scale = 1.5;
-                self.chase_enabled = true;
-            }
-            AppMode::Chaos => {
-                self.edge_affinity = 0.0;
-                self.behavior_energy_scale = 3.0;
-                self.chase_enabled = true;
-            }
-        }
+        let preset = self.overrides.over(self.preset(self.mode));
+        self.edge_affinity = preset.edge_affinity;
+        self.behavior_energy_scale = preset.energy_scale;
+        self.chase_enabled = preset.chase_enabled;
     }
 
-    /// Cycle to next mode (F11). Returns true if mode changed.
-    pub fn poll_f11(&mut self, f11_down: bool) -> bool {
-        if f11_down && !self.f11_was_down {
-            self.f11_was_down = true;
-            self.mode = self.mode.next();
+    /// Swap in new presets/thresholds (config reload). The current mode's
+    /// preset is re-applied unless AFK escalation is steering it.
+    pub fn set_tuning(&mut self, presets: ModesConfig, afk: AfkConfig) {
+        self.presets = presets;
+        self.afk = afk;
+        if !self.afk_active {
             self.apply_mode_preset();
-            // Cancel AFK if manually switching
-            self.afk_active = false;
-            self.prev_mode = None;
-            return true;
-        }
-        if !f11_down {
-            self.f11_was_down = false;
         }
-        false
+    }
+
+    /// Cycle to the next mode (cycle-mode hotkey).
+    pub fn cycle(&mut self) {
+        self.mode = self.mode.next();
+        self.overrides = PresetOverride::default();
+        self.apply_mode_preset();
+        // Cancel AFK if manually switching
+        self.afk_active = false;
+        self.prev_mode = None;
     }
 
     /// Set mode directly (from debug UI).
     pub fn set_mode(&mut self, mode: AppMode) {
         if self.mode != mode {
             self.mode = mode;
+            self.overrides = PresetOverride::default();
             self.apply_mode_preset();
             self.afk_active = false;
             self.prev_mode = None;
         }
     }
 
+    /// Switch to `mode` with `overrides` on top of its preset, on behalf of
+    /// a mode rule. Leaves AFK escalation alone.
+    pub fn steer(&mut self, mode: AppMode, overrides: PresetOverride) {
+        self.mode = mode;
+        self.overrides = overrides;
+        self.apply_mode_preset();
+    }
+
     /// Update AFK escalation logic. Call once per frame.
     /// Returns number of bonus cats to spawn this frame (0 usually).
     pub fn update_afk(&mut self, idle_seconds: f64, dt: f64) -> AtkAction {
@@ -137,29 +156,30 @@ impl ModeState {
             return self.on_return_from_afk();
         }
 
-        if !self.auto_zen {
+        if !self.afk.enabled {
             return AtkAction::None;
         }
 
         // AFK escalation thresholds
-        if idle_seconds < 30.0 {
+        let afk = self.afk;
+        if idle_seconds < afk.drift_after {
             return AtkAction::None;
         }
 
-        if idle_seconds < 120.0 {
-            // 30s-2min: gradually reduce edge affinity (cats drift center)
-            let t = ((idle_seconds - 30.0) / 90.0) as f32; // 0..1 over 90s
-            self.edge_affinity = self.edge_affinity * (1.0 - t * 0.5);
+        if idle_seconds < afk.energize_after {
+            // 30s-2min (default): gradually reduce edge affinity (cats drift center)
+            let t = ((idle_seconds - afk.drift_after) / (afk.energize_after - afk.drift_after)) as f32;
+            self.edge_affinity *= 1.0 - t * 0.5;
             return AtkAction::None;
         }
 
-        if idle_seconds < 300.0 {
+        if idle_seconds < afk.zen_after {
             // 2-5min: increase energy
             if !self.afk_active {
                 self.afk_active = true;
                 self.prev_mode = Some(self.mode);
             }
-            self.behavior_energy_scale = 1.5;
+            self.behavior_energy_scale = afk.energized_scale;
             self.edge_affinity = 0.0;
             return AtkAction::None;
         }
@@ -172,11 +192,15 @@ impl ModeState {
         self.mode = AppMode::Zen;
         self.apply_mode_preset();
 
-        // Spawn ~50 cats/min = ~0.83/s
-        if self.bonus_cats_spawned < self.bonus_cats_cap {
-            let spawn_rate = 50.0 / 60.0; // cats per second
-            let to_spawn = (spawn_rate * dt) as usize;
+        // Spawn ~50 cats/min = ~0.83/s. Accumulate fractions: at 60 Hz a
+        // single tick is always well under one cat.
+        if self.bonus_cats_spawned < afk.bonus_cats_cap {
+            let spawn_rate = afk.bonus_cats_per_minute / 60.0; // cats per second
+            self.bonus_spawn_accumulator += spawn_rate * dt;
+            let to_spawn = (self.bonus_spawn_accumulator as usize)
+                .min(afk.bonus_cats_cap - self.bonus_cats_spawned);
             if to_spawn > 0 {
+                self.bonus_spawn_accumulator -= to_spawn as f64;
                 self.bonus_cats_spawned += to_spawn;
                 return AtkAction::SpawnCats(to_spawn);
             }
@@ -189,6 +213,7 @@ impl ModeState {
         self.afk_active = false;
         let despawn = self.bonus_cats_spawned;
         self.bonus_cats_spawned = 0;
+        self.bonus_spawn_accumulator = 0.0;
 
         // Restore previous mode
         if let Some(prev) = self.prev_mode.take() {
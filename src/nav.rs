@@ -0,0 +1,423 @@
+//! Navigation for goal-seeking cats: a coarse cost grid over the desktop
+//! and flow fields toward goals.
+//!
+//! The grid is rebuilt a couple of times a second from the desktop windows
+//! (costly to cross), the config's `[navigation]` avoid zones (impassable),
+//! the gaps between monitors and the cursor heatmap. Each goal cell gets one
+//! flow field — every cell's cost to reach it, by Dijkstra — shared by every
+//! cat heading there, so a crowd converging on one treat costs a single
+//! search. Systems call [`Navigator::steer`] where they used to aim straight
+//! at the goal.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use glam::Vec2;
+
+use crate::config::NavigationConfig;
+use crate::desktop::Desktop;
+use crate::ecs::systems::window_aware::DesktopWindow;
+use crate::heatmap::Heatmap;
+
+/// Side of a navigation cell in pixels.
+pub const CELL_SIZE: f32 = 32.0;
+/// Seconds between grid rebuilds.
+const REBUILD_INTERVAL: f32 = 0.5;
+/// Flow fields kept at once; the least recently used goes first.
+const MAX_FIELDS: usize = 16;
+/// Cells ahead along the flow a cat aims at, which smooths out the grid's
+/// 45° steps.
+const LOOKAHEAD: usize = 3;
+/// Cost of a cell off every monitor: cats leap small gaps, reluctantly.
+const GAP_COST: f32 = 8.0;
+
+const NEIGHBORS: [(i32, i32); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+/// Per-cell cost of entering, row-major; `f32::INFINITY` is impassable.
+#[derive(Debug, Clone)]
+pub struct NavGrid {
+    pub cols: usize,
+    pub rows: usize,
+    pub cost: Vec<f32>,
+}
+
+impl NavGrid {
+    /// Open ground everywhere on `desktop`.
+    pub fn open(desktop: &Desktop) -> Self {
+        let size = desktop.size();
+        let cols = ((size.x / CELL_SIZE).ceil() as usize).max(1);
+        let rows = ((size.y / CELL_SIZE).ceil() as usize).max(1);
+        Self {
+            cols,
+            rows,
+            cost: vec![1.0; cols * rows],
+        }
+    }
+
+    /// Costs for the desktop as it is now.
+    pub fn build(
+        desktop: &Desktop,
+        windows: &[DesktopWindow],
+        heatmap: &Heatmap,
+        config: &NavigationConfig,
+    ) -> Self {
+        let mut grid = Self::open(desktop);
+        let zones: Vec<(Vec2, Vec2)> = config
+            .avoid
+            .iter()
+            .map(|z| {
+                let min = desktop.to_colony(Vec2::new(z.x, z.y));
+                (min, min + Vec2::new(z.w, z.h))
+            })
+            .collect();
+
+        for idx in 0..grid.cost.len() {
+            let c = grid.center(idx);
+            if zones
+                .iter()
+                .any(|(min, max)| c.cmpge(*min).all() && c.cmplt(*max).all())
+            {
+                grid.cost[idx] = f32::INFINITY;
+                continue;
+            }
+            let mut cost = if desktop.monitor_at(c).is_some() {
+                1.0
+            } else {
+                GAP_COST
+            };
+            if windows
+                .iter()
+                .filter(|w| w.is_platform())
+                .any(|w| c.x >= w.left && c.x < w.right && c.y >= w.top && c.y < w.bottom)
+            {
+                cost += config.window_cost;
+            }
+            cost += heatmap.sample(c.x, c.y) * config.heat_cost;
+            grid.cost[idx] = cost;
+        }
+        grid
+    }
+
+    /// Cell containing `pos`, if it's on the grid.
+    pub fn cell_of(&self, pos: Vec2) -> Option<usize> {
+        if pos.x < 0.0 || pos.y < 0.0 {
+            return None;
+        }
+        let cx = (pos.x / CELL_SIZE) as usize;
+        let cy = (pos.y / CELL_SIZE) as usize;
+        (cx < self.cols && cy < self.rows).then_some(cy * self.cols + cx)
+    }
+
+    /// Center of cell `idx` in colony coordinates.
+    pub fn center(&self, idx: usize) -> Vec2 {
+        let (cx, cy) = (idx % self.cols, idx / self.cols);
+        Vec2::new(cx as f32 + 0.5, cy as f32 + 0.5) * CELL_SIZE
+    }
+
+    /// Neighbors of `idx` a cat can step to, with the step length in cells.
+    /// Diagonals can't cut the corner of an impassable cell.
+    fn neighbors(&self, idx: usize) -> impl Iterator<Item = (usize, f32)> + '_ {
+        let (cx, cy) = ((idx % self.cols) as i32, (idx / self.cols) as i32);
+        let at = move |x: i32, y: i32| {
+            (x >= 0 && y >= 0 && (x as usize) < self.cols && (y as usize) < self.rows)
+                .then(|| y as usize * self.cols + x as usize)
+        };
+        NEIGHBORS.iter().filter_map(move |&(dx, dy)| {
+            let n = at(cx + dx, cy + dy)?;
+            if dx != 0 && dy != 0 {
+                let side_a = at(cx + dx, cy)?;
+                let side_b = at(cx, cy + dy)?;
+                if self.cost[side_a].is_infinite() || self.cost[side_b].is_infinite() {
+                    return None;
+                }
+                Some((n, std::f32::consts::SQRT_2))
+            } else {
+                Some((n, 1.0))
+            }
+        })
+    }
+}
+
+/// Every cell's cost to reach one goal cell.
+struct FlowField {
+    goal: usize,
+    dist: Vec<f32>,
+    last_used: u64,
+}
+
+/// Min-heap entry for Dijkstra.
+#[derive(PartialEq)]
+struct Frontier(f32, usize);
+
+impl Eq for Frontier {}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0).then(other.1.cmp(&self.1))
+    }
+}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FlowField {
+    fn build(grid: &NavGrid, goal: usize) -> Self {
+        let mut dist = vec![f32::INFINITY; grid.cost.len()];
+        let mut open = BinaryHeap::new();
+        dist[goal] = 0.0;
+        open.push(Frontier(0.0, goal));
+        while let Some(Frontier(d, cell)) = open.pop() {
+            if d > dist[cell] {
+                continue;
+            }
+            // Stepping from a neighbor into `cell` costs `cell`'s cost. An
+            // impassable cell still gets a distance (so cats stuck in one can
+            // climb out) but nothing routes through it.
+            for (n, step) in grid.neighbors(cell) {
+                let through = d + grid.cost[cell] * step;
+                if through < dist[n] {
+                    dist[n] = through;
+                    open.push(Frontier(through, n));
+                }
+            }
+        }
+        Self {
+            goal,
+            dist,
+            last_used: 0,
+        }
+    }
+}
+
+/// The grid plus the flow fields built on it so far.
+pub struct Navigator {
+    pub grid: NavGrid,
+    enabled: bool,
+    fields: Vec<FlowField>,
+    since_rebuild: f32,
+    /// Steering calls so far, for least-recently-used eviction.
+    clock: u64,
+}
+
+impl Navigator {
+    pub fn new(desktop: &Desktop) -> Self {
+        Self {
+            grid: NavGrid::open(desktop),
+            enabled: true,
+            fields: Vec::new(),
+            since_rebuild: REBUILD_INTERVAL,
+            clock: 0,
+        }
+    }
+
+    /// Rebuild the grid if it's due.
+    pub fn refresh(
+        &mut self,
+        dt: f32,
+        desktop: &Desktop,
+        windows: &[DesktopWindow],
+        heatmap: &Heatmap,
+        config: &NavigationConfig,
+    ) {
+        self.since_rebuild += dt;
+        if self.since_rebuild >= REBUILD_INTERVAL {
+            self.rebuild(desktop, windows, heatmap, config);
+        }
+    }
+
+    /// Rebuild the grid now, dropping every flow field.
+    pub fn rebuild(
+        &mut self,
+        desktop: &Desktop,
+        windows: &[DesktopWindow],
+        heatmap: &Heatmap,
+        config: &NavigationConfig,
+    ) {
+        self.enabled = config.enabled;
+        self.grid = NavGrid::build(desktop, windows, heatmap, config);
+        self.fields.clear();
+        self.since_rebuild = 0.0;
+    }
+
+    /// Flow fields currently cached.
+    pub fn field_count(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Unit direction a cat at `from` should head to reach `goal` around
+    /// obstacles. Falls back to a straight line when navigation is off, the
+    /// goal is next door, off the grid or walled in.
+    pub fn steer(&mut self, from: Vec2, goal: Vec2) -> Vec2 {
+        let straight = (goal - from).normalize_or_zero();
+        if !self.enabled {
+            return straight;
+        }
+        let (Some(start), Some(goal_cell)) = (self.grid.cell_of(from), self.grid.cell_of(goal))
+        else {
+            return straight;
+        };
+        if start == goal_cell || self.grid.cost[goal_cell].is_infinite() {
+            return straight;
+        }
+
+        self.clock += 1;
+        let idx = self.field(goal_cell);
+        let field = &self.fields[idx];
+        // Follow the flow downhill while it keeps the same heading, so the
+        // cat never aims across a corner, and never into impassable cells
+        // (their distances only exist to lead stuck cats out).
+        let mut cell = start;
+        let mut heading = None;
+        for _ in 0..LOOKAHEAD {
+            let Some((next, d)) = self
+                .grid
+                .neighbors(cell)
+                .filter(|&(n, _)| self.grid.cost[n].is_finite())
+                .map(|(n, _)| (n, field.dist[n]))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+            else {
+                break;
+            };
+            let step = next as isize - cell as isize;
+            if d >= field.dist[cell] || heading.is_some_and(|h| h != step) {
+                break;
+            }
+            heading = Some(step);
+            cell = next;
+            if cell == goal_cell {
+                break;
+            }
+        }
+        if cell == start {
+            return straight;
+        }
+        let target = if cell == goal_cell {
+            goal
+        } else {
+            self.grid.center(cell)
+        };
+        (target - from).normalize_or_zero()
+    }
+
+    /// Index of the flow field toward `goal`, built (and the stalest
+    /// evicted) if new.
+    fn field(&mut self, goal: usize) -> usize {
+        let idx = match self.fields.iter().position(|f| f.goal == goal) {
+            Some(idx) => idx,
+            None => {
+                if self.fields.len() >= MAX_FIELDS {
+                    let stalest = (0..self.fields.len())
+                        .min_by_key(|&i| self.fields[i].last_used)
+                        .expect("cache is full");
+                    self.fields.swap_remove(stalest);
+                }
+                self.fields.push(FlowField::build(&self.grid, goal));
+                self.fields.len() - 1
+            }
+        };
+        self.fields[idx].last_used = self.clock;
+        idx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AvoidZone;
+
+    fn navigator(avoid: Vec<AvoidZone>, windows: &[DesktopWindow]) -> Navigator {
+        let desktop = Desktop::single(640, 480);
+        let heatmap = Heatmap::new(640.0, 480.0);
+        let config = NavigationConfig {
+            avoid,
+            ..NavigationConfig::default()
+        };
+        let mut nav = Navigator::new(&desktop);
+        nav.rebuild(&desktop, windows, &heatmap, &config);
+        nav
+    }
+
+    /// Walk a cat from `from` toward `goal`, returning where it ends up
+    /// and whether it ever stepped into `zone`.
+    fn walk(nav: &mut Navigator, from: Vec2, goal: Vec2, zone: &AvoidZone) -> (Vec2, bool) {
+        let mut pos = from;
+        let mut trespassed = false;
+        for _ in 0..400 {
+            pos += nav.steer(pos, goal) * 4.0;
+            trespassed |= pos.x >= zone.x
+                && pos.x < zone.x + zone.w
+                && pos.y >= zone.y
+                && pos.y < zone.y + zone.h;
+        }
+        (pos, trespassed)
+    }
+
+    #[test]
+    fn routes_around_avoid_zones() {
+        // A wall down the middle with a gap at the bottom.
+        let wall = AvoidZone {
+            x: 288.0,
+            y: 0.0,
+            w: 64.0,
+            h: 384.0,
+        };
+        let mut nav = navigator(vec![wall], &[]);
+        let goal = Vec2::new(560.0, 100.0);
+        let from = Vec2::new(80.0, 100.0);
+
+        assert!(nav.steer(from, goal).y > 0.3, "heads for the gap");
+        let (end, trespassed) = walk(&mut nav, from, goal, &wall);
+        assert!(!trespassed);
+        assert!(end.distance(goal) < 8.0, "arrives ({end})");
+        assert_eq!(nav.field_count(), 1, "one field per goal");
+
+        // Walled-in goals and disabled navigation go straight.
+        let walled_in = Vec2::new(300.0, 100.0);
+        assert_eq!(nav.steer(from, walled_in), (walled_in - from).normalize());
+        nav.enabled = false;
+        assert_eq!(nav.steer(from, goal), Vec2::X);
+    }
+
+    #[test]
+    fn prefers_going_around_windows() {
+        let window = DesktopWindow {
+            id: 1,
+            left: 200.0,
+            top: 0.0,
+            right: 440.0,
+            bottom: 400.0,
+            ..DesktopWindow::default()
+        };
+        let mut nav = navigator(Vec::new(), std::slice::from_ref(&window));
+        let goal = Vec2::new(560.0, 200.0);
+        let from = Vec2::new(80.0, 200.0);
+        let mut pos = from;
+        let mut lowest = pos.y;
+        for _ in 0..300 {
+            pos += nav.steer(pos, goal) * 4.0;
+            lowest = lowest.max(pos.y);
+        }
+        assert!(
+            lowest > window.bottom - CELL_SIZE,
+            "went around the bottom of the window ({lowest})"
+        );
+        assert!(pos.distance(goal) < 8.0);
+
+        for goal_x in 0..MAX_FIELDS + 4 {
+            nav.steer(from, Vec2::new(40.0 + goal_x as f32 * 32.0, 460.0));
+        }
+        assert_eq!(nav.field_count(), MAX_FIELDS);
+    }
+}
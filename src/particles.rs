@@ -1,7 +1,8 @@
 use glam::Vec2;
 
-use crate::ecs::components::BehaviorState;
-use crate::render::instance::CatInstance;
+use pettoy::ecs::components::BehaviorState;
+
+use crate::render::instance::{CatInstance, UNCLIPPED};
 
 /// Maximum concurrent particles.
 const MAX_PARTICLES: usize = 2048;
@@ -104,7 +105,7 @@ impl ParticleSystem {
                 BehaviorState::Playing => {
                     // Colorful sparkles
                     if rng.f32() < 3.0 * dt {
-                        let colors = [0xFF88FFCC, 0xFFFF88CC, 0x88FFFFcc, 0xFFBB44CC];
+                        let colors = [0xFF88FFCC, 0xFFFF88CC, 0x88FFFFCC, 0xFFBB44CC];
                         Some(ParticleSpawn {
                             color: colors[rng.usize(0..colors.len())],
                             frame: 5, // star
@@ -241,6 +242,7 @@ impl ParticleSystem {
                 color,
                 frame: p.frame,
                 rotation: 0.0,
+                clip: UNCLIPPED,
             });
         }
     }
@@ -298,11 +300,6 @@ impl ParticleSystem {
             });
         }
     }
-
-    /// Number of active particles.
-    pub fn count(&self) -> usize {
-        self.particles.len()
-    }
 }
 
 struct ParticleSpawn {
@@ -0,0 +1,678 @@
+//! X11 backend — the Linux counterpart of `platform::win32`.
+//!
+//! One lazily opened X connection serves every query. Held state comes from
+//! `QueryPointer`/`QueryKeymap`; presses that start and end between two polls
+//! are caught by XInput2 raw events on the root window, the same job the
+//! transition bit of `GetAsyncKeyState` does on Windows.
+//!
+//! Wayland sessions run through XWayland when they have it (see
+//! [`use_xwayland`]). Wayland itself has no global cursor position, input
+//! polling or window list; without XWayland the overlay is drawn through the
+//! layer shell (`platform::wayland`) and the queries here find no display.
+
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use glam::Vec2;
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use x11rb::connection::Connection;
+use x11rb::properties::WmHints;
+use x11rb::protocol::screensaver::ConnectionExt as _;
+use x11rb::protocol::xinput::{self, ConnectionExt as _};
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ConnectionExt as _, KeyButMask, Keycode, MapState, PropMode, Window as XWindow,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+
+use pettoy::automation::Weekday;
+use pettoy::input::{Key, Keyboard};
+
+use pettoy::ecs::systems::window_aware::WindowState;
+
+use super::{Platform, TrayCommand, WindowRect};
+
+x11rb::atom_manager! {
+    Atoms: AtomsCookie {
+        _NET_ACTIVE_WINDOW,
+        _NET_CLIENT_LIST_STACKING,
+        _NET_FRAME_EXTENTS,
+        _NET_WM_NAME,
+        _NET_WM_PID,
+        _NET_WM_STATE,
+        _NET_WM_STATE_ABOVE,
+        _NET_WM_STATE_FULLSCREEN,
+        _NET_WM_STATE_HIDDEN,
+        _NET_WM_STATE_MAXIMIZED_HORZ,
+        _NET_WM_STATE_MAXIMIZED_VERT,
+        _NET_WM_STATE_SKIP_PAGER,
+        _NET_WM_STATE_SKIP_TASKBAR,
+        _NET_WM_STATE_STICKY,
+        _NET_WM_WINDOW_TYPE,
+        _NET_WM_WINDOW_TYPE_DIALOG,
+        _NET_WM_WINDOW_TYPE_NORMAL,
+        _NET_WM_WINDOW_TYPE_TOOLBAR,
+        _NET_WM_WINDOW_TYPE_UTILITY,
+        UTF8_STRING,
+    }
+}
+
+/// Keysyms producing `key` (X11/keysymdef.h). Modifiers cover both sides.
+fn keysyms(key: Key) -> Vec<u32> {
+    let name = key.name().as_bytes();
+    match key {
+        Key::Escape => vec![0xff1b],
+        Key::Space => vec![0x0020],
+        Key::Tab => vec![0xff09],
+        Key::Enter => vec![0xff0d, 0xff8d],
+        Key::Backspace => vec![0xff08],
+        Key::Insert => vec![0xff63],
+        Key::Delete => vec![0xffff],
+        Key::Home => vec![0xff50],
+        Key::End => vec![0xff57],
+        Key::PageUp => vec![0xff55],
+        Key::PageDown => vec![0xff56],
+        Key::Left => vec![0xff51],
+        Key::Up => vec![0xff52],
+        Key::Right => vec![0xff53],
+        Key::Down => vec![0xff54],
+        Key::Pause => vec![0xff13],
+        Key::ScrollLock => vec![0xff14],
+        Key::Ctrl => vec![0xffe3, 0xffe4],
+        Key::Shift => vec![0xffe1, 0xffe2],
+        Key::Alt => vec![0xffe9, 0xffea],
+        Key::Super => vec![0xffeb, 0xffec],
+        // F1..F12 are consecutive from 0xffbe.
+        _ if name[0] == b'F' && name.len() > 1 => {
+            let n: u32 = key.name()[1..].parse().expect("F-key number");
+            vec![0xffbe + n - 1]
+        }
+        // Letters and digits are their Latin-1 codes (lowercase for letters).
+        _ => vec![name[0].to_ascii_lowercase() as u32],
+    }
+}
+
+/// Presses seen in XInput2 raw events since each one was last read.
+struct Pending {
+    /// Left, right, middle.
+    buttons: [bool; 3],
+    /// Indexed by [`Key::index`].
+    keys: Vec<bool>,
+}
+
+impl Default for Pending {
+    fn default() -> Self {
+        Self {
+            buttons: [false; 3],
+            keys: vec![false; Key::ALL.len()],
+        }
+    }
+}
+
+struct X11 {
+    conn: RustConnection,
+    root: XWindow,
+    atoms: Atoms,
+    /// Every keycode producing each key, by [`Key::index`] (layouts can
+    /// have several).
+    keycodes: Vec<Vec<Keycode>>,
+    /// False when the server lacks XInput 2.1; only held state is seen then.
+    raw_input: bool,
+    pending: Mutex<Pending>,
+}
+
+static X11: OnceLock<Option<X11>> = OnceLock::new();
+
+/// The shared X connection, or `None` (logged once) when there is no display.
+fn x11() -> Option<&'static X11> {
+    X11.get_or_init(|| match X11::connect() {
+        Ok(x) => Some(x),
+        Err(e) => {
+            log::warn!("X11 unavailable ({e}); desktop input and window awareness disabled");
+            None
+        }
+    })
+    .as_ref()
+}
+
+impl X11 {
+    fn connect() -> Result<Self, Box<dyn std::error::Error>> {
+        let (conn, screen_num) = x11rb::connect(None)?;
+        let root = conn.setup().roots[screen_num].root;
+        let atoms = Atoms::new(&conn)?.reply()?;
+
+        let setup = conn.setup();
+        let (min, max) = (setup.min_keycode, setup.max_keycode);
+        let mapping = conn.get_keyboard_mapping(min, max - min + 1)?.reply()?;
+        let keycodes = Key::ALL
+            .iter()
+            .map(|&key| {
+                let mut codes: Vec<Keycode> = keysyms(key)
+                    .into_iter()
+                    .flat_map(|sym| {
+                        keycodes_for(&mapping.keysyms, mapping.keysyms_per_keycode, min, sym)
+                    })
+                    .collect();
+                codes.sort_unstable();
+                codes.dedup();
+                codes
+            })
+            .collect();
+
+        // Raw events on the root window are delivered regardless of focus
+        // from XInput 2.1 on.
+        let raw_input = conn
+            .xinput_xi_query_version(2, 1)
+            .ok()
+            .and_then(|c| c.reply().ok())
+            .is_some_and(|v| (v.major_version, v.minor_version) >= (2, 1));
+        if raw_input {
+            conn.xinput_xi_select_events(
+                root,
+                &[xinput::EventMask {
+                    deviceid: xinput::Device::ALL_MASTER.into(),
+                    mask: vec![
+                        xinput::XIEventMask::RAW_KEY_PRESS | xinput::XIEventMask::RAW_BUTTON_PRESS,
+                    ],
+                }],
+            )?;
+            conn.flush()?;
+        } else {
+            log::warn!("XInput 2.1 unavailable; quick clicks between polls may be missed");
+        }
+
+        log::info!("X11 platform backend connected (screen {screen_num})");
+        Ok(Self {
+            conn,
+            root,
+            atoms,
+            keycodes,
+            raw_input,
+            pending: Mutex::new(Pending::default()),
+        })
+    }
+
+    /// Drain queued raw events into `pending`, then hand it to `f`.
+    fn with_pending<R>(&self, f: impl FnOnce(&mut Pending) -> R) -> R {
+        let mut pending = self.pending.lock().expect("pending input lock poisoned");
+        if self.raw_input {
+            while let Ok(Some(event)) = self.conn.poll_for_event() {
+                match event {
+                    Event::XinputRawButtonPress(e) => match e.detail {
+                        1 => pending.buttons[0] = true,
+                        3 => pending.buttons[1] = true,
+                        2 => pending.buttons[2] = true,
+                        _ => {}
+                    },
+                    Event::XinputRawKeyPress(e) => {
+                        for (i, codes) in self.keycodes.iter().enumerate() {
+                            if codes.iter().any(|&c| u32::from(c) == e.detail) {
+                                pending.keys[i] = true;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        f(&mut pending)
+    }
+
+    /// Whether any keycode for `key` is held right now.
+    fn key_held(&self, key: Key) -> bool {
+        let Some(reply) = self.conn.query_keymap().ok().and_then(|c| c.reply().ok()) else {
+            return false;
+        };
+        self.keycodes[key.index()]
+            .iter()
+            .any(|&c| reply.keys[c as usize / 8] & (1 << (c % 8)) != 0)
+    }
+
+    /// Whether `key` was pressed since the last call.
+    fn key_pressed(&self, key: Key) -> bool {
+        if self.raw_input {
+            self.with_pending(|p| std::mem::take(&mut p.keys[key.index()]))
+        } else {
+            // Without raw events, fall back to held state. The action layer
+            // edge-detects, so a hold still fires once.
+            self.key_held(key)
+        }
+    }
+
+    fn property(&self, window: XWindow, prop: Atom, ty: impl Into<Atom>) -> Option<Vec<u8>> {
+        let reply = self
+            .conn
+            .get_property(false, window, prop, ty, 0, u32::MAX / 4)
+            .ok()?
+            .reply()
+            .ok()?;
+        (reply.format != 0).then_some(reply.value)
+    }
+
+    fn property32(&self, window: XWindow, prop: Atom, ty: impl Into<Atom>) -> Vec<u32> {
+        self.property(window, prop, ty)
+            .map(|bytes| {
+                bytes
+                    .chunks_exact(4)
+                    .map(|c| u32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn title(&self, window: XWindow) -> String {
+        self.property(window, self.atoms._NET_WM_NAME, self.atoms.UTF8_STRING)
+            .or_else(|| self.property(window, AtomEnum::WM_NAME.into(), AtomEnum::STRING))
+            .map(|bytes| String::from_utf8_lossy(&bytes).chars().take(256).collect())
+            .unwrap_or_default()
+    }
+
+    /// Outer rect (including WM decorations) of a managed client window.
+    fn frame_rect(&self, window: XWindow) -> Option<WindowRect> {
+        let geom = self.conn.get_geometry(window).ok()?.reply().ok()?;
+        let origin = self
+            .conn
+            .translate_coordinates(window, self.root, 0, 0)
+            .ok()?
+            .reply()
+            .ok()?;
+
+        // [left, right, top, bottom] frame sizes, so cats can sit on the
+        // titlebar like they do on Windows.
+        let extents = self.property32(window, self.atoms._NET_FRAME_EXTENTS, AtomEnum::CARDINAL);
+        let [left, right, top, bottom] = match extents[..] {
+            [l, r, t, b] => [l, r, t, b].map(|v| v as i32),
+            _ => [0; 4],
+        };
+
+        Some(WindowRect {
+            id: u64::from(window),
+            x: origin.dst_x as i32 - left,
+            y: origin.dst_y as i32 - top,
+            w: geom.width as i32 + left + right,
+            h: geom.height as i32 + top + bottom,
+            ..WindowRect::default()
+        })
+    }
+
+    /// Name of the process that owns `window`, from its `_NET_WM_PID`.
+    /// Empty for windows that don't set one, or whose process is on
+    /// another host.
+    fn process(&self, window: XWindow) -> String {
+        let pid = self.property32(window, self.atoms._NET_WM_PID, AtomEnum::CARDINAL);
+        pid.first()
+            .and_then(|pid| std::fs::read_to_string(format!("/proc/{pid}/comm")).ok())
+            .map(|comm| comm.trim_end().to_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Keycodes whose mapping contains `keysym` in any column.
+fn keycodes_for(
+    keysyms: &[u32],
+    per_keycode: u8,
+    min_keycode: Keycode,
+    keysym: u32,
+) -> Vec<Keycode> {
+    if per_keycode == 0 {
+        return Vec::new();
+    }
+    keysyms
+        .chunks(per_keycode as usize)
+        .enumerate()
+        .filter(|(_, syms)| syms.contains(&keysym))
+        .map(|(i, _)| min_keycode + i as Keycode)
+        .collect()
+}
+
+/// Whether to force winit onto XWayland. True in a Wayland session that
+/// also offers an X display, since only X11 gives us the global cursor and
+/// window list.
+pub fn use_xwayland() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some() && std::env::var_os("DISPLAY").is_some()
+}
+
+/// Extract the X11 window id from a winit window.
+pub fn get_xid(window: &winit::window::Window) -> XWindow {
+    let handle = window.window_handle().expect("window handle unavailable");
+    match handle.as_raw() {
+        RawWindowHandle::Xlib(h) => h.window as XWindow,
+        RawWindowHandle::Xcb(h) => h.window.get(),
+        _ => 0,
+    }
+}
+
+/// Set up the window as a transparent, click-through, always-on-top overlay.
+///
+/// Call before the window is first mapped: the window manager reads
+/// `_NET_WM_STATE` and `WM_HINTS` at map time.
+pub fn setup_overlay(window: &winit::window::Window) {
+    // winit clears the XShape input region, so clicks fall through.
+    window
+        .set_cursor_hittest(false)
+        .expect("failed to set cursor hittest");
+
+    let Some(x) = x11() else {
+        log::warn!("No X11 display; overlay runs as a plain window");
+        return;
+    };
+    let xid = get_xid(window);
+    if xid == 0 {
+        log::warn!("Not an X11 window; skipping overlay hints");
+        return;
+    }
+
+    let a = &x.atoms;
+    let state = [
+        a._NET_WM_STATE_ABOVE,
+        a._NET_WM_STATE_STICKY,
+        a._NET_WM_STATE_SKIP_TASKBAR,
+        a._NET_WM_STATE_SKIP_PAGER,
+    ];
+    let result = x
+        .conn
+        .change_property32(
+            PropMode::REPLACE,
+            xid,
+            a._NET_WM_STATE,
+            AtomEnum::ATOM,
+            &state,
+        )
+        .map(|_| ())
+        .and_then(|()| {
+            // Never take keyboard focus (WS_EX_NOACTIVATE on Windows).
+            let hints = WmHints {
+                input: Some(false),
+                ..WmHints::new()
+            };
+            hints.set(&x.conn, xid).map(|_| ())
+        })
+        .and_then(|()| x.conn.flush());
+    if let Err(e) = result {
+        log::warn!("Failed to set overlay window hints: {e}");
+    }
+
+    log::info!("X11 overlay setup complete (ARGB + click-through + above, sticky, no taskbar)");
+}
+
+/// Get the current global mouse cursor position in screen pixels.
+pub fn get_mouse_pos() -> (f32, f32) {
+    let Some(x) = x11() else {
+        return (0.0, 0.0);
+    };
+    match x
+        .conn
+        .query_pointer(x.root)
+        .ok()
+        .and_then(|c| c.reply().ok())
+    {
+        Some(p) => (p.root_x as f32, p.root_y as f32),
+        None => (0.0, 0.0),
+    }
+}
+
+/// Check if a key is currently held (works regardless of window focus).
+pub fn is_key_down(key: Key) -> bool {
+    x11().is_some_and(|x| x.key_held(key))
+}
+
+/// Check if a key was pressed since last call.
+pub fn was_key_pressed(key: Key) -> bool {
+    x11().is_some_and(|x| x.key_pressed(key))
+}
+
+/// Get seconds since last user input (keyboard/mouse, system-wide).
+/// Uses the MIT-SCREEN-SAVER extension's idle counter.
+pub fn get_idle_time() -> f64 {
+    let Some(x) = x11() else {
+        return 0.0;
+    };
+    x.conn
+        .screensaver_query_info(x.root)
+        .ok()
+        .and_then(|c| c.reply().ok())
+        .map_or(0.0, |info| info.ms_since_user_input as f64 / 1000.0)
+}
+
+/// Get the current local hour as a float (0.0-24.0, e.g. 14.5 = 2:30 PM).
+pub fn get_local_hour() -> f32 {
+    local_time().map_or(12.0, |tm| {
+        tm.tm_hour as f32 + tm.tm_min as f32 / 60.0 + tm.tm_sec as f32 / 3600.0
+    })
+}
+
+/// Get the local day of the week.
+pub fn get_weekday() -> Weekday {
+    local_time()
+        // tm_wday counts from Sunday
+        .and_then(|tm| Weekday::from_index(((tm.tm_wday + 6) % 7) as u8))
+        .unwrap_or_default()
+}
+
+fn local_time() -> Option<libc::tm> {
+    // SAFETY: `time` accepts null; `localtime_r` writes only into `tm`.
+    unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        (!libc::localtime_r(&now, &mut tm).is_null()).then_some(tm)
+    }
+}
+
+/// Charge of the first battery in percent while it's discharging, from
+/// `/sys/class/power_supply`. `None` on mains power or without a battery.
+pub fn get_battery() -> Option<f32> {
+    for supply in std::fs::read_dir("/sys/class/power_supply").ok()?.flatten() {
+        let path = supply.path();
+        let read = |name: &str| std::fs::read_to_string(path.join(name)).ok();
+        if read("type").as_deref().map(str::trim) != Some("Battery") {
+            continue;
+        }
+        if read("status").as_deref().map(str::trim) != Some("Discharging") {
+            return None;
+        }
+        return read("capacity")?.trim().parse().ok();
+    }
+    None
+}
+
+/// Get mouse button states. Returns (left_down, right_down, middle_down).
+/// Like the Win32 backend, reports a button that is held now or was pressed
+/// since the last call, so quick clicks between polls aren't lost.
+pub fn get_mouse_buttons() -> (bool, bool, bool) {
+    let Some(x) = x11() else {
+        return (false, false, false);
+    };
+    let held = x
+        .conn
+        .query_pointer(x.root)
+        .ok()
+        .and_then(|c| c.reply().ok())
+        .map_or(KeyButMask::default(), |p| p.mask);
+    let clicked = x.with_pending(|p| std::mem::take(&mut p.buttons));
+    (
+        held.contains(KeyButMask::BUTTON1) || clicked[0],
+        held.contains(KeyButMask::BUTTON3) || clicked[1],
+        held.contains(KeyButMask::BUTTON2) || clicked[2],
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Window Enumeration
+// ---------------------------------------------------------------------------
+
+/// Enumerate visible application windows on the current workspace, topmost
+/// first (the order `EnumWindows` uses). Excludes our own overlay, docks,
+/// panels, menus and other non-application windows, and windows with zero
+/// area; toolbars and utility palettes come back marked as tool windows.
+/// Call this periodically (e.g., every few seconds), NOT every frame.
+pub fn enumerate_windows(overlays: &[XWindow]) -> Vec<WindowRect> {
+    let Some(x) = x11() else {
+        return Vec::new();
+    };
+    let a = &x.atoms;
+
+    let active = x
+        .property32(x.root, a._NET_ACTIVE_WINDOW, AtomEnum::WINDOW)
+        .first()
+        .copied();
+
+    // Bottom-to-top stacking order
+    let clients = x.property32(x.root, a._NET_CLIENT_LIST_STACKING, AtomEnum::WINDOW);
+    let mut results = Vec::with_capacity(clients.len());
+
+    for window in clients.into_iter().rev() {
+        if overlays.contains(&window) {
+            continue;
+        }
+
+        // Unmapped = on another workspace (or withdrawn)
+        let viewable = x
+            .conn
+            .get_window_attributes(window)
+            .ok()
+            .and_then(|c| c.reply().ok())
+            .is_some_and(|attrs| attrs.map_state == MapState::VIEWABLE);
+        if !viewable {
+            continue;
+        }
+
+        // Skip docks, panels, menus, tooltips, etc. Untyped windows count
+        // as normal per EWMH.
+        let types = x.property32(window, a._NET_WM_WINDOW_TYPE, AtomEnum::ATOM);
+        let tool = types.contains(&a._NET_WM_WINDOW_TYPE_UTILITY)
+            || types.contains(&a._NET_WM_WINDOW_TYPE_TOOLBAR);
+        if !types.is_empty()
+            && !tool
+            && !types.contains(&a._NET_WM_WINDOW_TYPE_NORMAL)
+            && !types.contains(&a._NET_WM_WINDOW_TYPE_DIALOG)
+        {
+            continue;
+        }
+
+        let Some(mut rect) = x.frame_rect(window) else {
+            continue;
+        };
+
+        // Skip zero-area windows
+        if rect.w <= 0 || rect.h <= 0 {
+            continue;
+        }
+
+        let state = x.property32(window, a._NET_WM_STATE, AtomEnum::ATOM);
+        rect.state = WindowState {
+            focused: active == Some(window),
+            minimized: state.contains(&a._NET_WM_STATE_HIDDEN),
+            maximized: state.contains(&a._NET_WM_STATE_MAXIMIZED_HORZ)
+                && state.contains(&a._NET_WM_STATE_MAXIMIZED_VERT),
+            fullscreen: state.contains(&a._NET_WM_STATE_FULLSCREEN),
+            tool,
+            // Desktop and dock windows were skipped above
+            shell: false,
+        };
+        rect.title = x.title(window);
+        rect.process = x.process(window);
+        results.push(rect);
+    }
+
+    results
+}
+
+// ---------------------------------------------------------------------------
+// Platform
+// ---------------------------------------------------------------------------
+
+/// How long a battery reading is good for.
+const BATTERY_POLL: Duration = Duration::from_secs(30);
+
+/// [`Platform`] backed by the functions above. There is no tray icon on X11;
+/// the hotkeys cover quit, mode and debug.
+#[derive(Default)]
+pub struct LinuxPlatform {
+    /// Our overlays (one per monitor), excluded from window enumeration.
+    overlays: Vec<XWindow>,
+    /// Latest [`get_battery`] reading and when it was taken; sysfs is too
+    /// slow to read every tick.
+    battery: Option<(Instant, Option<f32>)>,
+}
+
+impl Keyboard for LinuxPlatform {
+    fn key_down(&mut self, key: Key) -> bool {
+        is_key_down(key)
+    }
+
+    fn key_pressed(&mut self, key: Key) -> bool {
+        was_key_pressed(key)
+    }
+}
+
+impl Platform for LinuxPlatform {
+    fn setup_overlay(&mut self, window: &winit::window::Window) {
+        self.overlays.push(get_xid(window));
+        setup_overlay(window);
+    }
+
+    fn mouse_pos(&mut self) -> Vec2 {
+        let (x, y) = get_mouse_pos();
+        Vec2::new(x, y)
+    }
+
+    fn mouse_buttons(&mut self) -> (bool, bool, bool) {
+        get_mouse_buttons()
+    }
+
+    fn idle_seconds(&mut self) -> f64 {
+        get_idle_time()
+    }
+
+    fn local_hour(&mut self) -> f32 {
+        get_local_hour()
+    }
+
+    fn weekday(&mut self) -> Weekday {
+        get_weekday()
+    }
+
+    fn battery(&mut self) -> Option<f32> {
+        match self.battery {
+            Some((read, charge)) if read.elapsed() < BATTERY_POLL => charge,
+            _ => {
+                let charge = get_battery();
+                self.battery = Some((Instant::now(), charge));
+                charge
+            }
+        }
+    }
+
+    fn enumerate_windows(&mut self) -> Vec<WindowRect> {
+        enumerate_windows(&self.overlays)
+    }
+
+    fn poll_tray(&mut self) -> TrayCommand {
+        TrayCommand::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keysyms_follow_keysymdef() {
+        assert_eq!(keysyms(Key::F1), vec![0xffbe]);
+        assert_eq!(keysyms(Key::F12), vec![0xffc9]);
+        assert_eq!(keysyms(Key::B), vec![0x0062]);
+        assert_eq!(keysyms(Key::Digit7), vec![0x0037]);
+        assert_eq!(keysyms(Key::Ctrl), vec![0xffe3, 0xffe4]);
+    }
+
+    #[test]
+    fn keycodes_for_finds_every_column() {
+        // Two keysyms per keycode, starting at keycode 8.
+        let syms = [0x0061, 0x0041, 0x0062, 0x0042, 0xff1b, 0, 0x0042, 0x0062];
+        assert_eq!(keycodes_for(&syms, 2, 8, 0x0062), vec![9, 11]);
+        assert_eq!(keycodes_for(&syms, 2, 8, 0xff1b), vec![10]);
+        assert!(keycodes_for(&syms, 2, 8, 0xffc9).is_empty());
+        assert!(keycodes_for(&syms, 0, 8, 0x0062).is_empty());
+    }
+}
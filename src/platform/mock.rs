@@ -0,0 +1,126 @@
+//! Scripted [`Platform`] for tests, and the stand-in on OSes without a
+//! backend. Tests set the held state directly and queue one-shot presses,
+//! clicks and tray commands, which are consumed the way a real backend
+//! consumes its transition bits.
+
+use std::collections::{HashSet, VecDeque};
+
+use glam::Vec2;
+
+use pettoy::automation::Weekday;
+use pettoy::input::{Key, Keyboard};
+
+use super::{Platform, TrayCommand, WindowRect};
+
+#[derive(Debug)]
+pub struct MockPlatform {
+    pub mouse: Vec2,
+    /// Buttons held right now: (left, right, middle).
+    pub buttons: (bool, bool, bool),
+    /// Keys held right now.
+    pub held: HashSet<Key>,
+    pub idle: f64,
+    pub hour: f32,
+    pub weekday: Weekday,
+    pub battery: Option<f32>,
+    pub windows: Vec<WindowRect>,
+    /// Set once `setup_overlay` has run.
+    pub overlay_ready: bool,
+    /// Presses not yet read by `key_pressed`.
+    pressed: HashSet<Key>,
+    /// Clicks not yet read by `mouse_buttons`.
+    clicked: (bool, bool, bool),
+    tray: VecDeque<TrayCommand>,
+}
+
+impl Default for MockPlatform {
+    fn default() -> Self {
+        Self {
+            mouse: Vec2::ZERO,
+            buttons: (false, false, false),
+            held: HashSet::new(),
+            idle: 0.0,
+            hour: 12.0,
+            weekday: Weekday::default(),
+            battery: None,
+            windows: Vec::new(),
+            overlay_ready: false,
+            pressed: HashSet::new(),
+            clicked: (false, false, false),
+            tray: VecDeque::new(),
+        }
+    }
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+impl MockPlatform {
+    /// Press and release `key` between two polls.
+    pub fn tap(&mut self, key: Key) {
+        self.pressed.insert(key);
+    }
+
+    /// Click and release buttons between two polls: (left, right, middle).
+    pub fn click(&mut self, buttons: (bool, bool, bool)) {
+        self.clicked.0 |= buttons.0;
+        self.clicked.1 |= buttons.1;
+        self.clicked.2 |= buttons.2;
+    }
+
+    /// Queue a tray menu command for `poll_tray`.
+    pub fn queue_tray(&mut self, command: TrayCommand) {
+        self.tray.push_back(command);
+    }
+}
+
+impl Keyboard for MockPlatform {
+    fn key_down(&mut self, key: Key) -> bool {
+        self.held.contains(&key)
+    }
+
+    fn key_pressed(&mut self, key: Key) -> bool {
+        self.pressed.remove(&key)
+    }
+}
+
+impl Platform for MockPlatform {
+    fn setup_overlay(&mut self, _window: &winit::window::Window) {
+        self.overlay_ready = true;
+    }
+
+    fn mouse_pos(&mut self) -> Vec2 {
+        self.mouse
+    }
+
+    fn mouse_buttons(&mut self) -> (bool, bool, bool) {
+        let clicked = std::mem::take(&mut self.clicked);
+        (
+            self.buttons.0 || clicked.0,
+            self.buttons.1 || clicked.1,
+            self.buttons.2 || clicked.2,
+        )
+    }
+
+    fn idle_seconds(&mut self) -> f64 {
+        self.idle
+    }
+
+    fn local_hour(&mut self) -> f32 {
+        self.hour
+    }
+
+    fn weekday(&mut self) -> Weekday {
+        self.weekday
+    }
+
+    fn battery(&mut self) -> Option<f32> {
+        self.battery
+    }
+
+    fn enumerate_windows(&mut self) -> Vec<WindowRect> {
+        self.windows.clone()
+    }
+
+    fn poll_tray(&mut self) -> TrayCommand {
+        self.tray.pop_front().unwrap_or(TrayCommand::None)
+    }
+}
@@ -1,2 +1,102 @@
+//! OS integration behind the [`Platform`] trait: global input polling, idle
+//! time, local time, desktop window enumeration, overlay setup and the tray.
+//!
+//! The app only talks to a `Box<dyn Platform>` from [`native`], so every
+//! input path can be driven by [`mock::MockPlatform`] in tests.
+
+#[cfg(target_os = "linux")]
+pub mod linux;
+#[cfg(any(test, not(any(windows, target_os = "linux"))))]
+pub mod mock;
+#[cfg(windows)]
+mod tray;
+#[cfg(all(target_os = "linux", feature = "wayland"))]
+pub mod wayland;
 #[cfg(windows)]
 pub mod win32;
+
+use glam::Vec2;
+use pettoy::ecs::systems::window_aware::WindowState;
+use pettoy::automation::Weekday;
+use pettoy::input::Keyboard;
+
+/// Commands returned from tray menu interactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(windows), allow(dead_code))]
+pub enum TrayCommand {
+    None,
+    Quit,
+    SetModeWork,
+    SetModePlay,
+    SetModeZen,
+    SetModeChaos,
+    TogglePause,
+    ToggleDoNotDisturb,
+    ToggleDebug,
+}
+
+/// A rectangle representing a visible desktop window.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowRect {
+    /// OS handle, the same across enumerations for as long as the window lives.
+    pub id: u64,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub state: WindowState,
+    pub title: String,
+    /// Executable name of the owning process, empty if unknown.
+    pub process: String,
+}
+
+/// Everything the app needs from the OS. Keys are polled system-wide
+/// through [`Keyboard`]: the overlay is click-through, so it never gets
+/// keyboard events of its own.
+pub trait Platform: Keyboard {
+    /// Make `window` a transparent, click-through, always-on-top overlay.
+    /// Called once per overlay (one per monitor), before it is first shown.
+    fn setup_overlay(&mut self, window: &winit::window::Window);
+
+    /// Global mouse cursor position in virtual-desktop pixels.
+    fn mouse_pos(&mut self) -> Vec2;
+
+    /// (left, right, middle): held now, or clicked since the last call, so
+    /// quick clicks between polls aren't lost.
+    fn mouse_buttons(&mut self) -> (bool, bool, bool);
+
+    /// Seconds since the last user input, system-wide.
+    fn idle_seconds(&mut self) -> f64;
+
+    /// Local hour as a float (0.0-24.0, e.g. 14.5 = 2:30 PM).
+    fn local_hour(&mut self) -> f32;
+
+    /// Local day of the week.
+    fn weekday(&mut self) -> Weekday;
+
+    /// Battery charge in percent while running on battery, `None` on mains
+    /// power or without a battery. Called every tick, so backends that
+    /// have to dig for it should cache.
+    fn battery(&mut self) -> Option<f32>;
+
+    /// Visible application windows in virtual-desktop pixels, topmost
+    /// first, excluding the overlays.
+    /// Call periodically (every few seconds), NOT every frame.
+    fn enumerate_windows(&mut self) -> Vec<WindowRect>;
+
+    /// Next pending tray menu command. Call once per frame.
+    fn poll_tray(&mut self) -> TrayCommand;
+}
+
+/// The platform backend for the OS being built.
+pub fn native() -> Box<dyn Platform> {
+    #[cfg(windows)]
+    return Box::new(win32::Win32Platform::new());
+    #[cfg(target_os = "linux")]
+    return Box::new(linux::LinuxPlatform::default());
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        log::warn!("No platform backend for this OS; desktop input disabled");
+        Box::new(mock::MockPlatform::default())
+    }
+}
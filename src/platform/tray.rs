@@ -1,13 +1,10 @@
-/// System tray icon with right-click context menu.
-/// Uses Win32 Shell_NotifyIconW API directly — no extra crate needed.
+//! System tray icon with right-click context menu.
+//! Uses Win32 Shell_NotifyIconW API directly — no extra crate needed.
 
-#[cfg(windows)]
 use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
-#[cfg(windows)]
 use windows::Win32::UI::Shell::{
     Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_DELETE, NOTIFYICONDATAW,
 };
-#[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::{
     AppendMenuW, CreatePopupMenu, CreateWindowExW, DefWindowProcW, DestroyMenu, DestroyWindow,
     GetCursorPos, LoadIconW, PostMessageW, RegisterClassW, SetForegroundWindow, TrackPopupMenu,
@@ -15,6 +12,8 @@ use windows::Win32::UI::WindowsAndMessaging::{
     TPM_LEFTALIGN, WM_COMMAND, WM_DESTROY, WM_USER, WNDCLASSW, WS_EX_TOOLWINDOW,
 };
 
+use super::TrayCommand;
+
 /// Custom message ID for tray icon callbacks.
 const WM_TRAYICON: u32 = WM_USER + 1;
 
@@ -26,31 +25,16 @@ const ID_MODE_ZEN: u16 = 1003;
 const ID_MODE_CHAOS: u16 = 1004;
 const ID_PAUSE: u16 = 1005;
 const ID_DEBUG: u16 = 1006;
-
-/// Commands returned from tray menu interactions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TrayCommand {
-    None,
-    Quit,
-    SetModeWork,
-    SetModePlay,
-    SetModeZen,
-    SetModeChaos,
-    TogglePause,
-    ToggleDebug,
-}
+const ID_DND: u16 = 1007;
 
 /// System tray icon state.
 pub struct TrayIcon {
-    #[cfg(windows)]
     hwnd: HWND,
-    #[cfg(windows)]
     nid: NOTIFYICONDATAW,
     /// Pending command from the last menu interaction.
     pub pending_command: TrayCommand,
 }
 
-#[cfg(windows)]
 impl TrayIcon {
     pub fn new() -> Self {
         unsafe {
@@ -116,7 +100,6 @@ impl TrayIcon {
 
     /// Poll for tray menu commands. Call once per frame.
     pub fn poll(&mut self) -> TrayCommand {
-        #[cfg(windows)]
         unsafe {
             // Process any pending messages for our hidden window.
             use windows::Win32::UI::WindowsAndMessaging::{
@@ -138,6 +121,7 @@ impl TrayIcon {
                         ID_MODE_CHAOS => TrayCommand::SetModeChaos,
                         ID_PAUSE => TrayCommand::TogglePause,
                         ID_DEBUG => TrayCommand::ToggleDebug,
+                        ID_DND => TrayCommand::ToggleDoNotDisturb,
                         _ => TrayCommand::None,
                     };
                 }
@@ -151,7 +135,6 @@ impl TrayIcon {
 
     /// Remove the tray icon (called on shutdown).
     pub fn remove(&mut self) {
-        #[cfg(windows)]
         unsafe {
             let _ = Shell_NotifyIconW(NIM_DELETE, &self.nid);
             let _ = DestroyWindow(self.hwnd);
@@ -159,7 +142,6 @@ impl TrayIcon {
     }
 }
 
-#[cfg(windows)]
 impl Drop for TrayIcon {
     fn drop(&mut self) {
         self.remove();
@@ -167,7 +149,6 @@ impl Drop for TrayIcon {
 }
 
 /// Window procedure for the hidden tray message window.
-#[cfg(windows)]
 unsafe extern "system" fn tray_wnd_proc(
     hwnd: HWND,
     msg: u32,
@@ -194,7 +175,6 @@ unsafe extern "system" fn tray_wnd_proc(
 }
 
 /// Show the right-click context menu at the cursor position.
-#[cfg(windows)]
 unsafe fn show_context_menu(hwnd: HWND) {
     let hmenu = CreatePopupMenu().expect("failed to create popup menu");
 
@@ -225,6 +205,14 @@ unsafe fn show_context_menu(hwnd: HWND) {
         windows::core::PCWSTR(pause_label.as_ptr()),
     );
 
+    let dnd_label: Vec<u16> = "Do Not Disturb\0".encode_utf16().collect();
+    let _ = AppendMenuW(
+        hmenu,
+        MF_STRING,
+        ID_DND as usize,
+        windows::core::PCWSTR(dnd_label.as_ptr()),
+    );
+
     let debug_label: Vec<u16> = "Debug Overlay (F12)\0".encode_utf16().collect();
     let _ = AppendMenuW(
         hmenu,
@@ -261,17 +249,3 @@ unsafe fn show_context_menu(hwnd: HWND) {
 
     let _ = DestroyMenu(hmenu);
 }
-
-// Non-windows stub
-#[cfg(not(windows))]
-impl TrayIcon {
-    pub fn new() -> Self {
-        Self {
-            pending_command: TrayCommand::None,
-        }
-    }
-    pub fn poll(&mut self) -> TrayCommand {
-        TrayCommand::None
-    }
-    pub fn remove(&mut self) {}
-}
@@ -0,0 +1,339 @@
+//! wlr-layer-shell overlays — how the cats get on screen in a Wayland
+//! session that has no XWayland to fall back to.
+//!
+//! Each output gets a layer surface in the overlay layer, anchored to every
+//! edge, with no keyboard focus and an empty input region so clicks reach
+//! whatever is underneath. The surfaces live on our own Wayland connection
+//! next to winit's; the renderer draws into them through raw handles (see
+//! [`crate::render::Target`]).
+//!
+//! Wayland hands no client the global cursor, keyboard or window list, so
+//! while the overlay draws fine, [`super::linux`] still only sees the
+//! desktop through X11: without it the cats can't see the cursor, hotkeys
+//! don't fire and window awareness is off. Compositors without the layer
+//! shell (GNOME, for one) get a plain window instead.
+
+use std::ffi::c_void;
+use std::ptr::NonNull;
+
+use glam::Vec2;
+use raw_window_handle::{
+    RawDisplayHandle, RawWindowHandle, WaylandDisplayHandle, WaylandWindowHandle,
+};
+use smithay_client_toolkit::compositor::{CompositorHandler, CompositorState, Region};
+use smithay_client_toolkit::output::{OutputHandler, OutputState};
+use smithay_client_toolkit::registry::{ProvidesRegistryState, RegistryState};
+use smithay_client_toolkit::shell::wlr_layer::{
+    Anchor, KeyboardInteractivity, Layer, LayerShell as WlrLayerShell, LayerShellHandler,
+    LayerSurface, LayerSurfaceConfigure,
+};
+use smithay_client_toolkit::shell::WaylandSurface;
+use smithay_client_toolkit::{
+    delegate_compositor, delegate_layer, delegate_output, delegate_registry, registry_handlers,
+};
+use wayland_client::backend::WaylandError;
+use wayland_client::globals::registry_queue_init;
+use wayland_client::protocol::{wl_output, wl_surface};
+use wayland_client::{Connection, EventQueue, Proxy, QueueHandle};
+
+/// Whether to overlay through the layer shell: a Wayland session without
+/// an X display to run through (see [`super::linux::use_xwayland`]).
+pub fn wanted() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some() && std::env::var_os("DISPLAY").is_none()
+}
+
+/// One output's overlay surface, ready to render to.
+#[derive(Debug, Clone, Copy)]
+pub struct LayerOverlay {
+    display: NonNull<c_void>,
+    surface: NonNull<c_void>,
+    /// Output's top-left in virtual-desktop pixels.
+    pub position: Vec2,
+    /// Buffer size in pixels.
+    pub width: u32,
+    pub height: u32,
+    /// Output scale factor.
+    pub scale: f32,
+}
+
+impl LayerOverlay {
+    pub fn raw_display_handle(&self) -> RawDisplayHandle {
+        RawDisplayHandle::Wayland(WaylandDisplayHandle::new(self.display))
+    }
+
+    pub fn raw_window_handle(&self) -> RawWindowHandle {
+        RawWindowHandle::Wayland(WaylandWindowHandle::new(self.surface))
+    }
+}
+
+/// The layer-shell connection and an overlay surface per output.
+///
+/// Surfaces rendered through [`LayerOverlay`] handles must be dropped
+/// before this is.
+pub struct LayerShell {
+    conn: Connection,
+    queue: EventQueue<State>,
+    state: State,
+}
+
+impl LayerShell {
+    /// Connect to the compositor and cover every output. `None` (logged)
+    /// when there is no Wayland display or it lacks the layer shell.
+    pub fn connect() -> Option<Self> {
+        let conn = Connection::connect_to_env()
+            .map_err(|e| log::warn!("No Wayland display ({e})"))
+            .ok()?;
+        let (globals, mut queue) = registry_queue_init::<State>(&conn)
+            .map_err(|e| log::warn!("Wayland registry unavailable ({e})"))
+            .ok()?;
+        let qh = queue.handle();
+        let compositor = CompositorState::bind(&globals, &qh)
+            .map_err(|e| log::warn!("No wl_compositor ({e})"))
+            .ok()?;
+        let layer_shell = WlrLayerShell::bind(&globals, &qh)
+            .map_err(|e| log::warn!("Compositor has no wlr-layer-shell ({e})"))
+            .ok()?;
+
+        let mut state = State {
+            registry: RegistryState::new(&globals),
+            outputs: OutputState::new(&globals, &qh),
+            compositor,
+            layer_shell,
+            overlays: Vec::new(),
+            retired: Vec::new(),
+            changed: false,
+        };
+        // Outputs announce themselves, then each new surface gets its size
+        for _ in 0..3 {
+            if let Err(e) = queue.roundtrip(&mut state) {
+                log::warn!("Wayland roundtrip failed ({e})");
+                return None;
+            }
+        }
+        state.changed = false;
+        log::info!("Layer-shell overlay on {} output(s)", state.overlays.len());
+        Some(Self { conn, queue, state })
+    }
+
+    /// Handle whatever the compositor sent since the last call, without
+    /// blocking. Returns whether outputs came, went or changed size, so
+    /// the overlays need rebuilding.
+    pub fn dispatch(&mut self) -> bool {
+        // The overlays were rebuilt after the last call returned true
+        self.state.retired.clear();
+        if let Err(e) = self.queue.flush() {
+            log::error!("Wayland connection failed: {e}");
+            return false;
+        }
+        if let Some(guard) = self.queue.prepare_read() {
+            match guard.read() {
+                Ok(_) => {}
+                Err(WaylandError::Io(e)) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(e) => log::error!("Wayland read failed: {e}"),
+            }
+        }
+        if let Err(e) = self.queue.dispatch_pending(&mut self.state) {
+            log::error!("Wayland dispatch failed: {e}");
+        }
+        std::mem::take(&mut self.state.changed)
+    }
+
+    /// Every output's overlay that the compositor has sized, first output
+    /// first.
+    pub fn overlays(&self) -> impl Iterator<Item = LayerOverlay> + '_ {
+        let display = self.conn.backend().display_ptr() as *mut c_void;
+        self.state.overlays.iter().filter_map(move |o| {
+            let (width, height) = o.size;
+            if width == 0 || height == 0 {
+                return None;
+            }
+            let info = self.state.outputs.info(&o.output);
+            let scale = info.as_ref().map_or(1, |i| i.scale_factor.max(1));
+            let (x, y) = info
+                .map(|i| i.logical_position.unwrap_or(i.location))
+                .unwrap_or_default();
+            Some(LayerOverlay {
+                display: NonNull::new(display)?,
+                surface: NonNull::new(o.layer.wl_surface().id().as_ptr() as *mut c_void)?,
+                position: Vec2::new((x * scale) as f32, (y * scale) as f32),
+                width: width * scale as u32,
+                height: height * scale as u32,
+                scale: scale as f32,
+            })
+        })
+    }
+}
+
+struct Overlay {
+    output: wl_output::WlOutput,
+    layer: LayerSurface,
+    /// Logical size from the latest configure; zero until the first one.
+    size: (u32, u32),
+}
+
+struct State {
+    registry: RegistryState,
+    outputs: OutputState,
+    compositor: CompositorState,
+    layer_shell: WlrLayerShell,
+    overlays: Vec<Overlay>,
+    /// Overlays gone since the last dispatch. Their surfaces stay alive
+    /// until the renderer has dropped its swapchains on them.
+    retired: Vec<Overlay>,
+    /// Set whenever the overlays need rebuilding.
+    changed: bool,
+}
+
+impl State {
+    /// Match the buffer scale to `output`'s, so HiDPI outputs get full
+    /// resolution frames.
+    fn apply_scale(&self, overlay: &Overlay) {
+        if let Some(info) = self.outputs.info(&overlay.output) {
+            overlay
+                .layer
+                .wl_surface()
+                .set_buffer_scale(info.scale_factor.max(1));
+        }
+    }
+
+    /// Take the overlays matching `gone` off screen.
+    fn retire(&mut self, gone: impl Fn(&Overlay) -> bool) {
+        let (retired, kept) = std::mem::take(&mut self.overlays)
+            .into_iter()
+            .partition(gone);
+        self.overlays = kept;
+        self.retired.extend::<Vec<_>>(retired);
+        self.changed = true;
+    }
+}
+
+impl CompositorHandler for State {
+    fn scale_factor_changed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &wl_surface::WlSurface,
+        _: i32,
+    ) {
+    }
+
+    fn transform_changed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &wl_surface::WlSurface,
+        _: wl_output::Transform,
+    ) {
+    }
+
+    fn frame(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &wl_surface::WlSurface, _: u32) {}
+
+    fn surface_enter(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &wl_surface::WlSurface,
+        _: &wl_output::WlOutput,
+    ) {
+    }
+
+    fn surface_leave(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &wl_surface::WlSurface,
+        _: &wl_output::WlOutput,
+    ) {
+    }
+}
+
+impl OutputHandler for State {
+    fn output_state(&mut self) -> &mut OutputState {
+        &mut self.outputs
+    }
+
+    fn new_output(&mut self, _: &Connection, qh: &QueueHandle<Self>, output: wl_output::WlOutput) {
+        let surface = self.compositor.create_surface(qh);
+        let layer = self.layer_shell.create_layer_surface(
+            qh,
+            surface,
+            Layer::Overlay,
+            Some("pettoy"),
+            Some(&output),
+        );
+        layer.set_anchor(Anchor::TOP | Anchor::BOTTOM | Anchor::LEFT | Anchor::RIGHT);
+        // Cover panels too, without pushing anything out of the way
+        layer.set_exclusive_zone(-1);
+        layer.set_keyboard_interactivity(KeyboardInteractivity::None);
+        // An empty input region lets every click through
+        match Region::new(&self.compositor) {
+            Ok(region) => layer.set_input_region(Some(region.wl_region())),
+            Err(e) => log::warn!("Overlay can't be made click-through: {e}"),
+        }
+        // The first commit, without a buffer, asks the compositor for a size
+        layer.commit();
+
+        let overlay = Overlay {
+            output,
+            layer,
+            size: (0, 0),
+        };
+        self.apply_scale(&overlay);
+        self.overlays.push(overlay);
+    }
+
+    fn update_output(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        output: wl_output::WlOutput,
+    ) {
+        if let Some(overlay) = self.overlays.iter().find(|o| o.output == output) {
+            self.apply_scale(overlay);
+            self.changed = true;
+        }
+    }
+
+    fn output_destroyed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        output: wl_output::WlOutput,
+    ) {
+        self.retire(|o| o.output == output);
+    }
+}
+
+impl LayerShellHandler for State {
+    fn closed(&mut self, _: &Connection, _: &QueueHandle<Self>, layer: &LayerSurface) {
+        self.retire(|o| &o.layer == layer);
+    }
+
+    fn configure(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        layer: &LayerSurface,
+        configure: LayerSurfaceConfigure,
+        _: u32,
+    ) {
+        if let Some(overlay) = self.overlays.iter_mut().find(|o| &o.layer == layer) {
+            if overlay.size != configure.new_size {
+                overlay.size = configure.new_size;
+                self.changed = true;
+            }
+        }
+    }
+}
+
+impl ProvidesRegistryState for State {
+    fn registry(&mut self) -> &mut RegistryState {
+        &mut self.registry
+    }
+    registry_handlers![OutputState];
+}
+
+delegate_compositor!(State);
+delegate_output!(State);
+delegate_layer!(State);
+delegate_registry!(State);
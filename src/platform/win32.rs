@@ -1,13 +1,30 @@
+use glam::Vec2;
 use raw_window_handle::{HasWindowHandle, RawWindowHandle};
-use windows::Win32::Foundation::{BOOL, HWND, LPARAM, POINT, RECT, TRUE};
-use windows::Win32::Graphics::Dwm::DwmSetWindowAttribute;
+use windows::core::PWSTR;
+use windows::Win32::Foundation::{CloseHandle, BOOL, HWND, LPARAM, POINT, RECT, TRUE};
+use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DwmSetWindowAttribute, DWMWA_CLOAKED};
+use windows::Win32::Graphics::Gdi::{
+    GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+};
+use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+use windows::Win32::System::Threading::{
+    OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+};
 use windows::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, GetLastInputInfo, LASTINPUTINFO};
 use windows::Win32::UI::WindowsAndMessaging::{
-    EnumWindows, GetCursorPos, GetWindowLongPtrW, GetWindowRect, GetWindowTextW, IsWindowVisible,
+    EnumWindows, GetClassNameW, GetCursorPos, GetForegroundWindow, GetWindowLongPtrW, GetWindowRect,
+    GetWindowTextW, GetWindowThreadProcessId, IsIconic, IsWindowVisible, IsZoomed,
     SetWindowLongPtrW, SetWindowPos, GWL_EXSTYLE, SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE,
     SWP_NOSIZE, SWP_NOZORDER, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
 };
 
+use super::tray::TrayIcon;
+use pettoy::automation::Weekday;
+use pettoy::ecs::systems::window_aware::WindowState;
+use pettoy::input::{Key, Keyboard};
+
+use super::{Platform, TrayCommand, WindowRect};
+
 /// Extract the Win32 HWND from a winit window.
 pub fn get_hwnd(window: &winit::window::Window) -> HWND {
     let handle = window.window_handle().expect("window handle unavailable");
@@ -112,41 +129,60 @@ pub fn get_mouse_pos() -> (f32, f32) {
     (point.x as f32, point.y as f32)
 }
 
-/// Check if the ESC key is currently pressed (works regardless of window focus).
-pub fn is_escape_pressed() -> bool {
-    // VK_ESCAPE = 0x1B. High bit set = key is currently down.
-    unsafe { GetAsyncKeyState(0x1B) & (0x8000u16 as i16) != 0 }
-}
-
-/// Check if F12 was pressed since last call.
-/// Uses low bit (transition) to detect single press, not held state.
-pub fn is_f12_pressed() -> bool {
-    // VK_F12 = 0x7B. Low bit = key was pressed since last call to GetAsyncKeyState.
-    unsafe { GetAsyncKeyState(0x7B) & 1 != 0 }
-}
-
-/// Check if Y key was pressed since last call (yarn ball hotkey).
-pub fn is_y_pressed() -> bool {
-    // VK_Y = 0x59
-    unsafe { GetAsyncKeyState(0x59) & 1 != 0 }
+/// Virtual-key code for a key. Ctrl/Shift/Alt use the side-agnostic codes.
+fn vk(key: Key) -> i32 {
+    let name = key.name().as_bytes();
+    match key {
+        Key::Escape => 0x1B,     // VK_ESCAPE
+        Key::Space => 0x20,      // VK_SPACE
+        Key::Tab => 0x09,        // VK_TAB
+        Key::Enter => 0x0D,      // VK_RETURN
+        Key::Backspace => 0x08,  // VK_BACK
+        Key::Insert => 0x2D,     // VK_INSERT
+        Key::Delete => 0x2E,     // VK_DELETE
+        Key::Home => 0x24,       // VK_HOME
+        Key::End => 0x23,        // VK_END
+        Key::PageUp => 0x21,     // VK_PRIOR
+        Key::PageDown => 0x22,   // VK_NEXT
+        Key::Left => 0x25,       // VK_LEFT
+        Key::Up => 0x26,         // VK_UP
+        Key::Right => 0x27,      // VK_RIGHT
+        Key::Down => 0x28,       // VK_DOWN
+        Key::Pause => 0x13,      // VK_PAUSE
+        Key::ScrollLock => 0x91, // VK_SCROLL
+        Key::Ctrl => 0x11,       // VK_CONTROL
+        Key::Shift => 0x10,      // VK_SHIFT
+        Key::Alt => 0x12,        // VK_MENU
+        Key::Super => 0x5B,      // VK_LWIN
+        // VK_F1..VK_F12 are consecutive from 0x70.
+        _ if name[0] == b'F' && name.len() > 1 => {
+            let n: i32 = key.name()[1..].parse().expect("F-key number");
+            0x70 + n - 1
+        }
+        // Letters and digits are their uppercase ASCII codes.
+        _ => name[0] as i32,
+    }
 }
 
-/// Check if B key was pressed since last call (cardboard box hotkey).
-pub fn is_b_pressed() -> bool {
-    // VK_B = 0x42
-    unsafe { GetAsyncKeyState(0x42) & 1 != 0 }
+/// Every virtual-key code for a key: Super has no side-agnostic code.
+fn vks(key: Key) -> impl Iterator<Item = i32> {
+    let right = (key == Key::Super).then_some(0x5C); // VK_RWIN
+    std::iter::once(vk(key)).chain(right)
 }
 
-/// Check if G key was pressed since last call (water glass hotkey).
-pub fn is_g_pressed() -> bool {
-    // VK_G = 0x47
-    unsafe { GetAsyncKeyState(0x47) & 1 != 0 }
+/// Check if a key is currently held (works regardless of window focus).
+pub fn is_key_down(key: Key) -> bool {
+    // High bit set = key is currently down.
+    vks(key).any(|vk| unsafe { GetAsyncKeyState(vk) & (0x8000u16 as i16) != 0 })
 }
 
-/// Check if F11 was pressed since last call (mode cycle hotkey).
-pub fn is_f11_pressed() -> bool {
-    // VK_F11 = 0x7A
-    unsafe { GetAsyncKeyState(0x7A) & 1 != 0 }
+/// Check if a key was pressed since last call.
+/// Uses low bit (transition) to detect single press, not held state.
+pub fn was_key_pressed(key: Key) -> bool {
+    // Read every code so each one's transition bit is consumed.
+    vks(key).fold(false, |pressed, vk| unsafe {
+        pressed | (GetAsyncKeyState(vk) & 1 != 0)
+    })
 }
 
 /// Get seconds since last user input (keyboard/mouse, system-wide).
@@ -175,6 +211,23 @@ pub fn get_local_hour() -> f32 {
     }
 }
 
+/// Get the local day of the week.
+pub fn get_weekday() -> Weekday {
+    let st = unsafe { windows::Win32::System::SystemInformation::GetLocalTime() };
+    // wDayOfWeek counts from Sunday
+    Weekday::from_index(((st.wDayOfWeek + 6) % 7) as u8).unwrap_or_default()
+}
+
+/// Battery charge in percent while running on battery, `None` on AC power,
+/// without a battery or when Windows doesn't know.
+pub fn get_battery() -> Option<f32> {
+    let mut status = SYSTEM_POWER_STATUS::default();
+    unsafe { GetSystemPowerStatus(&mut status) }.ok()?;
+    // ACLineStatus: 0 offline, 1 online, 255 unknown; 255% is unknown too
+    (status.ACLineStatus == 0 && status.BatteryLifePercent <= 100)
+        .then_some(status.BatteryLifePercent as f32)
+}
+
 /// Get mouse button states. Returns (left_down, right_down, middle_down).
 /// All buttons check both held state (high bit) and transition bit (low bit)
 /// to catch quick clicks that release between polls (e.g. right-click opening
@@ -196,41 +249,34 @@ pub fn get_mouse_buttons() -> (bool, bool, bool) {
 // Window Enumeration (Task #19)
 // ---------------------------------------------------------------------------
 
-/// A rectangle representing a visible desktop window.
-#[derive(Debug, Clone)]
-pub struct WindowRect {
-    pub x: i32,
-    pub y: i32,
-    pub w: i32,
-    pub h: i32,
-    pub title: String,
-}
-
-/// Enumerate all visible, non-tool windows on the desktop.
-/// Excludes our own overlay and windows with zero area.
+/// Enumerate all visible windows on the desktop, topmost first. Tool
+/// windows (tooltips, floating toolbars, etc.) come back marked as such.
+/// Excludes our own overlays and windows with zero area.
 /// Call this periodically (e.g., every few seconds), NOT every frame.
-pub fn enumerate_windows(own_hwnd: HWND) -> Vec<WindowRect> {
-    struct EnumState {
-        own_hwnd: HWND,
+pub fn enumerate_windows(overlays: &[HWND]) -> Vec<WindowRect> {
+    struct EnumState<'a> {
+        overlays: &'a [HWND],
+        foreground: HWND,
         results: Vec<WindowRect>,
     }
 
     unsafe extern "system" fn enum_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
         let state = &mut *(lparam.0 as *mut EnumState);
 
-        // Skip our own window
-        if hwnd == state.own_hwnd {
+        // Skip our own windows
+        if state.overlays.contains(&hwnd) {
             return TRUE;
         }
 
-        // Skip invisible windows
+        // Skip invisible windows, and cloaked ones: "visible" but kept off
+        // screen by DWM (suspended UWP apps, other virtual desktops)
         if !IsWindowVisible(hwnd).as_bool() {
             return TRUE;
         }
-
-        // Skip tool windows (tooltips, floating toolbars, etc.)
-        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
-        if (ex_style as u32) & WS_EX_TOOLWINDOW.0 != 0 {
+        let mut cloaked = 0u32;
+        let cloaked_ptr = &mut cloaked as *mut u32 as *mut std::ffi::c_void;
+        let size = std::mem::size_of::<u32>() as u32;
+        if DwmGetWindowAttribute(hwnd, DWMWA_CLOAKED, cloaked_ptr, size).is_ok() && cloaked != 0 {
             return TRUE;
         }
 
@@ -257,19 +303,54 @@ pub fn enumerate_windows(own_hwnd: HWND) -> Vec<WindowRect> {
             String::new()
         };
 
+        let ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        let tool = (ex_style as u32) & WS_EX_TOOLWINDOW.0 != 0;
+        let shell = is_shell(hwnd);
+
+        // Fullscreen = an application window covering its whole monitor,
+        // taskbar included (the desktop window always does)
+        let mut monitor = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        let hmonitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
+        let fullscreen = !tool
+            && !shell
+            && GetMonitorInfoW(hmonitor, &mut monitor).as_bool()
+            && monitor.rcMonitor == rect;
+
+        // Minimized windows are parked off screen; they take up no room
+        let minimized = IsIconic(hwnd).as_bool();
+        let (x, y, w, h) = if minimized {
+            (0, 0, 0, 0)
+        } else {
+            (rect.left, rect.top, w, h)
+        };
+
         state.results.push(WindowRect {
-            x: rect.left,
-            y: rect.top,
+            id: hwnd.0 as u64,
+            x,
+            y,
             w,
             h,
+            state: WindowState {
+                focused: hwnd == state.foreground,
+                minimized,
+                maximized: IsZoomed(hwnd).as_bool(),
+                fullscreen,
+                tool,
+                shell,
+            },
             title,
+            process: process_name(hwnd),
         });
 
         TRUE
     }
 
     let mut state = EnumState {
-        own_hwnd,
+        overlays,
+        foreground: unsafe { GetForegroundWindow() },
         results: Vec::with_capacity(64),
     };
 
@@ -282,3 +363,106 @@ pub fn enumerate_windows(own_hwnd: HWND) -> Vec<WindowRect> {
 
     state.results
 }
+
+/// Whether `hwnd` belongs to the shell itself: the desktop behind every
+/// window (`Progman`, or `WorkerW` once a wallpaper slideshow ran) or a
+/// taskbar.
+fn is_shell(hwnd: HWND) -> bool {
+    let mut buf = [0u16; 64];
+    let len = unsafe { GetClassNameW(hwnd, &mut buf) };
+    let class = String::from_utf16_lossy(&buf[..len.max(0) as usize]);
+    matches!(
+        class.as_str(),
+        "Progman" | "WorkerW" | "Shell_TrayWnd" | "Shell_SecondaryTrayWnd"
+    )
+}
+
+/// Executable file name of the process that owns `hwnd` (e.g.
+/// `notepad.exe`), or empty if it can't be queried.
+unsafe fn process_name(hwnd: HWND) -> String {
+    let mut pid = 0;
+    GetWindowThreadProcessId(hwnd, Some(&mut pid));
+    let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+        return String::new();
+    };
+    let mut buf = [0u16; 260];
+    let mut len = buf.len() as u32;
+    let name = PWSTR(buf.as_mut_ptr());
+    let ok = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, name, &mut len);
+    let _ = CloseHandle(process);
+    if ok.is_err() {
+        return String::new();
+    }
+    let path = String::from_utf16_lossy(&buf[..len as usize]);
+    path.rsplit('\\').next().unwrap_or_default().to_owned()
+}
+
+// ---------------------------------------------------------------------------
+// Platform
+// ---------------------------------------------------------------------------
+
+/// [`Platform`] backed by the functions above plus the system tray icon.
+pub struct Win32Platform {
+    /// Our overlays (one per monitor), excluded from window enumeration.
+    overlays: Vec<HWND>,
+    tray: TrayIcon,
+}
+
+impl Win32Platform {
+    pub fn new() -> Self {
+        Self {
+            overlays: Vec::new(),
+            tray: TrayIcon::new(),
+        }
+    }
+}
+
+impl Keyboard for Win32Platform {
+    fn key_down(&mut self, key: Key) -> bool {
+        is_key_down(key)
+    }
+
+    fn key_pressed(&mut self, key: Key) -> bool {
+        was_key_pressed(key)
+    }
+}
+
+impl Platform for Win32Platform {
+    fn setup_overlay(&mut self, window: &winit::window::Window) {
+        self.overlays.push(get_hwnd(window));
+        setup_overlay(window);
+    }
+
+    fn mouse_pos(&mut self) -> Vec2 {
+        let (x, y) = get_mouse_pos();
+        Vec2::new(x, y)
+    }
+
+    fn mouse_buttons(&mut self) -> (bool, bool, bool) {
+        get_mouse_buttons()
+    }
+
+    fn idle_seconds(&mut self) -> f64 {
+        get_idle_time()
+    }
+
+    fn local_hour(&mut self) -> f32 {
+        get_local_hour()
+    }
+
+    fn weekday(&mut self) -> Weekday {
+        get_weekday()
+    }
+
+    fn battery(&mut self) -> Option<f32> {
+        get_battery()
+    }
+
+    fn enumerate_windows(&mut self) -> Vec<WindowRect> {
+        enumerate_windows(&self.overlays)
+    }
+
+    fn poll_tray(&mut self) -> TrayCommand {
+        self.tray.poll()
+    }
+}
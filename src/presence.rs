@@ -0,0 +1,197 @@
+//! Getting out of the way.
+//!
+//! The overlay draws over everything, which is the last thing anyone wants
+//! during a fullscreen game, a video or a screen share. [`Presence`] watches
+//! the desktop windows for those, along with a manual do-not-disturb switch,
+//! and fades the cats out while any of them holds. Once the cats are fully
+//! faded out the front-end stops simulating and drawing until they can come
+//! back.
+
+use crate::config::PresenceConfig;
+use crate::ecs::systems::window_aware::DesktopWindow;
+
+/// Why the cats are staying out of sight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Quiet {
+    /// The user switched on do-not-disturb.
+    DoNotDisturb,
+    /// The focused application window fills its monitor.
+    Fullscreen,
+    /// A program from `presence.apps` has a window open.
+    App(String),
+}
+
+/// Whether the cats should be out, and how far they've faded.
+#[derive(Debug)]
+pub struct Presence {
+    pub do_not_disturb: bool,
+    /// What the latest window list called for.
+    detected: Option<Quiet>,
+    /// 1 with the cats fully shown, 0 once they've faded out.
+    visibility: f32,
+}
+
+impl Default for Presence {
+    fn default() -> Self {
+        Self {
+            do_not_disturb: false,
+            detected: None,
+            visibility: 1.0,
+        }
+    }
+}
+
+impl Presence {
+    /// Look for fullscreen windows and listed apps in `windows`. Returns
+    /// whether that changed why (or whether) the cats stay away.
+    pub fn check(&mut self, windows: &[DesktopWindow], config: &PresenceConfig) -> bool {
+        // Clicking the desktop focuses a window that always fills the
+        // screen, as can overlays and palettes; none of those are a game
+        let fullscreen = config.fullscreen
+            && windows.iter().any(|w| {
+                let s = w.state;
+                s.focused && s.fullscreen && !s.minimized && !s.tool && !s.shell
+            });
+        let detected = if fullscreen {
+            Some(Quiet::Fullscreen)
+        } else {
+            windows.iter().filter(|w| !w.state.minimized).find_map(|w| {
+                let name = app_name(&w.process);
+                let listed = config.apps.iter().any(|a| app_name(a) == name);
+                (listed && !name.is_empty()).then_some(Quiet::App(name))
+            })
+        };
+        let changed = detected != self.detected;
+        self.detected = detected;
+        changed
+    }
+
+    /// Why the cats are away, if they are.
+    pub fn quiet(&self) -> Option<&Quiet> {
+        if self.do_not_disturb {
+            Some(&Quiet::DoNotDisturb)
+        } else {
+            self.detected.as_ref()
+        }
+    }
+
+    /// Fade toward hidden or shown, whichever is called for, over
+    /// `presence.fade_time`.
+    pub fn advance(&mut self, dt: f32, config: &PresenceConfig) {
+        let target = if self.quiet().is_some() { 0.0 } else { 1.0 };
+        if config.fade_time <= 0.0 {
+            self.visibility = target;
+            return;
+        }
+        let step = dt / config.fade_time;
+        self.visibility = if target < self.visibility {
+            (self.visibility - step).max(target)
+        } else {
+            (self.visibility + step).min(target)
+        };
+    }
+
+    /// 1 with the cats fully shown, 0 once they've faded out.
+    pub fn visibility(&self) -> f32 {
+        self.visibility
+    }
+
+    /// Faded out completely: nothing to simulate or draw.
+    pub fn hidden(&self) -> bool {
+        self.visibility <= 0.0
+    }
+}
+
+/// Process name as compared against `presence.apps` (and mode rules):
+/// lowercase, without any `.exe`.
+pub(crate) fn app_name(process: &str) -> String {
+    let name = process.to_lowercase();
+    match name.strip_suffix(".exe") {
+        Some(stem) => stem.to_owned(),
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::systems::window_aware::WindowState;
+
+    fn window(process: &str, state: WindowState) -> DesktopWindow {
+        DesktopWindow {
+            right: 800.0,
+            bottom: 600.0,
+            state,
+            process: process.into(),
+            ..DesktopWindow::default()
+        }
+    }
+
+    #[test]
+    fn fullscreen_focus_fades_the_cats_out_and_back() {
+        let config = PresenceConfig::default();
+        let mut presence = Presence::default();
+        let game = WindowState {
+            focused: true,
+            fullscreen: true,
+            ..WindowState::default()
+        };
+
+        assert!(presence.check(&[window("game.exe", game)], &config));
+        assert_eq!(presence.quiet(), Some(&Quiet::Fullscreen));
+        presence.advance(0.5, &config);
+        assert_eq!(presence.visibility(), 0.5);
+        presence.advance(0.6, &config);
+        assert!(presence.hidden());
+
+        // Alt-tabbed away: the game is still fullscreen, just not in front
+        let backgrounded = WindowState {
+            focused: false,
+            ..game
+        };
+        assert!(presence.check(&[window("game.exe", backgrounded)], &config));
+        assert_eq!(presence.quiet(), None);
+        presence.advance(2.0, &config);
+        assert_eq!(presence.visibility(), 1.0);
+    }
+
+    #[test]
+    fn a_focused_desktop_or_tool_window_is_no_fullscreen_app() {
+        let config = PresenceConfig::default();
+        let mut presence = Presence::default();
+        let full = WindowState {
+            focused: true,
+            fullscreen: true,
+            ..WindowState::default()
+        };
+        let desktop = WindowState { shell: true, ..full };
+        let toolbar = WindowState { tool: true, ..full };
+
+        assert!(!presence.check(&[window("explorer.exe", desktop)], &config));
+        assert!(!presence.check(&[window("recorder", toolbar)], &config));
+        assert_eq!(presence.quiet(), None);
+    }
+
+    #[test]
+    fn listed_apps_and_do_not_disturb_send_the_cats_away() {
+        let config = PresenceConfig {
+            apps: vec!["OBS64.exe".into()],
+            ..PresenceConfig::default()
+        };
+        let mut presence = Presence::default();
+        let plain = WindowState::default();
+        let minimized = WindowState {
+            minimized: true,
+            ..plain
+        };
+
+        assert!(!presence.check(&[window("editor", plain)], &config));
+        assert!(!presence.check(&[window("obs64", minimized)], &config));
+        assert!(presence.check(&[window("obs64", plain)], &config));
+        assert_eq!(presence.quiet(), Some(&Quiet::App("obs64".into())));
+
+        presence.check(&[], &config);
+        presence.do_not_disturb = true;
+        assert_eq!(presence.quiet(), Some(&Quiet::DoNotDisturb));
+    }
+}
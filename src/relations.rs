@@ -0,0 +1,179 @@
+//! Cat-to-cat relationships: one affinity per pair of cats, keyed by their
+//! stable [`CatId`]s so it survives despawns and saves.
+//!
+//! Affinity runs from -1.0 (sworn rivals) to 1.0 (best friends). Playing,
+//! napping in the same pile and sharing gifts raise it; fights and chases
+//! lower it; everything slowly fades back toward indifference.
+//! `systems::interaction` feeds it and reads it back to bias who plays,
+//! fights, piles and parades with whom.
+
+use std::collections::HashMap;
+
+use crate::ecs::components::CatId;
+
+/// Affinity gained per second of playing together.
+pub const PLAY_BOND: f32 = 0.05;
+/// Affinity gained per second asleep in the same pile.
+pub const PILE_BOND: f32 = 0.03;
+/// Affinity gained with each cat nearby when a gift is delivered.
+pub const GIFT_BOND: f32 = 0.1;
+/// Affinity lost when a fight starts.
+pub const FIGHT_GRUDGE: f32 = 0.25;
+/// Affinity lost when one cat starts chasing another.
+pub const CHASE_GRUDGE: f32 = 0.05;
+/// Affinity lost when a cat hisses another off its territory.
+pub const HISS_GRUDGE: f32 = 0.05;
+/// Affinity fading per second — about an hour to forget a best friend.
+const FORGET_RATE: f32 = 1.0 / 3600.0;
+
+/// At or above this, two cats are friends.
+pub const FRIEND: f32 = 0.3;
+/// At or below this, two cats are rivals.
+pub const RIVAL: f32 = -0.3;
+
+/// Every pair of cats that has an opinion of each other.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Relationships {
+    pairs: HashMap<(CatId, CatId), f32>,
+}
+
+/// Pairs are stored once, lower id first.
+fn key(a: CatId, b: CatId) -> (CatId, CatId) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+impl Relationships {
+    /// How `a` and `b` feel about each other; 0.0 for strangers.
+    pub fn affinity(&self, a: CatId, b: CatId) -> f32 {
+        self.pairs.get(&key(a, b)).copied().unwrap_or(0.0)
+    }
+
+    /// [`Self::affinity`] for cats that may not have ids (always strangers).
+    pub fn between(&self, a: Option<CatId>, b: Option<CatId>) -> f32 {
+        match (a, b) {
+            (Some(a), Some(b)) => self.affinity(a, b),
+            _ => 0.0,
+        }
+    }
+
+    /// Nudge the pair's affinity by `delta`, clamped to [-1.0, 1.0].
+    pub fn adjust(&mut self, a: CatId, b: CatId, delta: f32) {
+        if a == b {
+            return;
+        }
+        let affinity = self.pairs.entry(key(a, b)).or_insert(0.0);
+        *affinity = (*affinity + delta).clamp(-1.0, 1.0);
+    }
+
+    /// [`Self::adjust`] by entity, for cats that have a [`CatId`].
+    pub fn adjust_cats(
+        &mut self,
+        world: &hecs::World,
+        a: hecs::Entity,
+        b: hecs::Entity,
+        delta: f32,
+    ) {
+        let (Ok(id_a), Ok(id_b)) = (world.get::<&CatId>(a), world.get::<&CatId>(b)) else {
+            return;
+        };
+        self.adjust(*id_a, *id_b, delta);
+    }
+
+    /// Set a pair's affinity outright (save loading).
+    pub fn set(&mut self, a: CatId, b: CatId, affinity: f32) {
+        if a != b {
+            self.pairs.insert(key(a, b), affinity.clamp(-1.0, 1.0));
+        }
+    }
+
+    /// Fade every relationship toward indifference, dropping the ones that
+    /// get there.
+    pub fn decay(&mut self, dt: f32) {
+        let step = FORGET_RATE * dt;
+        self.pairs.retain(|_, affinity| {
+            *affinity -= affinity.signum() * step.min(affinity.abs());
+            *affinity != 0.0
+        });
+    }
+
+    /// Drop everything involving a cat that's gone for good.
+    pub fn forget(&mut self, id: CatId) {
+        self.pairs.retain(|&(a, b), _| a != id && b != id);
+    }
+
+    /// Everyone `id` has feelings about, warmest first.
+    pub fn of(&self, id: CatId) -> Vec<(CatId, f32)> {
+        let mut others: Vec<(CatId, f32)> = self
+            .pairs
+            .iter()
+            .filter_map(|(&(a, b), &affinity)| {
+                if a == id {
+                    Some((b, affinity))
+                } else if b == id {
+                    Some((a, affinity))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        others.sort_by(|x, y| y.1.total_cmp(&x.1).then(x.0.cmp(&y.0)));
+        others
+    }
+
+    /// Every pair and its affinity, ordered by id (stable for saves).
+    pub fn pairs(&self) -> Vec<(CatId, CatId, f32)> {
+        let mut pairs: Vec<(CatId, CatId, f32)> =
+            self.pairs.iter().map(|(&(a, b), &f)| (a, b, f)).collect();
+        pairs.sort_by_key(|&(a, b, _)| (a, b));
+        pairs
+    }
+
+    /// Number of pairs with an opinion.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn affinity_is_symmetric_and_bounded() {
+        let mut rel = Relationships::default();
+        rel.adjust(CatId(2), CatId(1), 0.7);
+        rel.adjust(CatId(1), CatId(2), 0.7);
+        rel.adjust(CatId(1), CatId(3), -FIGHT_GRUDGE);
+        rel.adjust(CatId(4), CatId(4), 1.0);
+
+        assert_eq!(rel.affinity(CatId(1), CatId(2)), 1.0);
+        assert_eq!(rel.affinity(CatId(2), CatId(1)), 1.0);
+        assert_eq!(rel.len(), 2, "no opinions about yourself");
+        assert_eq!(
+            rel.of(CatId(1)),
+            [(CatId(2), 1.0), (CatId(3), -FIGHT_GRUDGE)]
+        );
+        assert_eq!(rel.between(Some(CatId(1)), None), 0.0);
+    }
+
+    #[test]
+    fn grudges_and_friendships_fade() {
+        let mut rel = Relationships::default();
+        rel.adjust(CatId(1), CatId(2), 0.5);
+        rel.adjust(CatId(1), CatId(3), -0.001);
+        rel.decay(900.0);
+
+        assert!((rel.affinity(CatId(1), CatId(2)) - 0.25).abs() < 0.001);
+        assert_eq!(rel.len(), 1, "forgotten pairs are dropped");
+        rel.forget(CatId(2));
+        assert!(rel.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
-/// GPU pipeline for rendering the cursor heatmap as a fullscreen overlay.
+//! GPU pipeline for rendering the cursor heatmap as a fullscreen overlay.
+//! The texture covers the whole desktop; each monitor samples its own slice.
 
 const HEATMAP_SIZE: u32 = 64;
 
@@ -9,7 +10,11 @@ pub struct HeatmapPipeline {
 }
 
 impl HeatmapPipeline {
-    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        view_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("heatmap_shader"),
             source: wgpu::ShaderSource::Wgsl(include_str!("shaders/heatmap.wgsl").into()),
@@ -80,7 +85,7 @@ impl HeatmapPipeline {
 
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("heatmap_pipeline_layout"),
-            bind_group_layouts: &[&bind_group_layout],
+            bind_group_layouts: &[&bind_group_layout, view_layout],
             push_constant_ranges: &[],
         });
 
@@ -1,10 +1,15 @@
 use bytemuck::{Pod, Zeroable};
 use glam::Vec2;
 
-use crate::ecs::components::{Appearance, BehaviorState, CatState, Position, PrevPosition};
+use pettoy::ecs::components::{Appearance, BehaviorState, CatState, Position, PrevPosition};
+
+use super::occlusion::{ClipRect, MAX_OCCLUDERS};
+
+/// Clip rectangles for an instance drawn over everything.
+pub const UNCLIPPED: [ClipRect; MAX_OCCLUDERS] = [[0.0; 4]; MAX_OCCLUDERS];
 
 /// Per-instance data uploaded to GPU each frame.
-/// Stride = 28 bytes.
+/// Stride = 60 bytes.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct CatInstance {
@@ -18,6 +23,9 @@ pub struct CatInstance {
     pub frame: u32,
     /// Rotation angle in radians (used for spawn somersault).
     pub rotation: f32,
+    /// Windows in front of this instance (see `render::occlusion`); the
+    /// parts inside them aren't drawn.
+    pub clip: [ClipRect; MAX_OCCLUDERS],
 }
 
 impl CatInstance {
@@ -70,6 +78,7 @@ impl CatInstance {
             color: appearance.color,
             frame,
             rotation: 0.0,
+            clip: UNCLIPPED,
         }
     }
 }
@@ -1,22 +1,108 @@
 pub mod heatmap_pipeline;
 pub mod instance;
+pub mod occlusion;
 pub mod pipeline;
 pub mod trail;
 
 use std::sync::Arc;
-use winit::window::Window;
+
+use bytemuck::{Pod, Zeroable};
+use glam::Vec2;
+use wgpu::util::DeviceExt;
+use winit::window::{Window, WindowId};
 
 use self::heatmap_pipeline::HeatmapPipeline;
 use self::instance::CatInstance;
 use self::pipeline::CatPipeline;
 use self::trail::{TrailPipeline, TrailVertex};
 
-/// Core GPU state — device, queue, surface, pipeline.
+/// Which slice of the colony a surface shows. Mirrors `View` in the shaders.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct ViewUniform {
+    /// Top-left of the monitor in colony pixels.
+    origin: [f32; 2],
+    /// Surface size in pixels.
+    size: [f32; 2],
+    /// Size of the whole desktop, for the heatmap texture.
+    desktop_size: [f32; 2],
+    /// Monitor DPI scale factor.
+    scale: f32,
+    _pad: f32,
+}
+
+/// What a monitor's overlay is presented in.
+pub enum Target {
+    /// An overlay window.
+    Window(Arc<Window>),
+    /// A layer-shell surface on Wayland.
+    #[cfg(all(target_os = "linux", feature = "wayland"))]
+    Layer(crate::platform::wayland::LayerOverlay),
+}
+
+impl Target {
+    /// The overlay window, unless this is a layer surface.
+    pub fn window(&self) -> Option<&Arc<Window>> {
+        match self {
+            Target::Window(window) => Some(window),
+            #[cfg(all(target_os = "linux", feature = "wayland"))]
+            Target::Layer(_) => None,
+        }
+    }
+
+    fn size(&self) -> winit::dpi::PhysicalSize<u32> {
+        match self {
+            Target::Window(window) => window.inner_size(),
+            #[cfg(all(target_os = "linux", feature = "wayland"))]
+            Target::Layer(overlay) => winit::dpi::PhysicalSize::new(overlay.width, overlay.height),
+        }
+    }
+
+    fn create_surface(
+        &self,
+        instance: &wgpu::Instance,
+    ) -> Result<wgpu::Surface<'static>, wgpu::CreateSurfaceError> {
+        match self {
+            Target::Window(window) => instance.create_surface(window.clone()),
+            // SAFETY: the layer shell keeps the surface alive until the app
+            // has dropped every `MonitorSurface` drawing to it.
+            #[cfg(all(target_os = "linux", feature = "wayland"))]
+            Target::Layer(overlay) => unsafe {
+                instance.create_surface_unsafe(wgpu::SurfaceTargetUnsafe::RawHandle {
+                    raw_display_handle: overlay.raw_display_handle(),
+                    raw_window_handle: overlay.raw_window_handle(),
+                })
+            },
+        }
+    }
+}
+
+/// One monitor's overlay and swapchain.
+pub struct MonitorSurface {
+    pub target: Target,
+    surface: wgpu::Surface<'static>,
+    pub config: wgpu::SurfaceConfiguration,
+    view_buffer: wgpu::Buffer,
+    view_bind_group: wgpu::BindGroup,
+    /// Monitor's top-left in virtual-desktop pixels.
+    pub position: Vec2,
+    /// Monitor DPI scale factor.
+    pub scale: f32,
+}
+
+/// Core GPU state — device, queue, pipelines, and a surface per monitor.
 pub struct GpuState {
+    instance: wgpu::Instance,
+    adapter: wgpu::Adapter,
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
-    pub surface: wgpu::Surface<'static>,
-    pub surface_config: wgpu::SurfaceConfiguration,
+    /// Swapchain format every surface shares (the pipelines are built for it).
+    pub format: wgpu::TextureFormat,
+    alpha_mode: wgpu::CompositeAlphaMode,
+    present_mode: wgpu::PresentMode,
+    view_layout: wgpu::BindGroupLayout,
+    /// The primary monitor's surface comes first.
+    pub surfaces: Vec<MonitorSurface>,
     pub cat_pipeline: CatPipeline,
     pub trail_pipeline: TrailPipeline,
     pub heatmap_pipeline: HeatmapPipeline,
@@ -24,20 +110,29 @@ pub struct GpuState {
 
 /// Intermediate frame state returned by `begin_frame`.
 pub struct FrameContext {
+    /// Index into [`GpuState::surfaces`].
+    pub surface: usize,
     pub output: wgpu::SurfaceTexture,
     pub view: wgpu::TextureView,
     pub encoder: wgpu::CommandEncoder,
 }
 
 impl GpuState {
-    /// Initialize wgpu and the cat rendering pipeline.
-    pub fn new(window: Arc<Window>) -> Self {
-        let size = window.inner_size();
+    /// Initialize wgpu and the render pipelines around the primary monitor's
+    /// overlay. Add the other monitors with [`Self::add_surface`].
+    pub fn new(target: Target, position: Vec2, scale: f32) -> Self {
+        let size = target.size();
 
         // DX12 only — Vulkan WSI on Windows doesn't support transparent composition.
         // Use DirectComposition presentation for per-pixel alpha transparency.
+        // Elsewhere the ARGB window visual handles it, so any native backend works.
+        let backends = if cfg!(windows) {
+            wgpu::Backends::DX12
+        } else {
+            wgpu::Backends::VULKAN | wgpu::Backends::GL
+        };
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::DX12,
+            backends,
             backend_options: wgpu::BackendOptions {
                 dx12: wgpu::Dx12BackendOptions {
                     presentation_system: wgpu_types::Dx12SwapchainKind::DxgiFromVisual,
@@ -48,8 +143,8 @@ impl GpuState {
             ..Default::default()
         });
 
-        let surface = instance
-            .create_surface(window)
+        let surface = target
+            .create_surface(&instance)
             .expect("failed to create wgpu surface");
 
         let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
@@ -118,59 +213,152 @@ impl GpuState {
             alpha_mode,
         );
 
-        let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format,
-            width: size.width.max(1),
-            height: size.height.max(1),
-            present_mode,
-            alpha_mode,
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
-        };
-        surface.configure(&device, &surface_config);
+        // Per-monitor view uniform, shared by every pipeline
+        let view_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("view_uniform_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
 
         // Create the cat rendering pipeline
-        let cat_pipeline = CatPipeline::new(&device, format);
+        let cat_pipeline = CatPipeline::new(&device, format, &view_layout);
 
-        // Create the trail rendering pipeline (shares screen_size uniform)
-        let trail_pipeline = TrailPipeline::new(
-            &device,
-            format,
-            &cat_pipeline.screen_uniform_buffer,
-        );
+        // Create the trail rendering pipeline
+        let trail_pipeline = TrailPipeline::new(&device, format, &view_layout);
 
         // Create the heatmap rendering pipeline
-        let heatmap_pipeline = HeatmapPipeline::new(&device, format);
-
-        // Set initial screen size uniform
-        cat_pipeline.update_screen_size(
-            &queue,
-            surface_config.width as f32,
-            surface_config.height as f32,
-        );
+        let heatmap_pipeline = HeatmapPipeline::new(&device, format, &view_layout);
 
-        Self {
+        let mut gpu = Self {
+            instance,
+            adapter,
             device,
             queue,
-            surface,
-            surface_config,
+            format,
+            alpha_mode,
+            present_mode,
+            view_layout,
+            surfaces: Vec::new(),
             cat_pipeline,
             trail_pipeline,
             heatmap_pipeline,
+        };
+        gpu.push_surface(target, surface, size, position, scale);
+        gpu
+    }
+
+    /// Add the overlay for another monitor. Returns false (and the monitor
+    /// stays dark) if its surface can't use the shared format.
+    pub fn add_surface(&mut self, target: Target, position: Vec2, scale: f32) -> bool {
+        let surface = match target.create_surface(&self.instance) {
+            Ok(surface) => surface,
+            Err(e) => {
+                log::warn!("No surface for monitor at {position}: {e}");
+                return false;
+            }
+        };
+        if !surface
+            .get_capabilities(&self.adapter)
+            .formats
+            .contains(&self.format)
+        {
+            log::warn!(
+                "Monitor at {position} doesn't support {:?}; skipping it",
+                self.format
+            );
+            return false;
         }
+        let size = target.size();
+        self.push_surface(target, surface, size, position, scale);
+        true
     }
 
-    /// Resize the surface.
-    pub fn resize(&mut self, width: u32, height: u32) {
+    fn push_surface(
+        &mut self,
+        target: Target,
+        surface: wgpu::Surface<'static>,
+        size: winit::dpi::PhysicalSize<u32>,
+        position: Vec2,
+        scale: f32,
+    ) {
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: self.format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: self.present_mode,
+            alpha_mode: self.alpha_mode,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        surface.configure(&self.device, &config);
+
+        let view_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("view_uniform_buffer"),
+                contents: bytemuck::bytes_of(&ViewUniform::zeroed()),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            });
+        let view_bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("view_bind_group"),
+            layout: &self.view_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: view_buffer.as_entire_binding(),
+            }],
+        });
+
+        self.surfaces.push(MonitorSurface {
+            target,
+            surface,
+            config,
+            view_buffer,
+            view_bind_group,
+            position,
+            scale,
+        });
+    }
+
+    /// Resize the surface belonging to `window`.
+    pub fn resize(&mut self, window: WindowId, width: u32, height: u32) {
         if width == 0 || height == 0 {
             return;
         }
-        self.surface_config.width = width;
-        self.surface_config.height = height;
-        self.surface.configure(&self.device, &self.surface_config);
-        self.cat_pipeline
-            .update_screen_size(&self.queue, width as f32, height as f32);
+        let Some(s) = self
+            .surfaces
+            .iter_mut()
+            .find(|s| s.target.window().is_some_and(|w| w.id() == window))
+        else {
+            return;
+        };
+        s.config.width = width;
+        s.config.height = height;
+        s.surface.configure(&self.device, &s.config);
+    }
+
+    /// Point every surface at its slice of the colony. `origin` is the
+    /// virtual-desktop position of colony (0, 0).
+    pub fn update_views(&self, origin: Vec2, desktop_size: Vec2) {
+        for s in &self.surfaces {
+            let view = ViewUniform {
+                origin: (s.position - origin).to_array(),
+                size: [s.config.width as f32, s.config.height as f32],
+                desktop_size: desktop_size.to_array(),
+                scale: s.scale,
+                _pad: 0.0,
+            };
+            self.queue
+                .write_buffer(&s.view_buffer, 0, bytemuck::bytes_of(&view));
+        }
     }
 
     /// Upload instance data for this frame.
@@ -191,20 +379,22 @@ impl GpuState {
 
     /// Change the present mode at runtime.
     pub fn set_present_mode(&mut self, mode: wgpu::PresentMode) {
-        self.surface_config.present_mode = mode;
-        self.surface
-            .configure(&self.device, &self.surface_config);
+        self.present_mode = mode;
+        for s in &mut self.surfaces {
+            s.config.present_mode = mode;
+            s.surface.configure(&self.device, &s.config);
+        }
         log::info!("Present mode changed to {:?}", mode);
     }
 
-    /// Acquire the next surface texture and create a command encoder.
+    /// Acquire the next texture of surface `index` and create a command encoder.
     /// Returns None if the surface is lost/outdated (caller should skip this frame).
-    pub fn begin_frame(&self) -> Option<FrameContext> {
-        let output = match self.surface.get_current_texture() {
+    pub fn begin_frame(&self, index: usize) -> Option<FrameContext> {
+        let s = &self.surfaces[index];
+        let output = match s.surface.get_current_texture() {
             Ok(output) => output,
             Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
-                self.surface
-                    .configure(&self.device, &self.surface_config);
+                s.surface.configure(&self.device, &s.config);
                 return None;
             }
             Err(wgpu::SurfaceError::OutOfMemory) => {
@@ -228,6 +418,7 @@ impl GpuState {
             });
 
         Some(FrameContext {
+            surface: index,
             output,
             view,
             encoder,
@@ -235,11 +426,11 @@ impl GpuState {
     }
 
     /// Draw heatmap overlay (before trails and cats so it's behind everything).
-    pub fn draw_heatmap(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
-        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+    pub fn draw_heatmap(&self, frame: &mut FrameContext) {
+        let mut render_pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("heatmap_render_pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view,
+                view: &frame.view,
                 resolve_target: None,
                 depth_slice: None,
                 ops: wgpu::Operations {
@@ -254,20 +445,21 @@ impl GpuState {
 
         render_pass.set_pipeline(&self.heatmap_pipeline.pipeline);
         render_pass.set_bind_group(0, &self.heatmap_pipeline.bind_group, &[]);
+        render_pass.set_bind_group(1, &self.surfaces[frame.surface].view_bind_group, &[]);
         render_pass.draw(0..3, 0..1); // fullscreen triangle
     }
 
     /// Draw cat trails (between clear and cats, so trails are behind cats).
-    pub fn draw_trails(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+    pub fn draw_trails(&self, frame: &mut FrameContext) {
         let p = &self.trail_pipeline;
         if p.num_vertices == 0 {
             return;
         }
 
-        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        let mut render_pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("trail_render_pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view,
+                view: &frame.view,
                 resolve_target: None,
                 depth_slice: None,
                 ops: wgpu::Operations {
@@ -281,17 +473,17 @@ impl GpuState {
         });
 
         render_pass.set_pipeline(&p.pipeline);
-        render_pass.set_bind_group(0, &p.screen_bind_group, &[]);
+        render_pass.set_bind_group(0, &self.surfaces[frame.surface].view_bind_group, &[]);
         render_pass.set_vertex_buffer(0, p.vertex_buffer.slice(..));
         render_pass.draw(0..p.num_vertices, 0..1);
     }
 
     /// Run the cat render pass (clear to transparent + draw instanced cats).
-    pub fn draw_cats(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
-        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+    pub fn draw_cats(&self, frame: &mut FrameContext) {
+        let mut render_pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("cat_render_pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view,
+                view: &frame.view,
                 resolve_target: None,
                 depth_slice: None,
                 ops: wgpu::Operations {
@@ -312,7 +504,7 @@ impl GpuState {
         let p = &self.cat_pipeline;
         if p.num_instances > 0 {
             render_pass.set_pipeline(&p.pipeline);
-            render_pass.set_bind_group(0, &p.screen_bind_group, &[]);
+            render_pass.set_bind_group(0, &self.surfaces[frame.surface].view_bind_group, &[]);
             render_pass.set_vertex_buffer(0, p.vertex_buffer.slice(..));
             render_pass.set_vertex_buffer(1, p.instance_buffer.slice(..));
             render_pass.set_index_buffer(p.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
@@ -322,8 +514,8 @@ impl GpuState {
 
     /// Create an egui render pass that preserves existing content (LoadOp::Load).
     /// Returns a 'static render pass suitable for egui_wgpu::Renderer::render().
-    pub fn begin_egui_pass<'a>(
-        encoder: &'a mut wgpu::CommandEncoder,
+    pub fn begin_egui_pass(
+        encoder: &mut wgpu::CommandEncoder,
         view: &wgpu::TextureView,
     ) -> wgpu::RenderPass<'static> {
         let render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -0,0 +1,205 @@
+//! Which desktop windows hide which sprites.
+//!
+//! The overlay sits above every window, so on its own it paints cats over
+//! whatever the user is working in. Each sprite is given a layer instead: the
+//! window it's perched on, or the desktop underneath everything. Windows
+//! stacked above that layer cover it. The covering windows go to the shader
+//! as per-instance clip rectangles, and sprites covered completely aren't
+//! drawn at all.
+
+use glam::Vec2;
+
+use pettoy::ecs::systems::window_aware::DesktopWindow;
+
+/// Covering windows a sprite can be clipped by. When more windows overlap
+/// it, the ones covering most of it win.
+pub const MAX_OCCLUDERS: usize = 2;
+
+/// Half the side of the base sprite quad, in pixels at size 1.0 on a
+/// monitor without DPI scaling.
+const HALF_EXTENT: f32 = 48.0;
+
+/// Left, top, right, bottom in colony pixels. An empty rectangle (all
+/// zeros) clips nothing.
+pub type ClipRect = [f32; 4];
+
+/// Layer of a sprite on the desktop itself, below every window.
+pub const DESKTOP: u32 = u32::MAX;
+
+/// Windows by stacking order, as of the latest refresh.
+#[derive(Debug)]
+pub struct Occlusion {
+    /// Off draws every sprite on top of every window, as it used to.
+    pub enabled: bool,
+    /// Topmost first.
+    windows: Vec<DesktopWindow>,
+    /// Each monitor in colony pixels, with the DPI scale its quads are
+    /// drawn at.
+    views: Vec<(ClipRect, f32)>,
+}
+
+impl Default for Occlusion {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            windows: Vec::new(),
+            views: Vec::new(),
+        }
+    }
+}
+
+impl Occlusion {
+    /// Take the current window list. Only windows cats can stand on count;
+    /// minimized, tool and shell windows hide nothing.
+    pub fn update(&mut self, windows: &[DesktopWindow]) {
+        self.windows.clear();
+        self.windows
+            .extend(windows.iter().filter(|w| w.is_platform()).cloned());
+        self.windows.sort_by_key(|w| w.z);
+    }
+
+    /// Take the monitors' rectangles and DPI scales, so sprites are
+    /// measured at the size the shader draws them.
+    pub fn set_views(&mut self, views: impl IntoIterator<Item = (ClipRect, f32)>) {
+        self.views.clear();
+        self.views.extend(views);
+    }
+
+    /// DPI scale of the monitor showing `pos`.
+    fn scale_at(&self, pos: Vec2) -> f32 {
+        self.views
+            .iter()
+            .find(|(r, _)| pos.x >= r[0] && pos.y >= r[1] && pos.x < r[2] && pos.y < r[3])
+            .map_or(1.0, |&(_, scale)| scale)
+    }
+
+    /// Layer of a sprite at `pos`: the topmost window it's perched on, or
+    /// [`DESKTOP`].
+    pub fn layer(&self, pos: Vec2) -> u32 {
+        self.windows
+            .iter()
+            .find(|w| w.carries(pos))
+            .map_or(DESKTOP, |w| w.z)
+    }
+
+    /// [`Self::clip`] for a sprite on whatever layer it's at.
+    pub fn sprite(&self, pos: Vec2, size: f32) -> Option<[ClipRect; MAX_OCCLUDERS]> {
+        self.clip(pos, size, self.layer(pos))
+    }
+
+    /// Clip rectangles for a sprite of `size` at `pos` on `layer`, or
+    /// `None` when a window covers it completely.
+    pub fn clip(&self, pos: Vec2, size: f32, layer: u32) -> Option<[ClipRect; MAX_OCCLUDERS]> {
+        let mut clips = [[0.0; 4]; MAX_OCCLUDERS];
+        if !self.enabled {
+            return Some(clips);
+        }
+        let half = HALF_EXTENT * size * self.scale_at(pos);
+        let bounds = [pos.x - half, pos.y - half, pos.x + half, pos.y + half];
+        let mut covered = [0.0; MAX_OCCLUDERS];
+        for w in self.windows.iter().take_while(|w| w.z < layer) {
+            let rect = [w.left, w.top, w.right, w.bottom];
+            let area = overlap(rect, bounds);
+            if area <= 0.0 {
+                continue;
+            }
+            if rect[0] <= bounds[0]
+                && rect[1] <= bounds[1]
+                && rect[2] >= bounds[2]
+                && rect[3] >= bounds[3]
+            {
+                return None;
+            }
+            // Keep the biggest few, largest first
+            if let Some(i) = covered.iter().position(|&c| area > c) {
+                clips.copy_within(i..MAX_OCCLUDERS - 1, i + 1);
+                covered.copy_within(i..MAX_OCCLUDERS - 1, i + 1);
+                clips[i] = rect;
+                covered[i] = area;
+            }
+        }
+        Some(clips)
+    }
+}
+
+/// Area of the intersection of `a` and `b`.
+fn overlap(a: ClipRect, b: ClipRect) -> f32 {
+    let w = a[2].min(b[2]) - a[0].max(b[0]);
+    let h = a[3].min(b[3]) - a[1].max(b[1]);
+    w.max(0.0) * h.max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(z: u32, left: f32, top: f32, right: f32, bottom: f32) -> DesktopWindow {
+        DesktopWindow {
+            id: z as u64,
+            left,
+            top,
+            right,
+            bottom,
+            z,
+            ..DesktopWindow::default()
+        }
+    }
+
+    #[test]
+    fn windows_above_a_layer_clip_it_and_ones_below_dont() {
+        let mut occlusion = Occlusion::default();
+        // An editor on top of a browser
+        let editor = window(0, 0.0, 0.0, 500.0, 400.0);
+        let browser = window(1, 300.0, 200.0, 900.0, 700.0);
+        occlusion.update(&[browser, editor]);
+
+        // Perched on the browser, peeking out from behind the editor
+        let on_browser = Vec2::new(520.0, 180.0);
+        assert_eq!(occlusion.layer(on_browser), 1);
+        let clips = occlusion.clip(on_browser, 1.0, 1).unwrap();
+        assert_eq!(clips, [[0.0, 0.0, 500.0, 400.0], [0.0; 4]]);
+
+        // Perched on the editor: nothing above it
+        let on_editor = Vec2::new(100.0, -20.0);
+        assert_eq!(occlusion.layer(on_editor), 0);
+        assert_eq!(occlusion.clip(on_editor, 1.0, 0), Some([[0.0; 4]; 2]));
+
+        // On the desktop, deep behind both: gone
+        let behind = Vec2::new(700.0, 500.0);
+        assert_eq!(occlusion.layer(behind), DESKTOP);
+        assert_eq!(occlusion.clip(behind, 1.0, DESKTOP), None);
+
+        // Out in the open
+        let open = Vec2::new(1200.0, 500.0);
+        assert_eq!(occlusion.clip(open, 1.0, DESKTOP), Some([[0.0; 4]; 2]));
+    }
+
+    #[test]
+    fn the_windows_covering_most_of_a_sprite_clip_it() {
+        let mut occlusion = Occlusion::default();
+        occlusion.update(&[
+            window(0, 0.0, 0.0, 105.0, 1000.0),
+            window(1, 0.0, 0.0, 140.0, 1000.0),
+            window(2, 190.0, 0.0, 400.0, 1000.0),
+        ]);
+        let clips = occlusion
+            .clip(Vec2::new(150.0, 500.0), 1.0, DESKTOP)
+            .unwrap();
+        assert_eq!(clips[0], [0.0, 0.0, 140.0, 1000.0]);
+        assert_eq!(clips[1], [190.0, 0.0, 400.0, 1000.0]);
+
+        occlusion.update(&[window(0, 0.0, 0.0, 1000.0, 1000.0)]);
+        assert_eq!(occlusion.clip(Vec2::new(500.0, 500.0), 1.0, DESKTOP), None);
+
+        // Quads grow with the monitor's DPI scale, and so does their reach
+        let beside = Vec2::new(1060.0, 500.0);
+        assert_eq!(occlusion.clip(beside, 1.0, DESKTOP), Some([[0.0; 4]; 2]));
+        occlusion.set_views([([0.0, 0.0, 2000.0, 1000.0], 2.0)]);
+        let clips = occlusion.clip(beside, 1.0, DESKTOP).unwrap();
+        assert_eq!(clips[0], [0.0, 0.0, 1000.0, 1000.0]);
+        occlusion.enabled = false;
+        assert!(occlusion
+            .clip(Vec2::new(500.0, 500.0), 1.0, DESKTOP)
+            .is_some());
+    }
+}
@@ -48,41 +48,29 @@ pub struct CatPipeline {
     pub vertex_buffer: wgpu::Buffer,
     pub index_buffer: wgpu::Buffer,
     pub instance_buffer: wgpu::Buffer,
-    pub screen_uniform_buffer: wgpu::Buffer,
-    pub screen_bind_group: wgpu::BindGroup,
     pub num_instances: u32,
 }
 
 impl CatPipeline {
-    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        view_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
         // Load shader
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("cat_shader"),
             source: wgpu::ShaderSource::Wgsl(include_str!("shaders/cat.wgsl").into()),
         });
 
-        // Bind group layout for screen_size uniform
-        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("screen_uniform_layout"),
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
-                },
-                count: None,
-            }],
-        });
-
+        // Group 0: the per-monitor view uniform
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("cat_pipeline_layout"),
-            bind_group_layouts: &[&bind_group_layout],
+            bind_group_layouts: &[view_layout],
             push_constant_ranges: &[],
         });
 
-        // Instance buffer layout (28 bytes per instance)
+        // Instance buffer layout (60 bytes per instance)
         let instance_layout = wgpu::VertexBufferLayout {
             array_stride: std::mem::size_of::<CatInstance>() as wgpu::BufferAddress,
             step_mode: wgpu::VertexStepMode::Instance,
@@ -117,6 +105,17 @@ impl CatPipeline {
                     offset: 24,
                     shader_location: 6,
                 },
+                // clip rectangles (2x vec4<f32>) — 28, 44
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Float32x4,
+                    offset: 28,
+                    shader_location: 7,
+                },
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Float32x4,
+                    offset: 44,
+                    shader_location: 8,
+                },
             ],
         };
 
@@ -186,29 +185,11 @@ impl CatPipeline {
             mapped_at_creation: false,
         });
 
-        // Screen size uniform — initialized to 1x1, updated each frame
-        let screen_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("screen_uniform_buffer"),
-            contents: bytemuck::cast_slice(&[1.0f32, 1.0f32]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-
-        let screen_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("screen_bind_group"),
-            layout: &bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: screen_uniform_buffer.as_entire_binding(),
-            }],
-        });
-
         Self {
             pipeline,
             vertex_buffer,
             index_buffer,
             instance_buffer,
-            screen_uniform_buffer,
-            screen_bind_group,
             num_instances: 0,
         }
     }
@@ -225,13 +206,4 @@ impl CatPipeline {
             );
         }
     }
-
-    /// Update the screen size uniform.
-    pub fn update_screen_size(&self, queue: &wgpu::Queue, width: f32, height: f32) {
-        queue.write_buffer(
-            &self.screen_uniform_buffer,
-            0,
-            bytemuck::cast_slice(&[width, height]),
-        );
-    }
 }
@@ -12,6 +12,9 @@ struct InstanceInput {
     @location(4) color: u32,
     @location(5) frame: u32,
     @location(6) rotation: f32,
+    // Windows in front of the sprite (left, top, right, bottom), colony pixels
+    @location(7) clip_a: vec4<f32>,
+    @location(8) clip_b: vec4<f32>,
 };
 
 struct VertexOutput {
@@ -20,26 +23,41 @@ struct VertexOutput {
     @location(1) color: vec4<f32>,
     @location(2) @interpolate(flat) frame: u32,
     @location(3) rotation: f32,
+    @location(4) colony: vec2<f32>,
+    @location(5) @interpolate(flat) clip_a: vec4<f32>,
+    @location(6) @interpolate(flat) clip_b: vec4<f32>,
+};
+
+// Which slice of the colony this monitor's surface shows (see render/mod.rs).
+struct View {
+    origin: vec2<f32>,
+    size: vec2<f32>,
+    desktop_size: vec2<f32>,
+    scale: f32,
 };
 
 @group(0) @binding(0)
-var<uniform> screen_size: vec2<f32>;
+var<uniform> view: View;
 
 @vertex
 fn vs_main(vert: VertexInput, inst: InstanceInput) -> VertexOutput {
     var out: VertexOutput;
 
-    let world_pos = vert.position * inst.size + inst.offset;
-    // Convert from screen pixels to clip space [-1, 1]
+    // Quads grow with the monitor's DPI scale so cats look the same size everywhere.
+    let world_pos = vert.position * inst.size * view.scale + inst.offset - view.origin;
+    // Convert from surface pixels to clip space [-1, 1]
     let clip = vec2<f32>(
-        (world_pos.x / screen_size.x) * 2.0 - 1.0,
-        1.0 - (world_pos.y / screen_size.y) * 2.0,
+        (world_pos.x / view.size.x) * 2.0 - 1.0,
+        1.0 - (world_pos.y / view.size.y) * 2.0,
     );
 
     out.clip_position = vec4<f32>(clip, 0.0, 1.0);
     out.uv = vert.uv;
     out.frame = inst.frame;
     out.rotation = inst.rotation;
+    out.colony = world_pos + view.origin;
+    out.clip_a = inst.clip_a;
+    out.clip_b = inst.clip_b;
 
     // Unpack RGBA from u32
     let r = f32((inst.color >> 24u) & 0xFFu) / 255.0;
@@ -363,8 +381,18 @@ fn eye_glow_walking(uv: vec2<f32>) -> f32 {
     return 1.0 - smoothstep(-0.005, 0.005, d);
 }
 
+// Point in a (left, top, right, bottom) rectangle; empty ones hold nothing
+fn inside(p: vec2<f32>, r: vec4<f32>) -> bool {
+    return p.x >= r.x && p.x < r.z && p.y >= r.y && p.y < r.w;
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Behind a window
+    if inside(in.colony, in.clip_a) || inside(in.colony, in.clip_b) {
+        discard;
+    }
+
     // Apply rotation to UVs (for spawn somersault etc.)
     var uv = in.uv;
     if abs(in.rotation) > 0.001 {
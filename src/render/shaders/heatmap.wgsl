@@ -6,6 +6,16 @@ var heatmap_tex: texture_2d<f32>;
 @group(0) @binding(1)
 var heatmap_sampler: sampler;
 
+struct View {
+    origin: vec2<f32>,
+    size: vec2<f32>,
+    desktop_size: vec2<f32>,
+    scale: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> view: View;
+
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) uv: vec2<f32>,
@@ -27,7 +37,9 @@ fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    let heat = textureSample(heatmap_tex, heatmap_sampler, in.uv).r;
+    // The texture spans the whole desktop; pick out this monitor's part.
+    let desktop_uv = (view.origin + in.uv * view.size) / view.desktop_size;
+    let heat = textureSample(heatmap_tex, heatmap_sampler, desktop_uv).r;
 
     if (heat < 0.01) {
         return vec4<f32>(0.0, 0.0, 0.0, 0.0);
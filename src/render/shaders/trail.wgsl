@@ -1,12 +1,15 @@
 // Trail line shader — renders line segments with per-vertex color + alpha fade.
 // Premultiplied alpha blending (same as cats).
 
-struct ScreenUniform {
+struct View {
+    origin: vec2<f32>,
     size: vec2<f32>,
+    desktop_size: vec2<f32>,
+    scale: f32,
 };
 
 @group(0) @binding(0)
-var<uniform> screen: ScreenUniform;
+var<uniform> view: View;
 
 struct VertexInput {
     @location(0) position: vec2<f32>,
@@ -21,9 +24,10 @@ struct VertexOutput {
 @vertex
 fn vs_main(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
-    // Convert pixel coords to NDC: x in [0, screen_w] -> [-1, 1], y in [0, screen_h] -> [1, -1]
-    let ndc_x = (in.position.x / screen.size.x) * 2.0 - 1.0;
-    let ndc_y = 1.0 - (in.position.y / screen.size.y) * 2.0;
+    // Convert colony pixels to NDC: x in [origin, origin + w] -> [-1, 1], y -> [1, -1]
+    let p = in.position - view.origin;
+    let ndc_x = (p.x / view.size.x) * 2.0 - 1.0;
+    let ndc_y = 1.0 - (p.y / view.size.y) * 2.0;
     out.clip_position = vec4<f32>(ndc_x, ndc_y, 0.0, 1.0);
     out.color = in.color;
     return out;
@@ -17,6 +17,9 @@ const MAX_TRAIL_VERTICES: usize = MAX_TRAIL_CATS * (TRAIL_POINTS_PER_CAT - 1) *
 /// Sample trail position every N frames.
 const TRAIL_SAMPLE_INTERVAL: u32 = 3;
 
+/// One cat's trail sample: (x, y, base_r, base_g, base_b, mood_r, mood_g, mood_b).
+pub type TrailSample = (f32, f32, f32, f32, f32, f32, f32, f32);
+
 /// Per-cat ring buffer of trail positions with per-point color.
 struct CatTrail {
     points: [(f32, f32); TRAIL_POINTS_PER_CAT],
@@ -68,7 +71,7 @@ impl TrailSystem {
     /// Update trail positions from cat positions. Call once per frame.
     /// `positions` is (x, y, base_r, base_g, base_b, mood_r, mood_g, mood_b) for each cat.
     /// mood color reflects behavior state; base color is the cat's fur color.
-    pub fn update(&mut self, positions: &[(f32, f32, f32, f32, f32, f32, f32, f32)]) {
+    pub fn update(&mut self, positions: &[TrailSample]) {
         self.frame_counter += 1;
 
         // Resize trail storage if needed
@@ -79,7 +82,7 @@ impl TrailSystem {
         self.trails.truncate(positions.len());
 
         // Only sample every N frames
-        if self.frame_counter % TRAIL_SAMPLE_INTERVAL != 0 {
+        if !self.frame_counter.is_multiple_of(TRAIL_SAMPLE_INTERVAL) {
             return;
         }
 
@@ -137,7 +140,6 @@ impl TrailSystem {
 pub struct TrailPipeline {
     pub pipeline: wgpu::RenderPipeline,
     pub vertex_buffer: wgpu::Buffer,
-    pub screen_bind_group: wgpu::BindGroup,
     pub num_vertices: u32,
 }
 
@@ -145,31 +147,16 @@ impl TrailPipeline {
     pub fn new(
         device: &wgpu::Device,
         format: wgpu::TextureFormat,
-        screen_uniform_buffer: &wgpu::Buffer,
+        view_layout: &wgpu::BindGroupLayout,
     ) -> Self {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("trail_shader"),
             source: wgpu::ShaderSource::Wgsl(include_str!("shaders/trail.wgsl").into()),
         });
 
-        let bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("trail_screen_layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                }],
-            });
-
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("trail_pipeline_layout"),
-            bind_group_layouts: &[&bind_group_layout],
+            bind_group_layouts: &[view_layout],
             push_constant_ranges: &[],
         });
 
@@ -237,19 +224,9 @@ impl TrailPipeline {
             mapped_at_creation: false,
         });
 
-        let screen_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("trail_screen_bind_group"),
-            layout: &bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: screen_uniform_buffer.as_entire_binding(),
-            }],
-        });
-
         Self {
             pipeline,
             vertex_buffer,
-            screen_bind_group,
             num_vertices: 0,
         }
     }
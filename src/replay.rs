@@ -0,0 +1,509 @@
+//! Input recording and deterministic replay.
+//!
+//! A recording is the colony seed plus everything the outside world fed the
+//! simulation, packed into one small record per tick. Feeding it back through
+//! a fresh [`Simulation`] reproduces the run tick-for-tick, and periodic state
+//! checksums pinpoint the first tick where a replay stops matching.
+//!
+//! Layout (little-endian): `"PTIL"`, version `u16`, seed `u64`, the monitor
+//! layout, the tuning config as TOML text, then per tick a `u16` flag word
+//! followed by only the fields the flags mark as present. Button/hotkey
+//! levels live in the flag word itself; everything else is written only when
+//! it changed since the previous tick.
+
+use std::fmt;
+use std::io;
+
+use glam::Vec2;
+
+use crate::automation::Weekday;
+use crate::codec::{invalid, Reader, Writer};
+use crate::config::Config;
+use crate::desktop::Desktop;
+use crate::ecs::systems::window_aware::{DesktopWindow, WindowState};
+use crate::mode::{AppMode, ModeState};
+use crate::sim::{SimInput, Simulation};
+
+const MAGIC: &[u8; 4] = b"PTIL";
+const VERSION: u16 = 6;
+/// A state checksum is stored every this many ticks.
+pub const CHECKSUM_INTERVAL: u64 = 60;
+
+// Per-tick flag bits.
+const LEFT: u16 = 1 << 0;
+const RIGHT: u16 = 1 << 1;
+const MIDDLE: u16 = 1 << 2;
+const SPAWN_BOX: u16 = 1 << 3;
+const SPAWN_GLASS: u16 = 1 << 4;
+const MOUSE: u16 = 1 << 5;
+const IDLE: u16 = 1 << 6;
+/// Hour, weekday or battery.
+const AMBIENT: u16 = 1 << 7;
+const DESKTOP: u16 = 1 << 8;
+const WINDOWS: u16 = 1 << 9;
+const MODE: u16 = 1 << 10;
+const CAT_COUNT: u16 = 1 << 11;
+const HEATMAP: u16 = 1 << 12;
+const CHECKSUM: u16 = 1 << 13;
+const CONFIG: u16 = 1 << 14;
+const TOGGLE_WAND: u16 = 1 << 15;
+
+/// Everything outside [`SimInput`] that can change the colony between ticks
+/// (monitor layout changes, window list refreshes, tray/hotkey mode switches, the
+/// debug population slider, the heatmap toggle, config reloads).
+#[derive(Debug, Clone, PartialEq)]
+struct Observed {
+    mouse: Vec2,
+    idle_seconds: f64,
+    hour: f32,
+    weekday: Weekday,
+    battery: Option<f32>,
+    desktop: Desktop,
+    /// [`Simulation::windows_generation`].
+    windows: u64,
+    mode: AppMode,
+    cat_count: usize,
+    heatmap: bool,
+    config: Config,
+}
+
+impl Observed {
+    /// What a freshly constructed [`Simulation`] and default input look like.
+    fn initial(desktop: Desktop, config: Config) -> Self {
+        let input = SimInput::default();
+        Self {
+            mouse: input.mouse,
+            idle_seconds: input.idle_seconds,
+            hour: input.hour,
+            weekday: input.weekday,
+            battery: input.battery,
+            desktop,
+            windows: 0,
+            mode: ModeState::new().mode,
+            cat_count: 0,
+            heatmap: false,
+            config,
+        }
+    }
+}
+
+fn mode_index(mode: AppMode) -> u8 {
+    ModeState::all_modes()
+        .iter()
+        .position(|&m| m == mode)
+        .unwrap_or(0) as u8
+}
+
+// ---------------------------------------------------------------------------
+// Recording
+// ---------------------------------------------------------------------------
+
+/// Appends one record per tick. Owned by [`Simulation`] while recording.
+pub struct InputRecorder {
+    out: Writer,
+    last: Observed,
+}
+
+impl InputRecorder {
+    pub(crate) fn new(sim: &Simulation) -> Self {
+        let mut out = Writer::with_capacity(64 * 1024);
+        out.bytes(MAGIC);
+        out.u16(VERSION);
+        out.u64(sim.seed);
+        sim.desktop.encode(&mut out);
+        out.text(&sim.config.to_toml());
+        Self {
+            out,
+            last: Observed::initial(sim.desktop.clone(), sim.config.clone()),
+        }
+    }
+
+    /// Log the input for the tick that is about to run, plus any outside
+    /// changes to the colony since the previous tick.
+    pub(crate) fn record(&mut self, sim: &Simulation, input: &SimInput) {
+        let mut flags = 0u16;
+        let mut payload = Writer::default();
+
+        for (down, bit) in [
+            (input.left_down, LEFT),
+            (input.right_down, RIGHT),
+            (input.middle_down, MIDDLE),
+            (input.spawn_box, SPAWN_BOX),
+            (input.spawn_glass, SPAWN_GLASS),
+            (input.toggle_wand, TOGGLE_WAND),
+        ] {
+            if down {
+                flags |= bit;
+            }
+        }
+
+        if input.mouse != self.last.mouse {
+            flags |= MOUSE;
+            payload.vec2(input.mouse);
+            self.last.mouse = input.mouse;
+        }
+        if input.idle_seconds.to_bits() != self.last.idle_seconds.to_bits() {
+            flags |= IDLE;
+            payload.f64(input.idle_seconds);
+            self.last.idle_seconds = input.idle_seconds;
+        }
+        let battery = input.battery.map(f32::to_bits);
+        if input.hour.to_bits() != self.last.hour.to_bits()
+            || input.weekday != self.last.weekday
+            || battery != self.last.battery.map(f32::to_bits)
+        {
+            flags |= AMBIENT;
+            payload.f32(input.hour);
+            payload.u8(input.weekday.index());
+            payload.u8(input.battery.is_some() as u8);
+            payload.f32(input.battery.unwrap_or(0.0));
+            self.last.hour = input.hour;
+            self.last.weekday = input.weekday;
+            self.last.battery = input.battery;
+        }
+
+        if sim.desktop != self.last.desktop {
+            flags |= DESKTOP;
+            sim.desktop.encode(&mut payload);
+            self.last.desktop.clone_from(&sim.desktop);
+        }
+        if sim.windows_generation() != self.last.windows {
+            flags |= WINDOWS;
+            payload.u16(sim.desktop_windows().len() as u16);
+            for w in sim.desktop_windows() {
+                payload.u64(w.id);
+                for v in [w.left, w.top, w.right, w.bottom] {
+                    payload.f32(v);
+                }
+                payload.u32(w.z);
+                payload.u8(w.state.bits());
+                payload.str(&w.title);
+                payload.str(&w.process);
+            }
+            self.last.windows = sim.windows_generation();
+        }
+        if sim.mode.mode != self.last.mode {
+            flags |= MODE;
+            payload.u8(mode_index(sim.mode.mode));
+        }
+        if sim.cat_count() != self.last.cat_count {
+            flags |= CAT_COUNT;
+            payload.u32(sim.cat_count() as u32);
+        }
+        if sim.heatmap.enabled != self.last.heatmap {
+            flags |= HEATMAP;
+            payload.u8(sim.heatmap.enabled as u8);
+        }
+        if sim.config != self.last.config {
+            flags |= CONFIG;
+            payload.text(&sim.config.to_toml());
+            self.last.config.clone_from(&sim.config);
+        }
+        if sim.tick_count.is_multiple_of(CHECKSUM_INTERVAL) {
+            flags |= CHECKSUM;
+            payload.u64(sim.checksum());
+        }
+
+        self.out.u16(flags);
+        self.out.bytes(&payload.buf);
+    }
+
+    /// Remember the colony state the tick left behind, so the next record
+    /// only captures changes made from outside the simulation.
+    pub(crate) fn observe(&mut self, sim: &Simulation) {
+        self.last.mode = sim.mode.mode;
+        self.last.cat_count = sim.cat_count();
+        self.last.heatmap = sim.heatmap.enabled;
+    }
+
+    /// Encoded log, ready to write to disk.
+    pub fn finish(self) -> Vec<u8> {
+        self.out.buf
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Replay
+// ---------------------------------------------------------------------------
+
+/// Why a replay stopped early.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The log is truncated or not a PetToy input log.
+    Corrupt(io::Error),
+    /// The replayed colony no longer matches the recorded one.
+    Desync {
+        tick: u64,
+        expected: u64,
+        actual: u64,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Corrupt(e) => write!(f, "corrupt input log: {e}"),
+            ReplayError::Desync { tick, expected, actual } => write!(
+                f,
+                "replay desynced at tick {tick}: expected checksum {expected:016x}, got {actual:016x}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> Self {
+        ReplayError::Corrupt(e)
+    }
+}
+
+/// Drives a fresh [`Simulation`] from a recorded input log.
+pub struct Replayer {
+    sim: Simulation,
+    reader: Reader,
+    input: SimInput,
+}
+
+impl Replayer {
+    /// Parse the log header and build the seeded colony it was recorded from.
+    pub fn new(bytes: Vec<u8>) -> io::Result<Self> {
+        let mut reader = Reader::new(bytes);
+        if &reader.take::<4>()? != MAGIC {
+            return Err(invalid("not a PetToy input log"));
+        }
+        let version = reader.u16()?;
+        if version != VERSION {
+            return Err(invalid(format!("unsupported input log version {version}")));
+        }
+        let seed = reader.u64()?;
+        let desktop = Desktop::decode(&mut reader)?;
+        let config = Config::from_toml(&reader.text()?).map_err(|e| invalid(e.to_string()))?;
+
+        let size = desktop.size();
+        let mut sim = Simulation::with_seed(size.x as u32, size.y as u32, seed);
+        sim.set_desktop(desktop);
+        sim.apply_config(config);
+        Ok(Self {
+            sim,
+            reader,
+            input: SimInput::default(),
+        })
+    }
+
+    /// The colony being replayed.
+    pub fn sim(&self) -> &Simulation {
+        &self.sim
+    }
+
+    /// Give up the replayer and keep the colony.
+    pub fn into_sim(self) -> Simulation {
+        self.sim
+    }
+
+    /// Replay one tick. Returns `Ok(false)` once the log is exhausted.
+    pub fn step(&mut self) -> Result<bool, ReplayError> {
+        if self.reader.at_end() {
+            return Ok(false);
+        }
+        let r = &mut self.reader;
+        let flags = r.u16()?;
+
+        self.input.left_down = flags & LEFT != 0;
+        self.input.right_down = flags & RIGHT != 0;
+        self.input.middle_down = flags & MIDDLE != 0;
+        self.input.spawn_box = flags & SPAWN_BOX != 0;
+        self.input.spawn_glass = flags & SPAWN_GLASS != 0;
+        self.input.toggle_wand = flags & TOGGLE_WAND != 0;
+        if flags & MOUSE != 0 {
+            self.input.mouse = r.vec2()?;
+        }
+        if flags & IDLE != 0 {
+            self.input.idle_seconds = r.f64()?;
+        }
+        if flags & AMBIENT != 0 {
+            self.input.hour = r.f32()?;
+            self.input.weekday =
+                Weekday::from_index(r.u8()?).ok_or_else(|| invalid("unknown weekday"))?;
+            let on_battery = r.u8()? != 0;
+            let charge = r.f32()?;
+            self.input.battery = on_battery.then_some(charge);
+        }
+
+        // Outside changes land before the tick, in the order they're recorded.
+        if flags & DESKTOP != 0 {
+            let desktop = Desktop::decode(r)?;
+            self.sim.set_desktop(desktop);
+        }
+        if flags & WINDOWS != 0 {
+            let count = r.u16()? as usize;
+            let mut windows = Vec::with_capacity(count);
+            for _ in 0..count {
+                windows.push(DesktopWindow {
+                    id: r.u64()?,
+                    left: r.f32()?,
+                    top: r.f32()?,
+                    right: r.f32()?,
+                    bottom: r.f32()?,
+                    z: r.u32()?,
+                    state: WindowState::from_bits(r.u8()?),
+                    title: r.str()?,
+                    process: r.str()?,
+                });
+            }
+            self.sim.set_desktop_windows(windows);
+        }
+        if flags & MODE != 0 {
+            let index = r.u8()? as usize;
+            let Some(&mode) = ModeState::all_modes().get(index) else {
+                return Err(invalid("unknown mode").into());
+            };
+            self.sim.mode.set_mode(mode);
+        }
+        if flags & CAT_COUNT != 0 {
+            let count = r.u32()? as usize;
+            self.sim.set_cat_count(count);
+        }
+        if flags & HEATMAP != 0 {
+            self.sim.heatmap.enabled = r.u8()? != 0;
+        }
+        if flags & CONFIG != 0 {
+            let config = Config::from_toml(&r.text()?).map_err(|e| invalid(e.to_string()))?;
+            self.sim.apply_config(config);
+        }
+        if flags & CHECKSUM != 0 {
+            let expected = r.u64()?;
+            let actual = self.sim.checksum();
+            if expected != actual {
+                return Err(ReplayError::Desync {
+                    tick: self.sim.tick_count,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        self.sim.tick(&self.input);
+        Ok(true)
+    }
+
+    /// Replay the whole log, verifying every checksum. Returns ticks replayed.
+    pub fn run_to_end(&mut self) -> Result<u64, ReplayError> {
+        let mut ticks = 0;
+        while self.step()? {
+            ticks += 1;
+        }
+        Ok(ticks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ModeRule;
+    use crate::desktop::Monitor;
+
+    /// Scripted input: cursor sweeping across the screen with the odd click,
+    /// toy drop and AFK stretch.
+    fn scripted_input(tick: u64) -> SimInput {
+        let t = tick as f32;
+        SimInput {
+            mouse: Vec2::new(
+                400.0 + (t * 0.05).sin() * 300.0,
+                300.0 + (t * 0.03).cos() * 200.0,
+            ),
+            left_down: tick % 97 < 3,
+            right_down: tick % 211 < 2,
+            middle_down: false,
+            spawn_box: tick == 120,
+            spawn_glass: tick == 240,
+            toggle_wand: tick == 300 || tick == 700,
+            idle_seconds: if tick > 400 {
+                (tick - 400) as f64 / 60.0
+            } else {
+                0.0
+            },
+            hour: 21.5,
+            weekday: Weekday::Sat,
+            battery: (tick > 600).then_some(40.0),
+        }
+    }
+
+    fn record_run(seed: u64, ticks: u64) -> (Vec<u8>, u64) {
+        let mut sim = Simulation::with_seed(800, 600, seed);
+        sim.start_recording();
+        sim.spawn_cats(30);
+        for tick in 0..ticks {
+            if tick == 450 {
+                let mut config = sim.config.clone();
+                config.interaction.play_chance = 0.05;
+                config.automation.hold = 1.0;
+                config.automation.rules.push(ModeRule {
+                    battery_below: Some(50.0),
+                    mode: Some(AppMode::Zen),
+                    ..ModeRule::default()
+                });
+                sim.apply_config(config);
+            }
+            if tick == 500 {
+                // A second monitor is plugged in left of the first.
+                sim.set_desktop(Desktop::from_virtual(&[
+                    Monitor::new(0.0, 0.0, 800.0, 600.0),
+                    Monitor::new(-1024.0, 0.0, 1024.0, 768.0),
+                ]));
+            }
+            if tick == 300 {
+                sim.mode.set_mode(AppMode::Chaos);
+                sim.set_desktop_windows(vec![DesktopWindow {
+                    id: 1,
+                    left: 100.0,
+                    top: 200.0,
+                    right: 500.0,
+                    bottom: 500.0,
+                    title: "notes.txt".into(),
+                    process: "editor".into(),
+                    ..DesktopWindow::default()
+                }]);
+            }
+            sim.tick(&scripted_input(tick));
+        }
+        let checksum = sim.checksum();
+        (sim.take_recording().expect("recording"), checksum)
+    }
+
+    #[test]
+    fn same_seed_same_colony() {
+        let (_, a) = record_run(42, 600);
+        let (_, b) = record_run(42, 600);
+        let (_, c) = record_run(43, 600);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn replay_reproduces_recording() {
+        let (log, final_checksum) = record_run(7, 900);
+        let mut replayer = Replayer::new(log).expect("valid log");
+        assert_eq!(replayer.run_to_end().expect("no desync"), 900);
+        assert_eq!(replayer.sim().checksum(), final_checksum);
+        assert_eq!(replayer.sim().mode.mode, AppMode::Zen, "battery rule kicked in");
+    }
+
+    #[test]
+    fn tampered_log_desyncs() {
+        let (mut log, _) = record_run(7, 300);
+        // Flip the seed: everything after the first checksum must diverge.
+        log[6] ^= 0xFF;
+        let mut replayer = Replayer::new(log).expect("valid header");
+        assert!(matches!(
+            replayer.run_to_end(),
+            Err(ReplayError::Desync { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(Replayer::new(b"nope".to_vec()).is_err());
+    }
+}
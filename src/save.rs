@@ -0,0 +1,838 @@
+//! Colony save files — cats, toys, treats and the cursor heatmap persist
+//! across restarts.
+//!
+//! Layout (little-endian): `"PTSV"`, version `u16`, then sections in a
+//! fixed order: header (monitor layout, elapsed time), cats, toys, heatmap,
+//! relationships, scent. Entity references between cats (play partners,
+//! bonds, tower bases) are stored as indices into the saved cat list and
+//! remapped to fresh entities on load; the spawn drop-in animation is
+//! transient and is skipped by landing the cat. Prey and hunts are transient
+//! too and aren't saved.
+//!
+//! What each version added. Older saves still load, with defaults for
+//! whatever they lack:
+//!
+//! - v2: the monitor layout, in place of a single screen size
+//! - v3: ages, kittens, bonds
+//! - v4: cat ids (fresh ones on load), journals
+//! - v5: needs
+//! - v6: relationships
+//! - v7: clans (cats join one at random), scent
+//! - v8: trust (cats start neutral)
+//! - v9: the prey tally in journals
+//! - v10: toys and treats in one section, not four of their own
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use glam::Vec2;
+
+use crate::codec::{invalid, Reader, Writer};
+use crate::desktop::Desktop;
+use crate::ecs::components::{
+    Age, Appearance, BehaviorState, Bond, CatId, CatName, CatState, Clan, GiftCarrier,
+    InteractionTarget, Kitten, Needs, Personality, Position, PrevPosition, SleepingPile,
+    Lifetime, Shattered, SpatialCell, SpawnAnimation, Stacked, Toy, Trust, Velocity,
+};
+use crate::ecs::systems;
+use crate::journal::{Journal, JournalEntry, JournalEvent, JOURNAL_LEN};
+use crate::relations::Relationships;
+use crate::scent::CLANS;
+use crate::sim::Simulation;
+use crate::toy::{self, ToyKind};
+
+const MAGIC: &[u8; 4] = b"PTSV";
+const VERSION: u16 = 10;
+/// Index marking a reference to a cat that wasn't saved.
+const NO_CAT: u32 = u32::MAX;
+/// Id of a cat that was never given one; it gets a fresh id on load.
+const NO_ID: u64 = u64::MAX;
+/// Clan of a cat that isn't in one.
+const NO_CLAN: u8 = u8::MAX;
+
+// Per-cat optional component flags.
+const HAS_PILE: u8 = 1 << 0;
+const HAS_GIFT: u8 = 1 << 1;
+const HAS_TARGET: u8 = 1 << 2;
+const HAS_STACKED: u8 = 1 << 3;
+const HAS_KITTEN: u8 = 1 << 4;
+const HAS_BOND: u8 = 1 << 5;
+
+/// Default save location: `%APPDATA%\PetToy\colony.sav` on Windows,
+/// `$XDG_DATA_HOME/pettoy/colony.sav` (or `~/.local/share/...`) elsewhere.
+pub fn default_path() -> Option<PathBuf> {
+    let dir = if cfg!(windows) {
+        PathBuf::from(std::env::var_os("APPDATA")?).join("PetToy")
+    } else if let Some(data) = std::env::var_os("XDG_DATA_HOME") {
+        PathBuf::from(data).join("pettoy")
+    } else {
+        PathBuf::from(std::env::var_os("HOME")?).join(".local/share/pettoy")
+    };
+    Some(dir.join("colony.sav"))
+}
+
+// ---------------------------------------------------------------------------
+// Encode
+// ---------------------------------------------------------------------------
+
+/// Encode the persistent parts of the colony.
+pub fn encode(sim: &Simulation) -> Vec<u8> {
+    let mut out = Writer::with_capacity(64 + sim.cat_count() * 64);
+    out.bytes(MAGIC);
+    out.u16(VERSION);
+    sim.desktop.encode(&mut out);
+    out.f64(sim.elapsed);
+    out.u64(sim.cat_ids.next);
+
+    // Cats, indexed in save order so references can be remapped.
+    let entities: Vec<hecs::Entity> = sim
+        .world
+        .query::<&CatState>()
+        .iter()
+        .map(|(e, _)| e)
+        .collect();
+    let index_of = |target: hecs::Entity| {
+        entities
+            .iter()
+            .position(|&e| e == target)
+            .map_or(NO_CAT, |i| i as u32)
+    };
+
+    out.u32(entities.len() as u32);
+    for &entity in &entities {
+        let e = sim.world.entity(entity).expect("entity listed above");
+        let pos = e.get::<&Position>().map_or(Vec2::ZERO, |p| p.0);
+        // A cat mid-drop lands where it was heading.
+        let pos = match e.get::<&SpawnAnimation>() {
+            Some(anim) => Vec2::new(pos.x, anim.target_y),
+            None => pos,
+        };
+        let vel = e.get::<&Velocity>().map_or(Vec2::ZERO, |v| v.0);
+        let state = *e.get::<&CatState>().expect("queried on CatState");
+        let p = e.get::<&Personality>().map_or(
+            Personality {
+                laziness: 0.5,
+                energy: 0.5,
+                curiosity: 0.5,
+                skittishness: 0.5,
+            },
+            |p| *p,
+        );
+        let look = e.get::<&Appearance>().map_or(
+            Appearance {
+                color: 0x808080FF,
+                pattern: 0,
+                size: 1.0,
+            },
+            |a| *a,
+        );
+        let name = e.get::<&CatName>().map(|n| n.0.clone()).unwrap_or_default();
+        let pile = e.get::<&SleepingPile>().map(|p| p.breathing_offset);
+        let gift = e.get::<&GiftCarrier>().map(|g| g.timer);
+        let target = e.get::<&InteractionTarget>().map(|t| index_of(t.0));
+        let stacked = e.get::<&Stacked>().map(|s| index_of(s.base));
+        let age = e.get::<&Age>().map_or(0.0, |a| a.0);
+        let id = e.get::<&CatId>().map_or(NO_ID, |id| id.0);
+        let journal = e.get::<&Journal>().map(|j| (*j).clone()).unwrap_or_default();
+        let needs = e.get::<&Needs>().map_or(Needs::default(), |n| *n);
+        let clan = e.get::<&Clan>().map_or(NO_CLAN, |c| c.0);
+        let trust = e.get::<&Trust>().map_or(Trust::default(), |t| *t);
+        let kitten = e.get::<&Kitten>().map(|k| k.adult_size);
+        let bond = e
+            .get::<&Bond>()
+            .map(|b| (index_of(b.partner), b.play_time))
+            .filter(|&(partner, _)| partner != NO_CAT);
+
+        out.vec2(pos);
+        out.vec2(vel);
+        out.u8(state.state as u8);
+        out.f32(state.timer);
+        out.f32(p.laziness);
+        out.f32(p.energy);
+        out.f32(p.curiosity);
+        out.f32(p.skittishness);
+        out.u32(look.color);
+        out.u8(look.pattern);
+        out.f32(look.size);
+        out.str(&name);
+        out.f32(age);
+        out.u64(id);
+        encode_journal(&mut out, &journal);
+        out.f32(needs.energy);
+        out.f32(needs.hunger);
+        out.f32(needs.social);
+        out.f32(needs.play);
+        out.f32(needs.comfort);
+        out.u8(clan);
+        out.f32(trust.0);
+
+        let mut flags = 0;
+        if pile.is_some() {
+            flags |= HAS_PILE;
+        }
+        if gift.is_some() {
+            flags |= HAS_GIFT;
+        }
+        if target.is_some() {
+            flags |= HAS_TARGET;
+        }
+        if stacked.is_some() {
+            flags |= HAS_STACKED;
+        }
+        if kitten.is_some() {
+            flags |= HAS_KITTEN;
+        }
+        if bond.is_some() {
+            flags |= HAS_BOND;
+        }
+        out.u8(flags);
+        if let Some(offset) = pile {
+            out.f32(offset);
+        }
+        if let Some(timer) = gift {
+            out.f32(timer);
+        }
+        if let Some(index) = target {
+            out.u32(index);
+        }
+        if let Some(index) = stacked {
+            out.u32(index);
+        }
+        if let Some(adult_size) = kitten {
+            out.f32(adult_size);
+        }
+        if let Some((partner, play_time)) = bond {
+            out.u32(partner);
+            out.f32(play_time);
+        }
+    }
+
+    // Toys
+    let toys: Vec<_> = sim
+        .world
+        .query::<(&Toy, &Position, &Velocity, &Lifetime, Option<&Shattered>)>()
+        .iter()
+        .map(|(_, (toy, pos, vel, life, shattered))| {
+            (toy.kind, pos.0, vel.0, life.0, shattered.is_some())
+        })
+        .collect();
+    out.u16(toys.len() as u16);
+    for (kind, pos, vel, lifetime, shattered) in toys {
+        out.u8(kind as u8);
+        out.vec2(pos);
+        out.vec2(vel);
+        out.f32(lifetime);
+        out.u8(shattered as u8);
+    }
+
+    // Heatmap
+    out.u16(sim.heatmap.grid_size as u16);
+    for &cell in &sim.heatmap.cells {
+        out.f32(cell);
+    }
+
+    // Relationships
+    let pairs = sim.relations.pairs();
+    out.u32(pairs.len() as u32);
+    for (a, b, affinity) in pairs {
+        out.u64(a.0);
+        out.u64(b.0);
+        out.f32(affinity);
+    }
+
+    // Scent
+    out.u16(sim.scent.grid_size as u16);
+    out.u8(sim.scent.layers.len() as u8);
+    for &cell in sim.scent.layers.iter().flatten() {
+        out.f32(cell);
+    }
+
+    out.buf
+}
+
+// ---------------------------------------------------------------------------
+// Decode
+// ---------------------------------------------------------------------------
+
+struct SavedCat {
+    pos: Vec2,
+    vel: Vec2,
+    state: CatState,
+    personality: Personality,
+    appearance: Appearance,
+    name: String,
+    pile: Option<f32>,
+    gift: Option<f32>,
+    target: Option<u32>,
+    stacked: Option<u32>,
+    age: f32,
+    id: Option<u64>,
+    journal: Journal,
+    needs: Needs,
+    /// `None` for saves from before clans; `Some(None)` for a cat without one.
+    clan: Option<Option<u8>>,
+    trust: Trust,
+    kitten: Option<f32>,
+    bond: Option<(u32, f32)>,
+}
+
+struct SavedToy {
+    kind: ToyKind,
+    pos: Vec2,
+    vel: Vec2,
+    lifetime: f32,
+    shattered: bool,
+}
+
+/// Toys as saved before version 10: yarn, boxes, glasses and treats each in
+/// a section of their own.
+fn decode_legacy_toys(r: &mut Reader) -> io::Result<Vec<SavedToy>> {
+    let mut toys = Vec::new();
+    for kind in ToyKind::ALL {
+        for _ in 0..r.u16()? {
+            let pos = r.vec2()?;
+            let vel = if kind == ToyKind::Box { Vec2::ZERO } else { r.vec2()? };
+            let lifetime = r.f32()?;
+            let shattered = kind == ToyKind::Glass && r.u8()? != 0;
+            toys.push(SavedToy {
+                kind,
+                pos,
+                vel,
+                lifetime,
+                shattered,
+            });
+        }
+    }
+    Ok(toys)
+}
+
+/// Replace the colony in `sim` with a decoded save. The save is parsed in
+/// full before anything is touched, so a bad file leaves `sim` as it was.
+/// Anything saved on a monitor that has since changed is re-homed onto the
+/// current layout (see [`Desktop::rehome`]). Returns cats loaded.
+pub fn decode_into(sim: &mut Simulation, bytes: Vec<u8>) -> io::Result<usize> {
+    let mut r = Reader::new(bytes);
+    if &r.take::<4>()? != MAGIC {
+        return Err(invalid("not a PetToy colony save"));
+    }
+    let version = r.u16()?;
+    let saved_desktop = match version {
+        1 => Desktop::single(r.u32()?.max(1), r.u32()?.max(1)),
+        2..=VERSION => Desktop::decode(&mut r)?,
+        _ => {
+            return Err(invalid(format!(
+                "unsupported colony save version {version}"
+            )))
+        }
+    };
+    let elapsed = r.f64()?;
+    let next_id = if version >= 4 { r.u64()? } else { 0 };
+
+    let cat_count = r.u32()? as usize;
+    let mut cats = Vec::with_capacity(cat_count.min(1 << 16));
+    for _ in 0..cat_count {
+        let pos = r.vec2()?;
+        let vel = r.vec2()?;
+        let state = BehaviorState::from_u8(r.u8()?).ok_or_else(|| invalid("unknown cat state"))?;
+        let timer = r.f32()?;
+        let personality = Personality {
+            laziness: r.f32()?,
+            energy: r.f32()?,
+            curiosity: r.f32()?,
+            skittishness: r.f32()?,
+        };
+        let appearance = Appearance {
+            color: r.u32()?,
+            pattern: r.u8()?,
+            size: r.f32()?,
+        };
+        let name = r.str()?;
+        let age = if version >= 3 { r.f32()? } else { 0.0 };
+        let (id, journal) = if version >= 4 {
+            let id = Some(r.u64()?).filter(|&id| id != NO_ID);
+            (id, decode_journal(&mut r, version)?)
+        } else {
+            (None, Journal::default())
+        };
+        let needs = if version >= 5 {
+            Needs {
+                energy: r.f32()?,
+                hunger: r.f32()?,
+                social: r.f32()?,
+                play: r.f32()?,
+                comfort: r.f32()?,
+                motive: None,
+            }
+        } else {
+            Needs::default()
+        };
+        let clan = if version >= 7 {
+            Some(Some(r.u8()?).filter(|&c| c != NO_CLAN))
+        } else {
+            None
+        };
+        let trust = if version >= 8 {
+            Trust(r.f32()?)
+        } else {
+            Trust::default()
+        };
+        let flags = r.u8()?;
+        let pile = if flags & HAS_PILE != 0 {
+            Some(r.f32()?)
+        } else {
+            None
+        };
+        let gift = if flags & HAS_GIFT != 0 {
+            Some(r.f32()?)
+        } else {
+            None
+        };
+        let target = if flags & HAS_TARGET != 0 {
+            Some(r.u32()?)
+        } else {
+            None
+        };
+        let stacked = if flags & HAS_STACKED != 0 {
+            Some(r.u32()?)
+        } else {
+            None
+        };
+        let kitten = if flags & HAS_KITTEN != 0 {
+            Some(r.f32()?)
+        } else {
+            None
+        };
+        let bond = if flags & HAS_BOND != 0 {
+            Some((r.u32()?, r.f32()?))
+        } else {
+            None
+        };
+        cats.push(SavedCat {
+            pos,
+            vel,
+            state: CatState { state, timer },
+            personality,
+            appearance,
+            name,
+            pile,
+            gift,
+            target,
+            stacked,
+            age,
+            id,
+            journal,
+            needs,
+            clan,
+            trust,
+            kitten,
+            bond,
+        });
+    }
+
+    let toys = if version >= 10 {
+        let mut toys = Vec::new();
+        for _ in 0..r.u16()? {
+            let kind = ToyKind::from_u8(r.u8()?).ok_or_else(|| invalid("unknown toy"))?;
+            toys.push(SavedToy {
+                kind,
+                pos: r.vec2()?,
+                vel: r.vec2()?,
+                lifetime: r.f32()?,
+                shattered: r.u8()? != 0,
+            });
+        }
+        toys
+    } else {
+        decode_legacy_toys(&mut r)?
+    };
+
+    let grid_size = r.u16()? as usize;
+    let mut heat = Vec::with_capacity(grid_size * grid_size);
+    for _ in 0..grid_size * grid_size {
+        heat.push(r.f32()?);
+    }
+
+    let mut relations = Relationships::default();
+    if version >= 6 {
+        for _ in 0..r.u32()? {
+            relations.set(CatId(r.u64()?), CatId(r.u64()?), r.f32()?);
+        }
+    }
+
+    let (scent_size, scent) = if version >= 7 {
+        let grid_size = r.u16()? as usize;
+        let layers = r.u8()? as usize;
+        let mut cells = Vec::with_capacity(layers * grid_size * grid_size);
+        for _ in 0..layers * grid_size * grid_size {
+            cells.push(r.f32()?);
+        }
+        (grid_size, cells)
+    } else {
+        (0, Vec::new())
+    };
+
+    // --- Parsed cleanly: swap the colony in ---
+    let rehome = |p: Vec2| sim.desktop.rehome(&saved_desktop, p);
+
+    sim.world.clear();
+    sim.elapsed = elapsed;
+    // Never hand out an id a loaded cat already has.
+    let max_id = cats.iter().filter_map(|c| c.id).max();
+    sim.cat_ids.next = next_id.max(max_id.map_or(0, |id| id + 1));
+
+    let entities: Vec<hecs::Entity> = cats
+        .iter()
+        .map(|cat| {
+            let pos = rehome(cat.pos);
+            sim.world.spawn((
+                Position(pos),
+                PrevPosition(pos),
+                Velocity(cat.vel),
+                cat.state,
+                cat.personality,
+                cat.appearance,
+                SpatialCell(0),
+                cat.id.map_or_else(|| sim.cat_ids.allocate(), CatId),
+                CatName(cat.name.clone()),
+                Age(cat.age),
+                cat.journal.clone(),
+                cat.needs,
+                cat.trust,
+            ))
+        })
+        .collect();
+    let lookup = |index: u32| entities.get(index as usize).copied();
+
+    for (cat, &entity) in cats.iter().zip(&entities) {
+        // Cats from before clans pick one.
+        let clan = cat.clan.unwrap_or_else(|| Some(sim.rng.u8(0..CLANS as u8)));
+        if let Some(clan) = clan {
+            let _ = sim.world.insert_one(entity, Clan(clan));
+        }
+        if let Some(offset) = cat.pile {
+            let _ = sim.world.insert_one(
+                entity,
+                SleepingPile {
+                    breathing_offset: offset,
+                },
+            );
+        }
+        if let Some(timer) = cat.gift {
+            let _ = sim.world.insert_one(entity, GiftCarrier { timer });
+        }
+        if let Some(base) = cat.stacked.and_then(lookup) {
+            let _ = sim.world.insert_one(entity, Stacked { base });
+        }
+        if let Some(adult_size) = cat.kitten {
+            let _ = sim.world.insert_one(entity, Kitten { adult_size });
+        }
+        if let Some((partner, play_time)) = cat.bond.and_then(|(i, t)| Some((lookup(i)?, t))) {
+            let _ = sim.world.insert_one(entity, Bond { partner, play_time });
+        }
+        match cat.target.and_then(lookup) {
+            Some(target) => {
+                let _ = sim.world.insert_one(entity, InteractionTarget(target));
+            }
+            // Partner wasn't saved: drop out of any paired activity.
+            None if matches!(
+                cat.state.state,
+                BehaviorState::ChasingCat
+                    | BehaviorState::Playing
+                    | BehaviorState::Pouncing
+                    | BehaviorState::Fighting
+            ) =>
+            {
+                if let Ok(mut state) = sim.world.get::<&mut CatState>(entity) {
+                    state.state = BehaviorState::Idle;
+                    state.timer = 1.0;
+                }
+            }
+            None => {}
+        }
+    }
+
+    for saved in toys {
+        let entity = toy::spawn(&mut sim.world, saved.kind, rehome(saved.pos));
+        if let Ok((vel, life)) = sim.world.query_one_mut::<(&mut Velocity, &mut Lifetime)>(entity)
+        {
+            vel.0 = saved.vel;
+            life.0 = saved.lifetime;
+        }
+        if saved.shattered {
+            systems::toys::shatter(&mut sim.world, entity);
+        }
+    }
+    sim.toys.rebuild(&sim.world);
+
+    if grid_size == sim.heatmap.grid_size {
+        sim.heatmap.cells = heat;
+    }
+    sim.relations = relations;
+    sim.scent.layers.iter_mut().flatten().for_each(|cell| *cell = 0.0);
+    if scent_size == sim.scent.grid_size {
+        let cells_per_layer = scent_size * scent_size;
+        for (layer, cells) in sim.scent.layers.iter_mut().zip(scent.chunks(cells_per_layer)) {
+            layer.copy_from_slice(cells);
+        }
+    }
+
+    Ok(entities.len())
+}
+
+fn encode_journal(out: &mut Writer, journal: &Journal) {
+    for total in [
+        journal.fights_won,
+        journal.fights_lost,
+        journal.gifts_delivered,
+        journal.treats_eaten,
+        journal.towers_climbed,
+        journal.piles_joined,
+        journal.zoomies,
+        journal.prey_caught,
+    ] {
+        out.u32(total);
+    }
+    out.u8(journal.recent.len() as u8);
+    for entry in &journal.recent {
+        out.f32(entry.age);
+        out.u8(entry.event as u8);
+    }
+}
+
+fn decode_journal(r: &mut Reader, version: u16) -> io::Result<Journal> {
+    let mut journal = Journal {
+        fights_won: r.u32()?,
+        fights_lost: r.u32()?,
+        gifts_delivered: r.u32()?,
+        treats_eaten: r.u32()?,
+        towers_climbed: r.u32()?,
+        piles_joined: r.u32()?,
+        zoomies: r.u32()?,
+        prey_caught: if version >= 9 { r.u32()? } else { 0 },
+        ..Journal::default()
+    };
+    let len = r.u8()? as usize;
+    if len > JOURNAL_LEN {
+        return Err(invalid("cat journal too long"));
+    }
+    for _ in 0..len {
+        let age = r.f32()?;
+        let event =
+            JournalEvent::from_u8(r.u8()?).ok_or_else(|| invalid("unknown journal event"))?;
+        journal.recent.push_back(JournalEntry { age, event });
+    }
+    Ok(journal)
+}
+
+// ---------------------------------------------------------------------------
+// Files
+// ---------------------------------------------------------------------------
+
+/// Write the colony to `path`, via a temp file so a crash mid-write never
+/// clobbers the previous save.
+pub fn save_to(sim: &Simulation, path: &Path) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, encode(sim))?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Load a colony from `path` into `sim`. Returns cats loaded.
+pub fn load_from(sim: &mut Simulation, path: &Path) -> io::Result<usize> {
+    decode_into(sim, std::fs::read(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::SimInput;
+
+    fn grown_colony() -> Simulation {
+        let mut sim = Simulation::with_seed(1280, 720, 99);
+        sim.spawn_cats(40);
+        sim.heatmap.enabled = true;
+        let mut input = SimInput {
+            mouse: Vec2::new(640.0, 360.0),
+            ..SimInput::default()
+        };
+        for tick in 0..900 {
+            input.middle_down = tick % 120 < 2;
+            input.right_down = tick == 60;
+            input.spawn_box = tick == 30;
+            sim.tick(&input);
+        }
+        sim
+    }
+
+    #[test]
+    fn round_trip_keeps_cats() {
+        let sim = grown_colony();
+        let mut restored = Simulation::with_seed(1280, 720, 1);
+        let loaded = decode_into(&mut restored, encode(&sim)).expect("valid save");
+
+        assert_eq!(loaded, sim.cat_count());
+        let names = |s: &Simulation| {
+            let mut v: Vec<String> = s
+                .world
+                .query::<&CatName>()
+                .iter()
+                .map(|(_, n)| n.0.clone())
+                .collect();
+            v.sort();
+            v
+        };
+        assert_eq!(names(&sim), names(&restored));
+        let toys = |s: &Simulation| {
+            let mut v: Vec<(u8, [f32; 2], f32)> = s
+                .world
+                .query::<(&Toy, &Position, &Lifetime)>()
+                .iter()
+                .map(|(_, (toy, pos, life))| (toy.kind as u8, pos.0.into(), life.0))
+                .collect();
+            v.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            v
+        };
+        assert!(toys(&sim).iter().any(|t| t.0 == ToyKind::Box as u8));
+        assert_eq!(toys(&sim), toys(&restored));
+        assert_eq!(sim.heatmap.cells, restored.heatmap.cells);
+        assert_eq!(sim.scent.layers, restored.scent.layers);
+        // Nothing transient survives, and every reference points at a live cat.
+        assert_eq!(restored.world.query::<&SpawnAnimation>().iter().count(), 0);
+        for (_, target) in restored.world.query::<&InteractionTarget>().iter() {
+            assert!(restored.world.contains(target.0));
+        }
+    }
+
+    #[test]
+    fn ids_and_memories_survive() {
+        let sim = grown_colony();
+        let mut restored = Simulation::with_seed(1280, 720, 1);
+        decode_into(&mut restored, encode(&sim)).expect("valid save");
+
+        let journals = |s: &Simulation| {
+            let mut v: Vec<(CatId, Journal, Needs, Clan, Trust)> = s
+                .world
+                .query::<(&CatId, &Journal, &Needs, &Clan, &Trust)>()
+                .iter()
+                .map(|(_, (id, j, n, c, t))| {
+                    (*id, j.clone(), Needs { motive: None, ..*n }, *c, *t)
+                })
+                .collect();
+            v.sort_by_key(|(id, ..)| *id);
+            v
+        };
+        let saved = journals(&sim);
+        assert_eq!(saved.len(), sim.cat_count());
+        assert!(saved.windows(2).all(|w| w[0].0 < w[1].0), "ids are unique");
+        assert!(saved.iter().any(|cat| cat.4 != Trust::default()), "treats won some over");
+        assert_eq!(saved, journals(&restored));
+        assert_eq!(restored.cat_ids, sim.cat_ids);
+        assert!(!sim.relations.is_empty());
+        assert_eq!(restored.relations, sim.relations);
+    }
+
+    #[test]
+    fn kittens_and_bonds_survive() {
+        let mut sim = Simulation::with_seed(1280, 720, 3);
+        sim.spawn_cats(1);
+        let parent = sim.world.iter().next().expect("one cat").entity();
+        let traits = (
+            *sim.world.get::<&Personality>(parent).unwrap(),
+            *sim.world.get::<&Appearance>(parent).unwrap(),
+        );
+        let kitten = crate::cat::spawn_kitten(
+            &mut sim.world,
+            Vec2::new(300.0, 300.0),
+            [traits, traits],
+            0.1,
+            &mut sim.cat_ids,
+            &mut sim.rng,
+        );
+        sim.world.get::<&mut Age>(kitten).unwrap().0 = 42.0;
+        let _ = sim.world.insert_one(
+            kitten,
+            Bond {
+                partner: parent,
+                play_time: 3.5,
+            },
+        );
+
+        let mut restored = Simulation::with_seed(1280, 720, 1);
+        decode_into(&mut restored, encode(&sim)).expect("valid save");
+        let mut query = restored.world.query::<(&Age, &Kitten, &Bond)>();
+        let (_, (age, grown, bond)) = query.iter().next().expect("kitten restored");
+        assert_eq!(age.0, 42.0);
+        assert_eq!(
+            grown.adult_size,
+            sim.world.get::<&Kitten>(kitten).unwrap().adult_size
+        );
+        assert_eq!(bond.play_time, 3.5);
+        assert!(restored.world.get::<&Kitten>(bond.partner).is_err());
+    }
+
+    #[test]
+    fn rescales_to_new_screen() {
+        let mut sim = Simulation::with_seed(1000, 1000, 5);
+        sim.world.spawn((
+            Position(Vec2::new(500.0, 250.0)),
+            Velocity(Vec2::ZERO),
+            CatState {
+                state: BehaviorState::Idle,
+                timer: 1.0,
+            },
+        ));
+        let mut restored = Simulation::with_seed(2000, 500, 5);
+        decode_into(&mut restored, encode(&sim)).expect("valid save");
+        let (_, pos) = restored
+            .world
+            .query_mut::<&Position>()
+            .into_iter()
+            .next()
+            .expect("one cat");
+        assert_eq!(pos.0, Vec2::new(1000.0, 125.0));
+    }
+
+    #[test]
+    fn rehomes_cats_from_unplugged_monitor() {
+        use crate::desktop::Monitor;
+        let mut sim = Simulation::with_seed(1920, 1080, 5);
+        sim.set_desktop(Desktop::from_virtual(&[
+            Monitor::new(0.0, 0.0, 1920.0, 1080.0),
+            Monitor::new(1920.0, 0.0, 1000.0, 1000.0),
+        ]));
+        for x in [100.0, 2420.0] {
+            sim.world.spawn((
+                Position(Vec2::new(x, 500.0)),
+                Velocity(Vec2::ZERO),
+                CatState {
+                    state: BehaviorState::Idle,
+                    timer: 1.0,
+                },
+            ));
+        }
+        let mut restored = Simulation::with_seed(1920, 1080, 5);
+        decode_into(&mut restored, encode(&sim)).expect("valid save");
+        let mut xs: Vec<f32> = restored
+            .world
+            .query_mut::<&Position>()
+            .into_iter()
+            .map(|(_, p)| p.0.x)
+            .collect();
+        xs.sort_by(f32::total_cmp);
+        // The cat on the primary stays put; the other lands mid-screen.
+        assert_eq!(xs, vec![100.0, 960.0]);
+    }
+
+    #[test]
+    fn bad_save_leaves_colony_alone() {
+        let sim = grown_colony();
+        let mut bytes = encode(&sim);
+        bytes.truncate(bytes.len() / 2);
+        let mut other = Simulation::with_seed(1280, 720, 2);
+        other.spawn_cats(3);
+        assert!(decode_into(&mut other, bytes).is_err());
+        assert_eq!(other.cat_count(), 3);
+    }
+}
@@ -0,0 +1,152 @@
+//! Territory scent — one grid layer per clan, laid down by cats wherever
+//! they spend time and fading when they stop coming by.
+//!
+//! Same grid as the cursor [`Heatmap`](crate::heatmap::Heatmap), so it can be
+//! drawn by the same overlay. `systems::territory` deposits scent and has
+//! territorial cats hiss at, fight or steer clear of their rivals' patches.
+
+use glam::Vec2;
+
+/// Number of clans; every cat belongs to one (see `components::Clan`).
+pub const CLANS: usize = 4;
+/// Grid resolution (cells per axis), matching the heatmap texture.
+const GRID_SIZE: usize = 64;
+/// Seconds for untended scent to fade to about a third.
+const SCENT_LIFETIME: f32 = 120.0;
+
+pub struct ScentField {
+    /// One row-major layer of scent strengths in [0, 1] per clan.
+    pub layers: Vec<Vec<f32>>,
+    pub grid_size: usize,
+    pub cell_w: f32,
+    pub cell_h: f32,
+    /// Draw the territories in place of the cursor heatmap.
+    pub shown: bool,
+}
+
+impl ScentField {
+    pub fn new(screen_w: f32, screen_h: f32) -> Self {
+        Self {
+            layers: vec![vec![0.0; GRID_SIZE * GRID_SIZE]; CLANS],
+            grid_size: GRID_SIZE,
+            cell_w: screen_w / GRID_SIZE as f32,
+            cell_h: screen_h / GRID_SIZE as f32,
+            shown: false,
+        }
+    }
+
+    /// Update on screen resize.
+    pub fn resize(&mut self, screen_w: f32, screen_h: f32) {
+        self.cell_w = screen_w / GRID_SIZE as f32;
+        self.cell_h = screen_h / GRID_SIZE as f32;
+    }
+
+    fn cell(&self, pos: Vec2) -> Option<usize> {
+        if pos.x < 0.0 || pos.y < 0.0 {
+            return None;
+        }
+        let cx = (pos.x / self.cell_w) as usize;
+        let cy = (pos.y / self.cell_h) as usize;
+        (cx < GRID_SIZE && cy < GRID_SIZE).then_some(cy * GRID_SIZE + cx)
+    }
+
+    /// Add `amount` of `clan`'s scent at `pos`, capped at 1.0.
+    pub fn deposit(&mut self, clan: usize, pos: Vec2, amount: f32) {
+        let Some(idx) = self.cell(pos) else {
+            return;
+        };
+        if let Some(layer) = self.layers.get_mut(clan) {
+            layer[idx] = (layer[idx] + amount).min(1.0);
+        }
+    }
+
+    /// Fade every layer.
+    pub fn fade(&mut self, dt: f32) {
+        let keep = (-dt / SCENT_LIFETIME).exp();
+        for cell in self.layers.iter_mut().flatten() {
+            *cell *= keep;
+        }
+    }
+
+    /// `clan`'s scent at `pos`, 0.0-1.0.
+    pub fn sample(&self, clan: usize, pos: Vec2) -> f32 {
+        match (self.layers.get(clan), self.cell(pos)) {
+            (Some(layer), Some(idx)) => layer[idx],
+            _ => 0.0,
+        }
+    }
+
+    /// The strongest clan other than `clan` at `pos`, and how strong.
+    pub fn strongest_rival(&self, clan: usize, pos: Vec2) -> Option<(usize, f32)> {
+        (0..self.layers.len())
+            .filter(|&c| c != clan)
+            .map(|c| (c, self.sample(c, pos)))
+            .filter(|&(_, s)| s > 0.0)
+            .fold(None, |best, cur| match best {
+                Some((_, s)) if s >= cur.1 => best,
+                _ => Some(cur),
+            })
+    }
+
+    /// Direction in which other clans' scent gets stronger around `pos`
+    /// (not normalized; zero on even ground).
+    pub fn rival_gradient(&self, clan: usize, pos: Vec2) -> Vec2 {
+        let rival = |p: Vec2| {
+            (0..self.layers.len())
+                .filter(|&c| c != clan)
+                .map(|c| self.sample(c, p))
+                .fold(0.0, f32::max)
+        };
+        let dx = Vec2::new(self.cell_w, 0.0);
+        let dy = Vec2::new(0.0, self.cell_h);
+        Vec2::new(
+            rival(pos + dx) - rival(pos - dx),
+            rival(pos + dy) - rival(pos - dy),
+        )
+    }
+
+    /// Flatten to R8 texture data for GPU upload: each cell shows its
+    /// strongest clan, so borders read as dips between neighborhoods.
+    pub fn to_texture_data(&self) -> Vec<u8> {
+        (0..GRID_SIZE * GRID_SIZE)
+            .map(|i| {
+                let strongest = self.layers.iter().map(|l| l[i]).fold(0.0, f32::max);
+                (strongest * 255.0).min(255.0) as u8
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scent_builds_up_and_fades() {
+        let mut scent = ScentField::new(640.0, 640.0);
+        let den = Vec2::new(105.0, 55.0);
+        for _ in 0..10 {
+            scent.deposit(1, den, 0.2);
+        }
+        assert_eq!(scent.sample(1, den), 1.0, "capped at full strength");
+        assert_eq!(scent.sample(0, den), 0.0, "clans keep separate layers");
+        assert_eq!(scent.sample(1, Vec2::new(-5.0, 55.0)), 0.0);
+
+        scent.fade(SCENT_LIFETIME);
+        assert!((scent.sample(1, den) - (-1.0f32).exp()).abs() < 0.001);
+        assert_eq!(scent.to_texture_data()[5 * GRID_SIZE + 10], 93);
+    }
+
+    #[test]
+    fn finds_rival_territory() {
+        let mut scent = ScentField::new(640.0, 640.0);
+        let border = Vec2::new(320.0, 320.0);
+        scent.deposit(2, border + Vec2::new(10.0, 0.0), 0.8);
+        scent.deposit(3, border, 0.4);
+        scent.deposit(0, border, 1.0);
+
+        assert_eq!(scent.strongest_rival(0, border), Some((3, 0.4)));
+        assert_eq!(scent.strongest_rival(0, Vec2::new(5.0, 5.0)), None);
+        assert!(scent.rival_gradient(0, border - Vec2::new(10.0, 0.0)).x > 0.0);
+    }
+}
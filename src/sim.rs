@@ -0,0 +1,597 @@
+use glam::Vec2;
+
+use crate::automation::{Automation, Conditions, Weekday};
+use crate::cat;
+use crate::click::ClickState;
+use crate::config::Config;
+use crate::daynight::DayNightState;
+use crate::desktop::Desktop;
+use crate::ecs::components::{CatId, CatState, Position, PrevPosition, SpawnAnimation, Velocity};
+use crate::ecs::systems;
+use crate::ecs::systems::interaction::InteractionBuffers;
+use crate::ecs::systems::mouse::CursorState;
+use crate::ecs::systems::physics::PhysicsWorld;
+use crate::ecs::systems::prey::Wildlife;
+use crate::ecs::systems::spawn::BounceEvent;
+use crate::ecs::systems::toys::ToyIndex;
+use crate::ecs::systems::window_aware::{DesktopWindow, WindowChange, WindowTracker};
+use crate::heatmap::Heatmap;
+use crate::mode::{AtkAction, ModeState};
+use crate::nav::Navigator;
+use crate::relations::Relationships;
+use crate::replay::InputRecorder;
+use crate::scent::ScentField;
+use crate::spatial::{CatSnapshot, SpatialHash};
+use crate::timer::SystemTimers;
+use crate::toy::{self, FeatherWand, ToyKind};
+
+/// Target simulation tick rate (seconds per tick).
+pub const TICK_RATE: f64 = 1.0 / 60.0;
+/// Spatial hash table size (prime-ish for good distribution).
+const SPATIAL_TABLE_SIZE: usize = 1024;
+
+// ---------------------------------------------------------------------------
+// Input / events
+// ---------------------------------------------------------------------------
+
+/// Everything the outside world tells the simulation for one tick.
+#[derive(Debug, Clone, Copy)]
+pub struct SimInput {
+    /// Cursor position in colony pixels (see [`Desktop::to_colony`]).
+    pub mouse: Vec2,
+    pub left_down: bool,
+    pub right_down: bool,
+    pub middle_down: bool,
+    /// Drop a cardboard box at the cursor this tick.
+    pub spawn_box: bool,
+    /// Drop a water glass at the cursor this tick.
+    pub spawn_glass: bool,
+    /// Take out or put away the feather wand this tick.
+    pub toggle_wand: bool,
+    /// Seconds since the user last touched mouse or keyboard.
+    pub idle_seconds: f64,
+    /// Local wall-clock hour (0.0-24.0) for the day/night cycle.
+    pub hour: f32,
+    /// Local day of the week, for mode rules.
+    pub weekday: Weekday,
+    /// Battery charge in percent while on battery power; `None` on mains
+    /// or without a battery.
+    pub battery: Option<f32>,
+}
+
+impl Default for SimInput {
+    fn default() -> Self {
+        Self {
+            mouse: Vec2::ZERO,
+            left_down: false,
+            right_down: false,
+            middle_down: false,
+            spawn_box: false,
+            spawn_glass: false,
+            toggle_wand: false,
+            idle_seconds: 0.0,
+            hour: 12.0,
+            weekday: Weekday::default(),
+            battery: None,
+        }
+    }
+}
+
+/// Things that happened during the last tick that a front-end may want to show.
+#[derive(Debug, Default)]
+pub struct TickEvents {
+    /// Positions of water glasses that shattered.
+    pub shattered: Vec<Vec2>,
+    /// Spawn drop-in landings.
+    pub bounces: Vec<BounceEvent>,
+    /// Where kittens were born.
+    pub births: Vec<Vec2>,
+    /// Desktop windows that opened, closed or moved.
+    pub windows: Vec<WindowChange>,
+}
+
+impl TickEvents {
+    fn clear(&mut self) {
+        self.shattered.clear();
+        self.bounces.clear();
+        self.births.clear();
+        self.windows.clear();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Simulation
+// ---------------------------------------------------------------------------
+
+/// The whole colony: ECS world, toys, modes and every piece of per-tick state.
+/// Advances in fixed [`TICK_RATE`] steps and never touches the OS.
+pub struct Simulation {
+    pub world: hecs::World,
+    /// Next stable [`crate::ecs::components::CatId`] to hand out.
+    pub cat_ids: cat::CatIds,
+    /// Who likes whom, by [`CatId`].
+    pub relations: Relationships,
+    pub grid: SpatialHash,
+    pub snapshots: Vec<CatSnapshot>,
+    pub interaction_bufs: InteractionBuffers,
+    pub cursor: CursorState,
+    pub mode: ModeState,
+    /// Mode rules from `config.automation`, steering `mode`.
+    pub automation: Automation,
+    pub click: ClickState,
+    pub heatmap: Heatmap,
+    /// Clan territories, laid down by the cats themselves.
+    pub scent: ScentField,
+    /// Obstacle-aware routing for cats heading to treats, toys and the cursor.
+    pub nav: Navigator,
+    /// Mice, bugs and butterflies for the cats to hunt.
+    pub wildlife: Wildlife,
+    /// Toys and treats in play (the toys themselves live in `world`).
+    pub toys: ToyIndex,
+    pub physics: PhysicsWorld,
+    pub wand: FeatherWand,
+    pub daynight: DayNightState,
+    /// Desktop windows cats can perch on (see [`Self::set_desktop_windows`]).
+    desktop_windows: Vec<DesktopWindow>,
+    /// Goes up every time `desktop_windows` changes.
+    windows_generation: u64,
+    /// What `desktop_windows` looked like at the last change.
+    window_tracker: WindowTracker,
+    pub timers: SystemTimers,
+    /// Tuning constants (defaults, or a config file — see [`Self::apply_config`]).
+    pub config: Config,
+    /// Seed the colony was created with. Same seed + same input = same colony.
+    pub seed: u64,
+    /// The only source of randomness for spawning and every system.
+    pub rng: fastrand::Rng,
+    /// Events from the most recent tick.
+    pub events: TickEvents,
+    pub tick_count: u64,
+    /// Simulated seconds since start.
+    pub elapsed: f64,
+    /// Monitor layout the colony lives on.
+    pub desktop: Desktop,
+    /// Size of the desktop's bounding box.
+    pub screen_w: u32,
+    pub screen_h: u32,
+    spawn_accumulator: f64,
+    /// Active input recording, if any (see [`Self::start_recording`]).
+    recorder: Option<InputRecorder>,
+}
+
+impl Simulation {
+    /// Empty colony for a single screen of the given size, seeded from
+    /// entropy. Multi-monitor front-ends follow up with [`Self::set_desktop`].
+    /// Call [`Self::spawn_cats`] to populate.
+    pub fn new(screen_w: u32, screen_h: u32) -> Self {
+        Self::with_seed(screen_w, screen_h, fastrand::u64(..))
+    }
+
+    /// Empty colony with a fixed RNG seed, for reproducible runs and replays.
+    pub fn with_seed(screen_w: u32, screen_h: u32, seed: u64) -> Self {
+        let config = Config::default();
+        let initial = config.population.initial_cat_count;
+        Self {
+            world: hecs::World::new(),
+            cat_ids: cat::CatIds::default(),
+            relations: Relationships::default(),
+            grid: SpatialHash::new(config.spatial.cell_size, SPATIAL_TABLE_SIZE),
+            snapshots: Vec::with_capacity(initial),
+            interaction_bufs: InteractionBuffers::new(initial),
+            cursor: CursorState::new(),
+            mode: ModeState::new(),
+            automation: Automation::default(),
+            click: ClickState::new(),
+            heatmap: Heatmap::new(screen_w as f32, screen_h as f32),
+            scent: ScentField::new(screen_w as f32, screen_h as f32),
+            nav: Navigator::new(&Desktop::single(screen_w, screen_h)),
+            wildlife: Wildlife::new(),
+            toys: ToyIndex::new(),
+            physics: PhysicsWorld::new(),
+            wand: FeatherWand::new(),
+            daynight: DayNightState::new(),
+            desktop_windows: Vec::new(),
+            windows_generation: 0,
+            window_tracker: WindowTracker::default(),
+            timers: SystemTimers::new(),
+            config,
+            seed,
+            rng: fastrand::Rng::with_seed(seed),
+            events: TickEvents::default(),
+            tick_count: 0,
+            elapsed: 0.0,
+            desktop: Desktop::single(screen_w, screen_h),
+            screen_w,
+            screen_h,
+            spawn_accumulator: 0.0,
+            recorder: None,
+        }
+    }
+
+    /// Begin logging every tick's input so the run can be replayed with
+    /// [`crate::replay::Replayer`]. Start on a fresh colony, before any cats
+    /// are spawned, or the replay will not match.
+    pub fn start_recording(&mut self) {
+        if self.tick_count > 0 || self.cat_count() > 0 {
+            log::warn!("Recording started mid-run — replay will diverge");
+        }
+        self.recorder = Some(InputRecorder::new(self));
+    }
+
+    /// Stop recording and return the encoded input log.
+    pub fn take_recording(&mut self) -> Option<Vec<u8>> {
+        self.recorder.take().map(InputRecorder::finish)
+    }
+
+    /// FNV-1a hash of every cat's position, velocity and state. Two colonies
+    /// with the same checksum at the same tick are (for all practical
+    /// purposes) identical.
+    pub fn checksum(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let mut hash = FNV_OFFSET;
+        let mut feed = |bytes: &[u8]| {
+            for &b in bytes {
+                hash ^= b as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+        feed(&self.tick_count.to_le_bytes());
+        for (_, (pos, vel, state)) in self
+            .world
+            .query::<(&Position, &Velocity, &CatState)>()
+            .iter()
+        {
+            feed(&pos.0.x.to_bits().to_le_bytes());
+            feed(&pos.0.y.to_bits().to_le_bytes());
+            feed(&vel.0.x.to_bits().to_le_bytes());
+            feed(&vel.0.y.to_bits().to_le_bytes());
+            feed(&[state.state as u8]);
+            feed(&state.timer.to_bits().to_le_bytes());
+        }
+        hash
+    }
+
+    /// Swap in new tuning, e.g. after the config file was edited. Takes
+    /// effect from the next tick; a new cell size rebuilds the spatial hash.
+    pub fn apply_config(&mut self, config: Config) {
+        if config.spatial.cell_size != self.grid.cell_size() {
+            self.grid = SpatialHash::new(config.spatial.cell_size, SPATIAL_TABLE_SIZE);
+        }
+        self.mode.set_tuning(config.modes.clone(), config.afk);
+        if config.automation != self.config.automation {
+            self.automation.reset(&mut self.mode);
+        }
+        self.config = config;
+    }
+
+    /// Update screen dimensions (single window resized).
+    pub fn resize(&mut self, screen_w: u32, screen_h: u32) {
+        self.set_desktop(Desktop::single(screen_w, screen_h));
+    }
+
+    /// Desktop windows cats can perch on, topmost first.
+    pub fn desktop_windows(&self) -> &[DesktopWindow] {
+        &self.desktop_windows
+    }
+
+    /// Goes up every time [`Self::desktop_windows`] changes.
+    pub fn windows_generation(&self) -> u64 {
+        self.windows_generation
+    }
+
+    /// Take a fresh window list from the front-end. A list identical to the
+    /// current one is dropped, so ticks only look for moved, opened and
+    /// closed windows after something changed.
+    pub fn set_desktop_windows(&mut self, windows: Vec<DesktopWindow>) {
+        if windows != self.desktop_windows {
+            self.desktop_windows = windows;
+            self.windows_generation += 1;
+        }
+    }
+
+    /// Switch to a new monitor layout. Cats, toys and treats on a monitor
+    /// that changed or went away are re-homed (see [`Desktop::rehome`]).
+    pub fn set_desktop(&mut self, desktop: Desktop) {
+        if desktop == self.desktop {
+            return;
+        }
+        let old = std::mem::replace(&mut self.desktop, desktop);
+        let new = &self.desktop;
+        let rehome = |p: Vec2| new.rehome(&old, p);
+
+        for (_, (pos, prev_pos, anim)) in self.world.query_mut::<(
+            &mut Position,
+            Option<&mut PrevPosition>,
+            Option<&mut SpawnAnimation>,
+        )>() {
+            let moved = rehome(pos.0) - pos.0;
+            pos.0 += moved;
+            if let Some(prev_pos) = prev_pos {
+                prev_pos.0 += moved;
+            }
+            // Cats mid-drop fall onto the same spot on the new screen.
+            if let Some(anim) = anim {
+                anim.start_y += moved.y;
+                anim.target_y += moved.y;
+            }
+        }
+
+        let size = self.desktop.size();
+        self.screen_w = size.x as u32;
+        self.screen_h = size.y as u32;
+        self.heatmap.resize(size.x, size.y);
+        self.scent.resize(size.x, size.y);
+        self.nav.rebuild(
+            &self.desktop,
+            &self.desktop_windows,
+            &self.heatmap,
+            &self.config.navigation,
+        );
+    }
+
+    /// Number of live cats.
+    pub fn cat_count(&self) -> usize {
+        self.world.query::<()>().with::<&CatState>().iter().count()
+    }
+
+    /// Drop `count` new cats in from above the screen.
+    pub fn spawn_cats(&mut self, count: usize) {
+        cat::spawn_cats(
+            &mut self.world,
+            count,
+            &self.desktop,
+            &mut self.cat_ids,
+            &mut self.rng,
+        );
+    }
+
+    /// Spawn or despawn cats to match target count.
+    pub fn set_cat_count(&mut self, target: usize) {
+        let current = self.cat_count();
+        if target > current {
+            self.spawn_cats(target - current);
+        } else if target < current {
+            let to_remove = current - target;
+            let entities: Vec<hecs::Entity> = self
+                .world
+                .query::<()>()
+                .with::<&CatState>()
+                .iter()
+                .take(to_remove)
+                .map(|(e, _)| e)
+                .collect();
+            for entity in entities {
+                if let Ok(id) = self.world.get::<&CatId>(entity).map(|id| *id) {
+                    self.relations.forget(id);
+                }
+                let _ = self.world.despawn(entity);
+            }
+        }
+    }
+
+    /// Advance the colony by one fixed tick.
+    pub fn tick(&mut self, input: &SimInput) {
+        let dt = TICK_RATE as f32;
+        let mouse = input.mouse;
+        self.events.clear();
+
+        if let Some(mut recorder) = self.recorder.take() {
+            recorder.record(self, input);
+            self.recorder = Some(recorder);
+        }
+
+        // AFK escalation
+        match self.mode.update_afk(input.idle_seconds, TICK_RATE) {
+            AtkAction::SpawnCats(n) => self.spawn_cats(n),
+            AtkAction::ScatterAndDespawn(n) => {
+                log::info!("User returned — despawning {} bonus cats", n);
+                self.set_cat_count(self.cat_count().saturating_sub(n));
+            }
+            AtkAction::Scatter => {
+                log::info!("User returned — scattering cats");
+            }
+            AtkAction::None => {}
+        }
+
+        // Mode rules
+        let foreground = self
+            .desktop_windows
+            .iter()
+            .find(|w| w.state.focused)
+            .map_or("", |w| w.process.as_str());
+        let conditions = Conditions {
+            hour: input.hour,
+            weekday: input.weekday,
+            foreground,
+            idle_seconds: input.idle_seconds,
+            battery: input.battery,
+        };
+        if self
+            .automation
+            .update(&self.config.automation, &conditions, &mut self.mode, dt)
+        {
+            log::info!(
+                "Mode rule: {} ({})",
+                self.automation.describe(&self.config.automation),
+                self.mode.mode.label()
+            );
+        }
+
+        self.daynight.update(input.hour);
+
+        self.click
+            .update(input.left_down, input.right_down, input.middle_down, dt);
+
+        if self.click.middle_clicked {
+            toy::spawn(&mut self.world, ToyKind::Treat, mouse);
+        }
+        if self.click.right_clicked {
+            toy::spawn(&mut self.world, ToyKind::Yarn, mouse);
+        }
+        if input.spawn_box {
+            toy::spawn(&mut self.world, ToyKind::Box, mouse);
+            log::info!("Cardboard box placed at ({:.0}, {:.0})", mouse.x, mouse.y);
+        }
+        if input.spawn_glass {
+            toy::spawn(&mut self.world, ToyKind::Glass, mouse);
+            log::info!("Water glass placed at ({:.0}, {:.0})", mouse.x, mouse.y);
+        }
+
+        // Cats on windows that moved or closed since the last refresh ride
+        // along or tumble off; new windows draw curious cats
+        self.window_tracker.refresh(
+            &self.desktop_windows,
+            self.windows_generation,
+            &mut self.events.windows,
+        );
+        systems::window_aware::react(
+            &mut self.world,
+            &self.events.windows,
+            self.config.gravity.enabled,
+            &mut self.rng,
+        );
+
+        // Toys move, bump into each other, cats and window tops, then age
+        systems::physics::step(
+            &mut self.world,
+            &mut self.physics,
+            &self.desktop,
+            &self.desktop_windows,
+            &self.snapshots,
+            &self.grid,
+            dt,
+        );
+        self.events.shattered =
+            systems::toys::update(&mut self.world, &mut self.toys, &self.desktop, mouse, dt);
+        if input.toggle_wand {
+            self.wand.toggle(mouse);
+            log::info!("Feather wand {}", if self.wand.active { "out" } else { "put away" });
+        }
+
+        if self.heatmap.enabled {
+            self.heatmap.update(mouse.x, mouse.y, dt);
+        }
+
+        self.nav.refresh(
+            dt,
+            &self.desktop,
+            &self.desktop_windows,
+            &self.heatmap,
+            &self.config.navigation,
+        );
+
+        // Effective energy scale: mode preset * day/night modifier
+        let energy_scale = self.mode.behavior_energy_scale * self.daynight.energy_modifier;
+
+        systems::tick(
+            &mut self.world,
+            dt,
+            &self.desktop,
+            mouse.x,
+            mouse.y,
+            &mut self.cursor,
+            &mut self.rng,
+            &mut self.grid,
+            &mut self.snapshots,
+            &mut self.interaction_bufs,
+            &mut self.timers,
+            &self.heatmap,
+            self.mode.edge_affinity,
+            &self.desktop_windows,
+            energy_scale,
+            &self.config,
+            &mut self.relations,
+            &mut self.scent,
+            &mut self.nav,
+            &mut self.wildlife,
+            &mut self.events.bounces,
+        );
+        self.relations.decay(dt);
+
+        // The wand swings with the cursor as of this tick
+        self.wand.update(dt, &self.desktop, mouse, self.cursor.velocity);
+
+        // Cursor interactions (startle, laser, wand), then toys and treats
+        systems::click::update(
+            &mut self.world,
+            &self.click,
+            mouse,
+            &mut self.rng,
+            &mut self.wand,
+            &mut self.nav,
+        );
+        systems::toys::play(&mut self.world, &mut self.toys, &mut self.rng, &mut self.nav);
+
+        // Advance spawn drop-in animations
+        systems::spawn::update(&mut self.world, dt, &mut self.events.bounces);
+
+        // Aging, kitten growth and breeding (never past the target population)
+        let population = &self.config.population;
+        let room = population.target_cat_count.saturating_sub(self.cat_count());
+        systems::lifecycle::update(
+            &mut self.world,
+            dt,
+            &mut self.rng,
+            &self.config.lifecycle,
+            &mut self.cat_ids,
+            room,
+            &mut self.events.births,
+        );
+
+        // Population growth: gradually spawn cats up to target
+        self.elapsed += TICK_RATE;
+        let population = &self.config.population;
+        if self.elapsed > population.growth_delay {
+            let current = self.cat_count();
+            let target = population.target_cat_count;
+            if current < target {
+                self.spawn_accumulator += TICK_RATE * population.growth_rate;
+                let to_spawn = self.spawn_accumulator as usize;
+                if to_spawn > 0 {
+                    self.spawn_accumulator -= to_spawn as f64;
+                    self.spawn_cats(to_spawn.min(target - current));
+                }
+            }
+        }
+
+        self.tick_count += 1;
+
+        if let Some(mut recorder) = self.recorder.take() {
+            recorder.observe(self);
+            self.recorder = Some(recorder);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_headless() {
+        let mut sim = Simulation::new(1920, 1080);
+        let initial = sim.config.population.initial_cat_count;
+        sim.spawn_cats(initial);
+        let input = SimInput {
+            mouse: Vec2::new(960.0, 540.0),
+            ..SimInput::default()
+        };
+        for _ in 0..600 {
+            sim.tick(&input);
+        }
+        assert_eq!(sim.tick_count, 600);
+        // Growth kicks in after GROWTH_DELAY seconds of simulated time.
+        assert!(sim.cat_count() > initial);
+    }
+
+    #[test]
+    fn set_cat_count_spawns_and_despawns() {
+        let mut sim = Simulation::new(800, 600);
+        sim.set_cat_count(50);
+        assert_eq!(sim.cat_count(), 50);
+        sim.set_cat_count(10);
+        assert_eq!(sim.cat_count(), 10);
+    }
+}
@@ -1,11 +1,13 @@
 use glam::Vec2;
 
-use crate::ecs::components::{BehaviorState, Personality};
+use crate::ecs::components::{BehaviorState, CatId, Personality};
 
 /// Snapshot of a cat's state for interaction queries.
 /// Stored alongside the spatial hash to avoid ECS lookups in hot path.
 pub struct CatSnapshot {
     pub entity: hecs::Entity,
+    /// Stable id for relationship lookups; `None` for bare test cats.
+    pub id: Option<CatId>,
     pub pos: Vec2,
     pub vel: Vec2,
     pub state: BehaviorState,
@@ -42,6 +44,11 @@ impl SpatialHash {
         }
     }
 
+    /// Side length of one grid cell in pixels.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
     /// Clear all buckets. Call at start of each rebuild.
     pub fn clear(&mut self) {
         for bucket in &mut self.buckets {
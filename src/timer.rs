@@ -50,6 +50,12 @@ pub struct SystemTimers {
 
 const EMA_ALPHA: f64 = 0.1;
 
+impl Default for SystemTimers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SystemTimers {
     pub fn new() -> Self {
         Self {
@@ -1,237 +1,435 @@
-use glam::Vec2;
-
-// ---------------------------------------------------------------------------
-// Cardboard Box
-// ---------------------------------------------------------------------------
+//! Toys and treats. Every kind is plain data: a [`ToySpec`] saying how it
+//! moves, which cats it draws in and what they do with it, and how it's
+//! drawn. [`spawn`] turns a kind into an entity in the colony's world, and
+//! `systems::toys` runs them all alike. Adding a toy means adding a
+//! [`ToyKind`] and its spec.
+//!
+//! The feather wand is the exception: it hangs off the cursor instead of
+//! lying about.
 
-/// A cardboard box that cats can sit in.
-#[derive(Debug, Clone, Copy)]
-pub struct CardboardBox {
-    pub pos: Vec2,
-    pub lifetime: f32,
-    /// Number of cats currently inside (0-2).
-    pub occupants: u8,
-}
+use glam::Vec2;
 
-/// Manages cardboard boxes on screen.
-pub struct Boxes {
-    pub boxes: Vec<CardboardBox>,
+use crate::desktop::Desktop;
+use crate::ecs::components::{
+    Appeal, Collider, Drive, Lifetime, Occupancy, Play, Position, RigidBody, Shape, Toy, ToyBody,
+    ToySprite, Velocity,
+};
+
+/// Every kind of toy. Saved as `kind as u8`, so only ever append.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ToyKind {
+    /// Right click: rolls and bounces about for cats to bat.
+    Yarn,
+    /// Hotkey: cats sit in it.
+    Box,
+    /// Hotkey: cats knock it off the screen.
+    Glass,
+    /// Middle click: the first cat to reach it eats it.
+    Treat,
 }
 
-const MAX_BOXES: usize = 5;
-const BOX_LIFETIME: f32 = 60.0;
+impl ToyKind {
+    pub const ALL: [ToyKind; 4] = [ToyKind::Yarn, ToyKind::Box, ToyKind::Glass, ToyKind::Treat];
 
-impl Boxes {
-    pub fn new() -> Self {
-        Self {
-            boxes: Vec::with_capacity(MAX_BOXES),
-        }
+    /// Inverse of `kind as u8`, for save files.
+    pub fn from_u8(v: u8) -> Option<Self> {
+        Self::ALL.get(v as usize).copied()
     }
 
-    pub fn spawn(&mut self, pos: Vec2) {
-        if self.boxes.len() >= MAX_BOXES {
-            self.boxes.remove(0);
-        }
-        self.boxes.push(CardboardBox {
-            pos,
-            lifetime: BOX_LIFETIME,
-            occupants: 0,
-        });
-    }
-
-    pub fn update(&mut self, dt: f32) {
-        for b in &mut self.boxes {
-            b.lifetime -= dt;
-        }
-        self.boxes.retain(|b| b.lifetime > 0.0);
+    pub fn spec(self) -> &'static ToySpec {
+        &SPECS[self as usize]
     }
 }
 
-// ---------------------------------------------------------------------------
-// Water Glass
-// ---------------------------------------------------------------------------
-
-/// A water glass that cats knock off the screen edge.
-#[derive(Debug, Clone, Copy)]
-pub struct WaterGlass {
-    pub pos: Vec2,
-    pub vel: Vec2,
+/// Everything about one kind of toy.
+#[derive(Debug)]
+pub struct ToySpec {
+    /// Most of this kind out at once; another one clears away the oldest.
+    pub cap: usize,
+    /// Seconds before it's cleared away.
     pub lifetime: f32,
-    /// True when the glass has been shattered (reached edge).
-    pub shattered: bool,
+    pub body: ToyBody,
+    pub rigid_body: RigidBody,
+    pub collider: Collider,
+    pub appeal: Appeal,
+    pub sprite: ToySprite,
+    /// Cats that fit inside, for toys they sit in.
+    pub capacity: u8,
 }
 
-pub struct Glasses {
-    pub glasses: Vec<WaterGlass>,
-}
-
-const MAX_GLASSES: usize = 5;
-const GLASS_LIFETIME: f32 = 45.0;
-const GLASS_FRICTION: f32 = 0.97;
-
-impl Glasses {
-    pub fn new() -> Self {
-        Self {
-            glasses: Vec::with_capacity(MAX_GLASSES),
-        }
-    }
-
-    pub fn spawn(&mut self, pos: Vec2) {
-        if self.glasses.len() >= MAX_GLASSES {
-            self.glasses.remove(0);
-        }
-        self.glasses.push(WaterGlass {
-            pos,
-            vel: Vec2::ZERO,
-            lifetime: GLASS_LIFETIME,
-            shattered: false,
-        });
-    }
-
-    /// Update glass physics. Returns positions of shattered glasses for particle effects.
-    pub fn update(&mut self, dt: f32, screen_w: f32, screen_h: f32) -> Vec<Vec2> {
-        let mut shattered_positions = Vec::new();
-        let margin = 15.0;
-
-        for glass in &mut self.glasses {
-            if glass.shattered {
-                continue;
-            }
-
-            glass.pos += glass.vel * dt;
-            glass.vel *= GLASS_FRICTION;
-            glass.lifetime -= dt;
-
-            // Shatter when reaching screen edge
-            if glass.pos.x < margin || glass.pos.x > screen_w - margin
-                || glass.pos.y < margin || glass.pos.y > screen_h - margin
-            {
-                glass.shattered = true;
-                shattered_positions.push(glass.pos);
-            }
+/// Specs in [`ToyKind`] order.
+const SPECS: [ToySpec; 4] = [
+    // Yarn
+    ToySpec {
+        cap: 20,
+        lifetime: 30.0,
+        body: ToyBody {
+            push_radius: 120.0,
+            push_strength: 600.0,
+            fragile: false,
+        },
+        rigid_body: RigidBody {
+            inv_mass: 1.0,
+            damping: 0.3,
+            walls: true,
+        },
+        collider: Collider {
+            shape: Shape::Circle { radius: 22.0 },
+            restitution: 0.75,
+            hit_by_cats: true,
+        },
+        appeal: Appeal {
+            play: Play::Bat,
+            radius: 250.0,
+            reach: 45.0,
+            speed: 100.0,
+            strength: 250.0,
+            drive: Drive::Energy,
+            min_drive: 0.3,
+        },
+        sprite: ToySprite {
+            frame: 3,
+            size: [1.0, 1.0],
+            color: 0xDD333300,
+            fade: 5.0,
+            pulse: 0.0,
+        },
+        capacity: 0,
+    },
+    // Box
+    ToySpec {
+        cap: 5,
+        lifetime: 60.0,
+        body: ToyBody {
+            push_radius: 0.0,
+            push_strength: 0.0,
+            fragile: false,
+        },
+        rigid_body: RigidBody {
+            inv_mass: 0.0,
+            damping: 0.0,
+            walls: false,
+        },
+        collider: Collider {
+            shape: Shape::Rect {
+                half: Vec2::new(34.0, 22.0),
+            },
+            restitution: 0.3,
+            hit_by_cats: false,
+        },
+        appeal: Appeal {
+            play: Play::SitIn,
+            radius: 200.0,
+            reach: 25.0,
+            speed: 40.0,
+            strength: 0.0,
+            drive: Drive::Curiosity,
+            min_drive: 0.3,
+        },
+        sprite: ToySprite {
+            frame: 9,
+            size: [1.2, 1.2],
+            color: 0xC48A3F00,
+            fade: 5.0,
+            pulse: 0.0,
+        },
+        capacity: 2,
+    },
+    // Glass
+    ToySpec {
+        cap: 5,
+        lifetime: 45.0,
+        body: ToyBody {
+            push_radius: 0.0,
+            push_strength: 0.0,
+            fragile: true,
+        },
+        rigid_body: RigidBody {
+            inv_mass: 0.5,
+            damping: 1.8,
+            walls: false,
+        },
+        collider: Collider {
+            shape: Shape::Rect {
+                half: Vec2::new(11.0, 13.0),
+            },
+            restitution: 0.3,
+            hit_by_cats: true,
+        },
+        appeal: Appeal {
+            play: Play::Nudge,
+            radius: 30.0,
+            reach: 30.0,
+            speed: 0.0,
+            strength: 80.0,
+            drive: Drive::Energy,
+            min_drive: 0.0,
+        },
+        sprite: ToySprite {
+            frame: 10,
+            size: [0.7, 0.9],
+            color: 0x88CCFF00,
+            fade: 5.0,
+            pulse: 0.0,
+        },
+        capacity: 0,
+    },
+    // Treat
+    ToySpec {
+        cap: 10,
+        lifetime: 10.0,
+        body: ToyBody {
+            push_radius: 80.0,
+            push_strength: 300.0,
+            fragile: false,
+        },
+        rigid_body: RigidBody {
+            inv_mass: 2.0,
+            damping: 3.1,
+            walls: false,
+        },
+        collider: Collider {
+            shape: Shape::Circle { radius: 10.0 },
+            restitution: 0.2,
+            hit_by_cats: false,
+        },
+        appeal: Appeal {
+            play: Play::Eat,
+            radius: 300.0,
+            reach: 5.0,
+            speed: 80.0,
+            strength: 0.0,
+            drive: Drive::Curiosity,
+            min_drive: 0.0,
+        },
+        sprite: ToySprite {
+            frame: 5,
+            size: [0.7, 0.7],
+            color: 0xFFCC3300,
+            fade: 10.0,
+            pulse: 0.1,
+        },
+        capacity: 0,
+    },
+];
+
+/// Put a new `kind` of toy down at `pos`, clearing away the oldest of its
+/// kind if there are already [`ToySpec::cap`] of them.
+pub fn spawn(world: &mut hecs::World, kind: ToyKind, pos: Vec2) -> hecs::Entity {
+    let spec = kind.spec();
+    let mut same: Vec<(hecs::Entity, f32)> = world
+        .query::<(&Toy, &Lifetime)>()
+        .iter()
+        .filter(|(_, (toy, _))| toy.kind == kind)
+        .map(|(e, (_, life))| (e, life.0))
+        .collect();
+    if same.len() >= spec.cap {
+        // Same kind, same starting lifetime: the least left is the oldest.
+        same.sort_by(|a, b| a.1.total_cmp(&b.1));
+        for &(oldest, _) in &same[..=same.len() - spec.cap] {
+            let _ = world.despawn(oldest);
         }
-
-        // Remove expired or shattered glasses (keep shattered briefly for visual)
-        self.glasses.retain(|g| g.lifetime > 0.0);
-        shattered_positions
     }
 
-    /// Apply a push from a cat nudging the glass.
-    pub fn push(&mut self, index: usize, impulse: glam::Vec2) {
-        if let Some(glass) = self.glasses.get_mut(index) {
-            if !glass.shattered {
-                glass.vel += impulse;
-            }
-        }
+    let entity = world.spawn((
+        Position(pos),
+        Velocity(Vec2::ZERO),
+        Toy { kind },
+        Lifetime(spec.lifetime),
+        spec.body,
+        spec.rigid_body,
+        spec.collider,
+        spec.appeal,
+        spec.sprite,
+    ));
+    if spec.capacity > 0 {
+        let _ = world.insert_one(
+            entity,
+            Occupancy {
+                occupants: 0,
+                capacity: spec.capacity,
+            },
+        );
     }
+    entity
 }
 
 // ---------------------------------------------------------------------------
-// Yarn Ball
+// Feather Wand
 // ---------------------------------------------------------------------------
 
-/// A single yarn ball with physics.
-#[derive(Debug, Clone, Copy)]
-pub struct YarnBall {
-    pub pos: Vec2,
-    pub vel: Vec2,
-    pub lifetime: f32,
+/// Rope segments between the cursor and the feather.
+const WAND_SEGMENTS: usize = 8;
+/// Length of one rope segment.
+const WAND_SEGMENT_LEN: f32 = 14.0;
+/// Full string length, cursor to feather.
+pub const WAND_LENGTH: f32 = WAND_SEGMENTS as f32 * WAND_SEGMENT_LEN;
+/// Downward pull on the string (pixels/second²).
+const WAND_GRAVITY: f32 = 900.0;
+/// Fraction of its velocity a rope point keeps each substep.
+const WAND_DAMPING: f32 = 0.995;
+/// Physics substeps per tick, so fast flicks don't stretch the string.
+const WAND_SUBSTEPS: usize = 4;
+/// Length-constraint passes per substep.
+const WAND_ITERATIONS: usize = 4;
+/// The feather keeps this far inside monitor walls.
+const WAND_MARGIN: f32 = 6.0;
+/// The feather swings low when it hangs at least this fraction of the
+/// string below the cursor...
+const WAND_LOW: f32 = 0.85;
+/// ...while moving at least this fast.
+const WAND_SWING_SPEED: f32 = 80.0;
+
+/// A feather on a string dangling from the cursor while switched on. The
+/// string is a verlet rope pinned to the cursor: it swings as the cursor
+/// moves and settles back underneath when it stops.
+pub struct FeatherWand {
+    pub active: bool,
+    /// Rope points from the cursor end (index 0) down to the feather.
+    pub points: [Vec2; WAND_SEGMENTS + 1],
+    /// Each point's position one substep ago (its velocity, verlet-style).
+    prev: [Vec2; WAND_SEGMENTS + 1],
+    /// Feather velocity over the last tick, in pixels/second.
+    pub feather_vel: Vec2,
+    /// Length of the last substep, for turning impulses into verlet offsets.
+    step: f32,
 }
 
-/// Manages multiple yarn balls.
-pub struct YarnBalls {
-    pub balls: Vec<YarnBall>,
+impl Default for FeatherWand {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-/// Max yarn balls on screen at once.
-const MAX_YARN_BALLS: usize = 20;
-/// Yarn ball lifetime in seconds (auto-despawn).
-const YARN_LIFETIME: f32 = 30.0;
-/// Friction applied each frame.
-const YARN_FRICTION: f32 = 0.995;
-/// Minimum speed before yarn ball stops.
-const YARN_MIN_SPEED: f32 = 5.0;
-/// Bounce elasticity.
-const YARN_BOUNCE: f32 = 0.75;
-/// Mouse push radius.
-const MOUSE_PUSH_RADIUS: f32 = 120.0;
-/// Mouse push strength.
-const MOUSE_PUSH_STRENGTH: f32 = 600.0;
-
-impl YarnBalls {
+impl FeatherWand {
     pub fn new() -> Self {
         Self {
-            balls: Vec::with_capacity(MAX_YARN_BALLS),
+            active: false,
+            points: [Vec2::ZERO; WAND_SEGMENTS + 1],
+            prev: [Vec2::ZERO; WAND_SEGMENTS + 1],
+            feather_vel: Vec2::ZERO,
+            step: 1.0 / 240.0,
         }
     }
 
-    /// Spawn a new yarn ball at position.
-    pub fn spawn(&mut self, pos: Vec2) {
-        // Remove oldest if at capacity
-        if self.balls.len() >= MAX_YARN_BALLS {
-            self.balls.remove(0);
+    /// Switch the wand on or off. It comes out hanging straight down from
+    /// `anchor`.
+    pub fn toggle(&mut self, anchor: Vec2) {
+        self.active = !self.active;
+        if self.active {
+            for (i, p) in self.points.iter_mut().enumerate() {
+                *p = anchor + Vec2::Y * (i as f32 * WAND_SEGMENT_LEN);
+            }
+            self.prev = self.points;
+            self.feather_vel = Vec2::ZERO;
         }
-        self.balls.push(YarnBall {
-            pos,
-            vel: Vec2::ZERO,
-            lifetime: YARN_LIFETIME,
-        });
     }
 
-    /// Update all yarn balls: physics, mouse push, bouncing, lifetime.
-    pub fn update(&mut self, dt: f32, screen_w: f32, screen_h: f32, mouse_pos: Vec2) {
-        for ball in &mut self.balls {
-            // Mouse pushes yarn balls
-            let to_ball = ball.pos - mouse_pos;
-            let dist = to_ball.length();
-            if dist < MOUSE_PUSH_RADIUS && dist > 1.0 {
-                let push_dir = to_ball / dist;
-                let push_strength = (1.0 - dist / MOUSE_PUSH_RADIUS) * MOUSE_PUSH_STRENGTH;
-                ball.vel += push_dir * push_strength * dt;
-            }
+    /// Where the feather is.
+    pub fn feather(&self) -> Vec2 {
+        self.points[WAND_SEGMENTS]
+    }
 
-            ball.pos += ball.vel * dt;
-            ball.vel *= YARN_FRICTION;
-            ball.lifetime -= dt;
+    /// Whether the feather is sweeping through the bottom of its arc: the
+    /// moment cats bat and jump at it.
+    pub fn swinging_low(&self) -> bool {
+        self.active
+            && self.feather().y - self.points[0].y > WAND_LENGTH * WAND_LOW
+            && self.feather_vel.length_squared() > WAND_SWING_SPEED * WAND_SWING_SPEED
+    }
 
-            // Bounce off walls
-            let margin = 10.0;
-            if ball.pos.x < margin {
-                ball.pos.x = margin;
-                ball.vel.x = ball.vel.x.abs() * YARN_BOUNCE;
-            }
-            if ball.pos.x > screen_w - margin {
-                ball.pos.x = screen_w - margin;
-                ball.vel.x = -ball.vel.x.abs() * YARN_BOUNCE;
-            }
-            if ball.pos.y < margin {
-                ball.pos.y = margin;
-                ball.vel.y = ball.vel.y.abs() * YARN_BOUNCE;
-            }
-            if ball.pos.y > screen_h - margin {
-                ball.pos.y = screen_h - margin;
-                ball.vel.y = -ball.vel.y.abs() * YARN_BOUNCE;
+    /// Step the string for one tick. The pinned end follows the cursor from
+    /// where it was last tick to `anchor` at `anchor_vel`, spread over the
+    /// substeps so a fast flick whips the feather instead of yanking it.
+    pub fn update(&mut self, dt: f32, desktop: &Desktop, anchor: Vec2, anchor_vel: Vec2) {
+        if !self.active {
+            return;
+        }
+        let h = dt / WAND_SUBSTEPS as f32;
+        self.step = h;
+        let start = self.feather();
+
+        for k in 1..=WAND_SUBSTEPS {
+            let pin = anchor - anchor_vel * (dt - h * k as f32);
+            self.points[0] = pin;
+            self.prev[0] = pin;
+
+            for i in 1..=WAND_SEGMENTS {
+                let p = self.points[i];
+                let vel = (p - self.prev[i]) * WAND_DAMPING;
+                self.prev[i] = p;
+                self.points[i] = p + vel + Vec2::Y * WAND_GRAVITY * h * h;
             }
 
-            // Stop if very slow
-            if ball.vel.length_squared() < YARN_MIN_SPEED * YARN_MIN_SPEED {
-                ball.vel = Vec2::ZERO;
+            for _ in 0..WAND_ITERATIONS {
+                for i in 0..WAND_SEGMENTS {
+                    let delta = self.points[i + 1] - self.points[i];
+                    let dist = delta.length();
+                    if dist < 1e-4 {
+                        continue;
+                    }
+                    let correction = delta * ((dist - WAND_SEGMENT_LEN) / dist);
+                    if i == 0 {
+                        // The cursor end is pinned; the next point takes it all.
+                        self.points[1] -= correction;
+                    } else {
+                        self.points[i] += correction * 0.5;
+                        self.points[i + 1] -= correction * 0.5;
+                    }
+                }
             }
+
+            let last = WAND_SEGMENTS;
+            self.points[last] = desktop.confine(self.prev[last], self.points[last], WAND_MARGIN);
         }
 
-        // Remove expired
-        self.balls.retain(|b| b.lifetime > 0.0);
+        self.feather_vel = (self.feather() - start) / dt.max(0.001);
     }
 
-    /// Apply a bat impulse to a specific ball by index.
-    pub fn bat(&mut self, index: usize, impulse: Vec2) {
-        if let Some(ball) = self.balls.get_mut(index) {
-            ball.vel += impulse;
-        }
+    /// Knock the feather with `impulse` (pixels/second).
+    pub fn bat(&mut self, impulse: Vec2) {
+        self.prev[WAND_SEGMENTS] -= impulse * self.step;
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feather_wand_swings_with_the_cursor_and_settles() {
+        let desktop = Desktop::single(800, 600);
+        let mut wand = FeatherWand::new();
+        let dt = 1.0 / 60.0;
+        let mut anchor = Vec2::new(200.0, 100.0);
+        wand.toggle(anchor);
+        assert_eq!(wand.feather(), anchor + Vec2::Y * WAND_LENGTH);
+
+        // Drag the cursor right: the feather trails behind, then swings through.
+        let vel = Vec2::new(600.0, 0.0);
+        for _ in 0..20 {
+            anchor += vel * dt;
+            wand.update(dt, &desktop, anchor, vel);
+        }
+        assert!(wand.feather().x < anchor.x - 10.0, "trails {:?}", wand.feather());
+        let mut swung_low = false;
+        for _ in 0..120 {
+            wand.update(dt, &desktop, anchor, Vec2::ZERO);
+            swung_low |= wand.swinging_low();
+        }
+        assert!(swung_low);
+
+        // The string never stretches much, and hangs still in the end.
+        for _ in 0..1200 {
+            wand.update(dt, &desktop, anchor, Vec2::ZERO);
+        }
+        let span = wand.feather().distance(anchor);
+        assert!(span <= WAND_LENGTH * 1.02 && span > WAND_LENGTH * 0.95, "{span}");
+        assert!((wand.feather().x - anchor.x).abs() < 2.0);
+        assert!(!wand.swinging_low());
 
-    /// Are there any active yarn balls?
-    pub fn any_active(&self) -> bool {
-        !self.balls.is_empty()
+        wand.toggle(anchor);
+        assert!(!wand.active);
     }
 }